@@ -1,5 +1,6 @@
 use casper_types::ApiError;
 
+#[derive(Debug, PartialEq, Eq)]
 #[repr(u16)]
 pub enum Error {
     /// ERC20 CRV ERRORS
@@ -95,6 +96,114 @@ pub enum Error {
     Erc20CRVOverFlow23 = 10045,
     /// (ERC20 CRV Over flow24)
     Erc20CRVOverFlow24 = 10046,
+    /// (ERC20 CRV Over flow25)
+    Erc20CRVOverFlow25 = 10047,
+    /// (ERC20 CRV Over flow26)
+    Erc20CRVOverFlow26 = 10048,
+    /// (ERC20 CRV Over flow27)
+    Erc20CRVOverFlow27 = 10049,
+    /// (ERC20 CRV Over flow28)
+    Erc20CRVOverFlow28 = 10050,
+    /// (ERC20 CRV Over flow29)
+    Erc20CRVOverFlow29 = 10051,
+    /// (ERC20 CRV Over flow30)
+    Erc20CRVOverFlow30 = 10052,
+    /// (ERC20 CRV Over flow31)
+    Erc20CRVOverFlow31 = 10053,
+    /// (ERC20 CRV Over flow32)
+    Erc20CRVOverFlow32 = 10054,
+    /// (ERC20 CRV Over flow33)
+    Erc20CRVOverFlow33 = 10055,
+    /// (ERC20 CRV Over flow34)
+    Erc20CRVOverFlow34 = 10056,
+    /// (ERC20 CRV Over flow35)
+    Erc20CRVOverFlow35 = 10057,
+    /// (ERC20 CRV Under flow6)
+    Erc20CRVUnderFlow6 = 10058,
+    /// (ERC20 CRV Under flow7)
+    Erc20CRVUnderFlow7 = 10059,
+    /// (ERC20 CRV Under flow8)
+    Erc20CRVUnderFlow8 = 10060,
+    /// (ERC20 CRV Airthmetic Error6)
+    Erc20CRVAirthmeticError6 = 10061,
+    /// (ERC20 CRV Airthmetic Error7)
+    Erc20CRVAirthmeticError7 = 10062,
+    /// (ERC20 CRV Over flow36)
+    Erc20CRVOverFlow36 = 10063,
+    /// (ERC20 CRV Under flow9)
+    Erc20CRVUnderFlow9 = 10064,
+    /// (ERC20 CRV Schedule Too Long)
+    Erc20CRVScheduleTooLong = 10065,
+    /// (ERC20 CRV Under flow10)
+    Erc20CRVUnderFlow10 = 10066,
+    /// (ERC20 CRV Over flow37)
+    Erc20CRVOverFlow37 = 10067,
+    /// (ERC20 CRV Over flow38)
+    Erc20CRVOverFlow38 = 10068,
+    /// (ERC20 CRV Airthmetic Error8)
+    Erc20CRVAirthmeticError8 = 10069,
+    /// (ERC20 CRV Under flow11)
+    Erc20CRVUnderFlow11 = 10070,
+    /// (ERC20 CRV Admin Only3)
+    Erc20CRVAdminOnly3 = 10071,
+    /// (ERC20 CRV Admin Only4)
+    Erc20CRVAdminOnly4 = 10072,
+    /// (ERC20 CRV Zero Address2)
+    Erc20CRVZeroAddress2 = 10073,
+    /// (ERC20 CRV Invalid Quota)
+    Erc20CRVInvalidQuota = 10074,
+    /// (ERC20 CRV Share Too Large)
+    Erc20CRVShareTooLarge = 10075,
+    /// (ERC20 CRV Minter Not Found)
+    Erc20CRVMinterNotFound = 10076,
+    /// (ERC20 CRV Exceeds Minter Allowance)
+    Erc20CRVExceedsMinterAllowance = 10077,
+    /// (ERC20 CRV Over flow39)
+    Erc20CRVOverFlow39 = 10078,
+    /// (ERC20 CRV Over flow40)
+    Erc20CRVOverFlow40 = 10079,
+    /// (ERC20 CRV Airthmetic Error9)
+    Erc20CRVAirthmeticError9 = 10080,
+    /// (ERC20 CRV Over flow41)
+    Erc20CRVOverFlow41 = 10081,
+    /// (ERC20 CRV Admin Only5)
+    Erc20CRVAdminOnly5 = 10082,
+    /// (ERC20 CRV History Already Enabled)
+    Erc20CRVHistoryAlreadyEnabled = 10083,
+    /// (ERC20 CRV History Disabled1)
+    Erc20CRVHistoryDisabled1 = 10084,
+    /// (ERC20 CRV History Disabled2)
+    Erc20CRVHistoryDisabled2 = 10085,
+    /// (ERC20 CRV Before History Start1)
+    Erc20CRVBeforeHistoryStart1 = 10086,
+    /// (ERC20 CRV Before History Start2)
+    Erc20CRVBeforeHistoryStart2 = 10087,
+    /// (ERC20 CRV Over flow42)
+    Erc20CRVOverFlow42 = 10088,
+    /// (ERC20 CRV Over flow43)
+    Erc20CRVOverFlow43 = 10089,
+    /// (ERC20 CRV Under flow12)
+    Erc20CRVUnderFlow12 = 10090,
+    /// (ERC20 CRV Under flow13)
+    Erc20CRVUnderFlow13 = 10091,
+    /// (ERC20 CRV Over flow44)
+    Erc20CRVOverFlow44 = 10092,
+    /// (ERC20 CRV Over flow45)
+    Erc20CRVOverFlow45 = 10093,
+    /// (ERC20 CRV Under flow14)
+    Erc20CRVUnderFlow14 = 10094,
+    /// (ERC20 CRV Under flow15)
+    Erc20CRVUnderFlow15 = 10095,
+    /// (ERC20 CRV Under flow16)
+    Erc20CRVUnderFlow16 = 10096,
+    /// (ERC20 CRV Over flow46)
+    Erc20CRVOverFlow46 = 10097,
+    /// (ERC20 CRV Total Share Too Large)
+    Erc20CRVTotalShareTooLarge = 10098,
+    /// (ERC20 CRV No Share Left)
+    Erc20CRVNoShareLeft = 10099,
+    /// (ERC20 CRV Under flow17)
+    Erc20CRVUnderFlow17 = 10100,
 
     /// (Liquidity Gauge Reward Unauthorized)
     LiquidityGaugeRewardUnauthorized = 10101,
@@ -704,6 +813,8 @@ pub enum Error {
     GaugeControllerOverFlow29 = 10797,
     ///(Gauge Controller OverFlow30)
     GaugeControllerOverFlow30 = 10798,
+    ///(Gauge Controller UnderFlow25)
+    GaugeControllerUnderFlow25 = 10799,
 
     ///(Minter Gauge Is Not Added)
     MinterIsNotAdded = 10801,
@@ -713,6 +824,64 @@ pub enum Error {
     MinterLocked2 = 10803,
     ///(Minter Gauge Locked)
     MinterLocked3 = 10804,
+    ///(Minter Gauge Is Killed)
+    MinterGaugeIsKilled = 10805,
+    ///(Minter Not Admin)
+    MinterNotAdmin1 = 10806,
+    ///(Minter Not Admin)
+    MinterNotAdmin2 = 10807,
+    ///(Minter Token Address Zero)
+    MinterTokenAddressZero = 10808,
+    ///(Minter Emission Token Already Added)
+    MinterEmissionTokenAlreadyAdded = 10809,
+    ///(Minter Unknown Emission Token)
+    MinterUnknownEmissionToken = 10810,
+    ///(Minter Under Flow)
+    MinterUnderFlow1 = 10811,
+    ///(Minter Over Flow)
+    MinterOverFlow1 = 10812,
+    ///(Minter Under Flow)
+    MinterUnderFlow2 = 10813,
+    ///(Minter Over Flow)
+    MinterOverFlow2 = 10814,
+    ///(Minter Not Allowed To Mint For)
+    MinterNotAllowedToMintFor = 10815,
+    ///(Minter Signature Expired)
+    MinterSignatureExpired = 10816,
+    ///(Minter Invalid Signer)
+    MinterInvalidSigner = 10817,
+    ///(Minter Unsupported Public Key)
+    MinterUnsupportedPublicKey = 10818,
+    ///(Minter Invalid Signature)
+    MinterInvalidSignature = 10819,
+    ///(Minter Gauge Locked)
+    MinterLocked4 = 10820,
+    ///(Minter Over Flow)
+    MinterOverFlow3 = 10821,
+    ///(Minter Gauge Locked)
+    MinterLocked5 = 10822,
+    ///(Minter Gauge Token Can Not Be Locked)
+    MinterCannotLockGaugeToken = 10823,
+    ///(Minter Nothing To Lock)
+    MinterNothingToLock = 10824,
+    ///(Minter Unlock Time Required)
+    MinterUnlockTimeRequired = 10825,
+    ///(Minter Over Flow)
+    MinterOverFlow4 = 10826,
+    ///(Minter Not Admin)
+    MinterNotAdmin3 = 10827,
+    ///(Minter Not Admin)
+    MinterNotAdmin4 = 10828,
+    ///(Minter Admin Not Set)
+    MinterAdminNotSet = 10829,
+    ///(Minter Claimable Page Too Large)
+    MinterClaimablePageTooLarge = 10830,
+    ///(Minter OverFlow)
+    MinterOverFlow5 = 10831,
+    ///(Minter Lock Expired)
+    MinterLockExpired = 10832,
+    ///(Minter Voting Escrow Unlocked)
+    MinterVotingEscrowUnlocked = 10833,
 
     /// (Reward Only Gauge OverFlow1)
     RewardOnlyGaugeOverFlow1 = 10901,
@@ -1076,6 +1245,192 @@ pub enum Error {
     VotingEscrowInvalidBlockNumber1 = 11406,
     /// (Voting Escrow Invalid Block Number2)
     VotingEscrowInvalidBlockNumber2 = 11407,
+    /// (Voting Escrow Cannot Boost Self)
+    VotingEscrowCannotBoostSelf = 11408,
+    /// (Voting Escrow Invalid Boost Percentage)
+    VotingEscrowInvalidBoostPercentage = 11409,
+    /// (Voting Escrow Invalid Boost Expire Time)
+    VotingEscrowInvalidBoostExpireTime = 11410,
+    /// (Voting Escrow Boost Already Exists)
+    VotingEscrowBoostAlreadyExists = 11411,
+    /// (Voting Escrow No Active Boost)
+    VotingEscrowNoActiveBoost = 11412,
+    /// (Voting Escrow No Boost Available)
+    VotingEscrowNoBoostAvailable = 11413,
+    /// (Voting Escrow Cannot Decrease Boost)
+    VotingEscrowCannotDecreaseBoost = 11414,
+    /// (Voting Escrow Not Boost Delegator Or Receiver)
+    VotingEscrowNotBoostDelegatorOrReceiver = 11415,
+    /// (Voting Escrow Boost Arithmetic Error 1)
+    VotingEscrowBoostArithmeticError1 = 11416,
+    /// (Voting Escrow Boost Arithmetic Error 2)
+    VotingEscrowBoostArithmeticError2 = 11417,
+    /// (Voting Escrow Boost Arithmetic Error 3)
+    VotingEscrowBoostArithmeticError3 = 11418,
+    /// (Voting Escrow Boost Arithmetic Error 4)
+    VotingEscrowBoostArithmeticError4 = 11419,
+    /// (Voting Escrow Boost Arithmetic Error 5)
+    VotingEscrowBoostArithmeticError5 = 11420,
+    /// (Voting Escrow Boost Arithmetic Error 6)
+    VotingEscrowBoostArithmeticError6 = 11421,
+    /// (Voting Escrow Boost Arithmetic Error 7)
+    VotingEscrowBoostArithmeticError7 = 11422,
+    /// (Voting Escrow Boost Arithmetic Error 8)
+    VotingEscrowBoostArithmeticError8 = 11423,
+    /// (Voting Escrow Boost Arithmetic Error 9)
+    VotingEscrowBoostArithmeticError9 = 11424,
+    /// (Voting Escrow Boost Arithmetic Error 10)
+    VotingEscrowBoostArithmeticError10 = 11425,
+    /// (Voting Escrow Boost Arithmetic Error 11)
+    VotingEscrowBoostArithmeticError11 = 11426,
+    /// (Voting Escrow Boost Arithmetic Error 12)
+    VotingEscrowBoostArithmeticError12 = 11427,
+    /// (Voting Escrow Boost Arithmetic Error 13)
+    VotingEscrowBoostArithmeticError13 = 11428,
+    /// (Voting Escrow Boost Arithmetic Error 14)
+    VotingEscrowBoostArithmeticError14 = 11429,
+    /// (Voting Escrow Boost Arithmetic Error 15)
+    VotingEscrowBoostArithmeticError15 = 11430,
+    /// (Voting Escrow Boost Arithmetic Error 16)
+    VotingEscrowBoostArithmeticError16 = 11431,
+    /// (Voting Escrow Boost Arithmetic Error 17)
+    VotingEscrowBoostArithmeticError17 = 11432,
+    /// (Voting Escrow Boost Arithmetic Error 18)
+    VotingEscrowBoostArithmeticError18 = 11433,
+    /// (Voting Escrow Boost Arithmetic Error 19)
+    VotingEscrowBoostArithmeticError19 = 11434,
+    /// (Voting Escrow Boost Arithmetic Error 20)
+    VotingEscrowBoostArithmeticError20 = 11435,
+    /// (Voting Escrow Boost Arithmetic Error 21)
+    VotingEscrowBoostArithmeticError21 = 11436,
+    /// (Voting Escrow Boost Arithmetic Error 22)
+    VotingEscrowBoostArithmeticError22 = 11437,
+    /// (Voting Escrow Boost Arithmetic Error 23)
+    VotingEscrowBoostArithmeticError23 = 11438,
+    /// (Voting Escrow Smart contract depositors not allowed)
+    VotingEscrowSmartContractDepositorsNotAllowed = 11439,
+    /// (Voting Escrow Block height feeder only)
+    VotingEscrowBlockHeightFeederOnly = 11440,
+    /// (Voting Escrow Block height must increase)
+    VotingEscrowBlockHeightMustIncrease = 11441,
+    /// (Voting Escrow Block height not recorded)
+    VotingEscrowBlockHeightNotRecorded = 11442,
+    /// (Voting Escrow Invalid Timestamp 1)
+    VotingEscrowInvalidTimestamp1 = 11443,
+    /// (Voting Escrow Invalid Timestamp 2)
+    VotingEscrowInvalidTimestamp2 = 11444,
+    /// (Voting Escrow Block height arithmetic error 1)
+    VotingEscrowBlockHeightArithmeticError1 = 11445,
+    /// (Voting Escrow Is Locked5)
+    VotingEscrowIsLocked5 = 11446,
+    /// (Voting Escrow Not Approved Depositor)
+    VotingEscrowNotApprovedDepositor = 11447,
+    /// (Voting Escrow Need Non Zero Value4)
+    VotingEscrowNeedNonZeroValue4 = 11448,
+    /// (Voting Escrow Withdraw Old Tokens First2)
+    VotingEscrowWithdrawOldTokensFirst2 = 11449,
+    /// (Voting Escrow Can Only Lock Until Time In The Future2)
+    VotingEscrowCanOnlyLockUntilTimeInTheFuture2 = 11450,
+    /// (Voting Escrow Voting Lock Can Be 4 Years Max3)
+    VotingEscrowVotingLockCanBe4YearsMax3 = 11451,
+    /// (Voting Escrow Division Error 13)
+    VotingEscrowDivisionError13 = 11452,
+    /// (Voting Escrow Multiplication Error 17)
+    VotingEscrowMultiplicationError17 = 11453,
+    /// (Voting Escrow Addition Error 28)
+    VotingEscrowAdditionError28 = 11454,
+    /// (Voting Escrow Early Withdraw Disabled)
+    VotingEscrowEarlyWithdrawDisabled = 11455,
+    /// (Voting Escrow Penalty Receiver Not Set)
+    VotingEscrowPenaltyReceiverNotSet = 11456,
+    /// (Voting Escrow Nothing Is Locked2)
+    VotingEscrowNothingIsLocked2 = 11457,
+    /// (Voting Escrow Cancel Boosts First)
+    VotingEscrowCancelBoostsFirst = 11458,
+    /// (Voting Escrow Penalty Arithmetic Error 1)
+    VotingEscrowPenaltyArithmeticError1 = 11459,
+    /// (Voting Escrow Penalty Arithmetic Error 2)
+    VotingEscrowPenaltyArithmeticError2 = 11460,
+    /// (Voting Escrow Penalty Arithmetic Error 3)
+    VotingEscrowPenaltyArithmeticError3 = 11461,
+    /// (Voting Escrow Penalty Arithmetic Error 4)
+    VotingEscrowPenaltyArithmeticError4 = 11462,
+    /// (Voting Escrow Is Locked 6)
+    VotingEscrowIsLocked6 = 11463,
+    /// (Voting Escrow Is Locked 7)
+    VotingEscrowIsLocked7 = 11464,
+    /// (Voting Escrow Invalid Lock Id 1)
+    VotingEscrowInvalidLockId1 = 11465,
+    /// (Voting Escrow Invalid Lock Id 2)
+    VotingEscrowInvalidLockId2 = 11466,
+    /// (Voting Escrow Invalid Lock Id 3)
+    VotingEscrowInvalidLockId3 = 11467,
+    /// (Voting Escrow Cannot Merge Lock Into Itself)
+    VotingEscrowCannotMergeLockIntoItself = 11468,
+    /// (Voting Escrow Nothing Is Locked 3)
+    VotingEscrowNothingIsLocked3 = 11469,
+    /// (Voting Escrow Nothing Is Locked 4)
+    VotingEscrowNothingIsLocked4 = 11470,
+    /// (Voting Escrow Lock Expired 2)
+    VotingEscrowLockExpired2 = 11471,
+    /// (Voting Escrow Lock Expired 3)
+    VotingEscrowLockExpired3 = 11472,
+    /// (Voting Escrow Cancel Boosts First 2)
+    VotingEscrowCancelBoostsFirst2 = 11473,
+    /// (Voting Escrow Cancel Boosts First 3)
+    VotingEscrowCancelBoostsFirst3 = 11474,
+    /// (Voting Escrow Need Non Zero Value 5)
+    VotingEscrowNeedNonZeroValue5 = 11475,
+    /// (Voting Escrow Split Exceeds Lock)
+    VotingEscrowSplitExceedsLock = 11476,
+    /// (Voting Escrow The Lock Didnt Expire 2)
+    VotingEscrowTheLockDidntExpire2 = 11477,
+    /// (Voting Escrow Lock Id Arithmetic Error 1)
+    VotingEscrowLockIdArithmeticError1 = 11478,
+    /// (Voting Escrow Lock Id Arithmetic Error 2)
+    VotingEscrowLockIdArithmeticError2 = 11479,
+    /// (Voting Escrow Lock Id Arithmetic Error 3)
+    VotingEscrowLockIdArithmeticError3 = 11480,
+    /// (Voting Escrow Lock Id Arithmetic Error 4)
+    VotingEscrowLockIdArithmeticError4 = 11481,
+    /// (Voting Escrow Lock Id Arithmetic Error 5)
+    VotingEscrowLockIdArithmeticError5 = 11482,
+    /// (Voting Escrow Lock Id Arithmetic Error 6)
+    VotingEscrowLockIdArithmeticError6 = 11483,
+    /// (Voting Escrow Lock Id Arithmetic Error 7)
+    VotingEscrowLockIdArithmeticError7 = 11484,
+    /// (Voting Escrow Lock Id Arithmetic Error 8)
+    VotingEscrowLockIdArithmeticError8 = 11485,
+    /// (Voting Escrow Checkpoint Too Stale, call checkpoint_bounded first)
+    VotingEscrowCheckpointTooStale = 11486,
+    /// (Voting Escrow Need Non Zero Weeks)
+    VotingEscrowNeedNonZeroWeeks = 11487,
+    /// (Voting Escrow Checkpoint Arithmetic Error 1)
+    VotingEscrowCheckpointArithmeticError1 = 11488,
+    /// (Voting Escrow Checkpoint Arithmetic Error 2)
+    VotingEscrowCheckpointArithmeticError2 = 11489,
+    /// (Voting Escrow Checkpoint Arithmetic Error 3)
+    VotingEscrowCheckpointArithmeticError3 = 11490,
+    /// (Voting Escrow Invalid Time Range)
+    VotingEscrowInvalidTimeRange = 11491,
+    /// (Voting Escrow Too Many Timestamps 1)
+    VotingEscrowTooManyTimestamps1 = 11492,
+    /// (Voting Escrow Too Many Timestamps 2)
+    VotingEscrowTooManyTimestamps2 = 11493,
+    /// (Voting Escrow Too Many Timestamps 3)
+    VotingEscrowTooManyTimestamps3 = 11494,
+    /// (Voting Escrow History Arithmetic Error 1)
+    VotingEscrowHistoryArithmeticError1 = 11495,
+    /// (Voting Escrow Is Locked 8)
+    VotingEscrowIsLocked8 = 11496,
+    /// (Voting Escrow Perpetual Lock Unchanged)
+    VotingEscrowPerpetualLockUnchanged = 11497,
+    /// (Voting Escrow Nothing Is Locked 5)
+    VotingEscrowNothingIsLocked5 = 11498,
+    /// (Voting Escrow Lock Expired 4)
+    VotingEscrowLockExpired4 = 11499,
+    /// (Voting Escrow Cancel Boosts First 4)
+    VotingEscrowCancelBoostsFirst4 = 11500,
 
     /// (Liquidity Gauge Wrapper Unauthorized)
     GaugeWrapperUnauthorized = 11501,
@@ -1357,6 +1712,255 @@ pub enum Error {
     GaugeProxyIsLocked2 = 11982,
     /// (Gauge Proxy Is Locked)
     GaugeProxyIsLocked3 = 11983,
+
+    /// (Smart Wallet Checker Admin Only)
+    SmartWalletCheckerAdminOnly = 12001,
+    /// (Smart Wallet Checker Zero Address)
+    SmartWalletCheckerZeroAddress = 12002,
+
+    /// (Voting Escrow Perpetual Lock 1)
+    VotingEscrowPerpetualLock1 = 12101,
+    /// (Voting Escrow Perpetual Lock 2)
+    VotingEscrowPerpetualLock2 = 12102,
+    /// (Voting Escrow Perpetual Lock 3)
+    VotingEscrowPerpetualLock3 = 12103,
+    /// (Voting Escrow Perpetual Lock 4)
+    VotingEscrowPerpetualLock4 = 12104,
+    /// (Voting Escrow Perpetual Lock 5)
+    VotingEscrowPerpetualLock5 = 12105,
+    /// (Voting Escrow Perpetual Lock 6)
+    VotingEscrowPerpetualLock6 = 12106,
+    /// (Voting Escrow Perpetual Arithmetic Error 1)
+    VotingEscrowPerpetualArithmeticError1 = 12107,
+    /// (Voting Escrow Perpetual Arithmetic Error 2)
+    VotingEscrowPerpetualArithmeticError2 = 12108,
+    /// (Voting Escrow Perpetual Arithmetic Error 3)
+    VotingEscrowPerpetualArithmeticError3 = 12109,
+    /// (Voting Escrow Perpetual Arithmetic Error 4)
+    VotingEscrowPerpetualArithmeticError4 = 12110,
+    /// (Voting Escrow Perpetual Arithmetic Error 5)
+    VotingEscrowPerpetualArithmeticError5 = 12111,
+    /// (Voting Escrow Unlocked, all locks were released)
+    VotingEscrowUnlocked = 12112,
+    /// (Voting Escrow Already Unlocked)
+    VotingEscrowAlreadyUnlocked = 12113,
+    /// (Voting Escrow Find Timestamp Epoch Arithmetic Error 1)
+    VotingEscrowFindTimestampEpochError1 = 12114,
+    /// (Voting Escrow Find Timestamp Epoch Arithmetic Error 2)
+    VotingEscrowFindTimestampEpochError2 = 12115,
+    /// (Voting Escrow Find Timestamp Epoch Arithmetic Error 3)
+    VotingEscrowFindTimestampEpochError3 = 12116,
+    /// (Voting Escrow Find Timestamp Epoch Arithmetic Error 4)
+    VotingEscrowFindTimestampEpochError4 = 12117,
+    /// (Voting Escrow Too Many Locks)
+    VotingEscrowTooManyLocks = 12118,
+    /// (Voting Escrow Lock Id Arithmetic Error 9)
+    VotingEscrowLockIdArithmeticError9 = 12119,
+    /// (Voting Escrow Invalid Boost Cancel Time)
+    VotingEscrowInvalidBoostCancelTime = 12120,
+    /// (Voting Escrow Boost Not Cancellable Yet)
+    VotingEscrowBoostNotCancellable = 12121,
+
+    /// (Gauge Controller Not Admin5)
+    GaugeControllerNotAdmin5 = 12201,
+    /// (Gauge Controller Gauge not added 2)
+    GaugeControllerGaugeNotAdded2 = 12202,
+    /// (Gauge Controller Gauge already killed)
+    GaugeControllerGaugeAlreadyKilled = 12203,
+    /// (Gauge Controller Gauge is killed 1)
+    GaugeControllerGaugeIsKilled1 = 12204,
+    /// (Gauge Controller Gauge is killed 2)
+    GaugeControllerGaugeIsKilled2 = 12205,
+    /// (Gauge Controller Gauge is not killed)
+    GaugeControllerGaugeNotKilled = 12206,
+    /// (Gauge Controller No voting power to reclaim)
+    GaugeControllerNothingToReclaim = 12207,
+    /// (Gauge Controller Kill Arithmetic Error 1)
+    GaugeControllerKillArithmeticError1 = 12208,
+    /// (Gauge Controller Kill Arithmetic Error 2)
+    GaugeControllerKillArithmeticError2 = 12209,
+    /// (Gauge Controller Kill Arithmetic Error 3)
+    GaugeControllerKillArithmeticError3 = 12210,
+    /// (Gauge Controller Kill Arithmetic Error 4)
+    GaugeControllerKillArithmeticError4 = 12211,
+    /// (Gauge Controller Kill Arithmetic Error 5)
+    GaugeControllerKillArithmeticError5 = 12212,
+    /// (Gauge Controller Kill Arithmetic Error 6)
+    GaugeControllerKillArithmeticError6 = 12213,
+    /// (Gauge Controller Kill Arithmetic Error 7)
+    GaugeControllerKillArithmeticError7 = 12214,
+    /// (Gauge Controller Kill Arithmetic Error 8)
+    GaugeControllerKillArithmeticError8 = 12215,
+    /// (Gauge Controller Kill Arithmetic Error 9)
+    GaugeControllerKillArithmeticError9 = 12216,
+    /// (Gauge Controller Kill Arithmetic Error 10)
+    GaugeControllerKillArithmeticError10 = 12217,
+    /// (Gauge Controller Kill Arithmetic Error 11)
+    GaugeControllerKillArithmeticError11 = 12218,
+    /// (Gauge Controller Kill Arithmetic Error 12)
+    GaugeControllerKillArithmeticError12 = 12219,
+    /// (Gauge Controller Too many votes in one call)
+    GaugeControllerTooManyVotes = 12220,
+    /// (Gauge Controller Used too much power 2)
+    GaugeControllerUsedTooMuchPower2 = 12221,
+    /// (Gauge Controller Caller is not the vote delegate)
+    GaugeControllerNotVoteDelegate = 12222,
+    /// (Gauge Controller Invalid vote delegate)
+    GaugeControllerInvalidVoteDelegate = 12223,
+    /// (Gauge Controller No vote delegate set)
+    GaugeControllerNoVoteDelegate = 12224,
+    /// (Gauge Controller Used too much power 3)
+    GaugeControllerUsedTooMuchPower3 = 12225,
+    /// (Gauge Controller Not Admin6)
+    GaugeControllerNotAdmin6 = 12226,
+    /// (Gauge Controller Gauge not added 3)
+    GaugeControllerGaugeNotAdded3 = 12227,
+    /// (Gauge Controller Weight cap is greater than 1e9)
+    GaugeControllerInvalidWeightCap = 12228,
+    /// (Gauge Controller Weight Cap Arithmetic Error 1)
+    GaugeControllerWeightCapArithmeticError1 = 12229,
+    /// (Gauge Controller Weight Cap Arithmetic Error 2)
+    GaugeControllerWeightCapArithmeticError2 = 12230,
+    /// (Gauge Controller Weight Cap Arithmetic Error 3)
+    GaugeControllerWeightCapArithmeticError3 = 12231,
+    /// (Gauge Controller Weight Cap Arithmetic Error 4)
+    GaugeControllerWeightCapArithmeticError4 = 12232,
+    /// (Gauge Controller Weight Cap Arithmetic Error 5)
+    GaugeControllerWeightCapArithmeticError5 = 12233,
+    /// (Gauge Controller Weight Cap Arithmetic Error 6)
+    GaugeControllerWeightCapArithmeticError6 = 12234,
+    /// (Gauge Controller Weight Cap Arithmetic Error 7)
+    GaugeControllerWeightCapArithmeticError7 = 12235,
+    /// (Gauge Controller Weight Cap Arithmetic Error 8)
+    GaugeControllerWeightCapArithmeticError8 = 12236,
+    /// (Gauge Controller Weight Cap Arithmetic Error 9)
+    GaugeControllerWeightCapArithmeticError9 = 12237,
+    /// (Gauge Controller Weight Cap Arithmetic Error 10)
+    GaugeControllerWeightCapArithmeticError10 = 12238,
+    /// (Gauge Controller Weight Cap Arithmetic Error 11)
+    GaugeControllerWeightCapArithmeticError11 = 12239,
+    /// (Gauge Controller Too Many Projection Weeks)
+    GaugeControllerTooManyProjectionWeeks = 12241,
+    /// (Gauge Controller Gauge Not Added 4)
+    GaugeControllerGaugeNotAdded4 = 12242,
+    /// (Gauge Controller Projection Arithmetic Error 1)
+    GaugeControllerProjectionArithmeticError1 = 12243,
+    /// (Gauge Controller Projection Arithmetic Error 2)
+    GaugeControllerProjectionArithmeticError2 = 12244,
+    /// (Gauge Controller Projection Arithmetic Error 3)
+    GaugeControllerProjectionArithmeticError3 = 12245,
+    /// (Gauge Controller Projection Arithmetic Error 4)
+    GaugeControllerProjectionArithmeticError4 = 12246,
+    /// (Gauge Controller Projection Arithmetic Error 5)
+    GaugeControllerProjectionArithmeticError5 = 12247,
+    /// (Gauge Controller Projection Arithmetic Error 6)
+    GaugeControllerProjectionArithmeticError6 = 12248,
+    /// (Gauge Controller Projection Arithmetic Error 7)
+    GaugeControllerProjectionArithmeticError7 = 12249,
+    /// (Gauge Controller Projection Arithmetic Error 8)
+    GaugeControllerProjectionArithmeticError8 = 12250,
+    /// (Gauge Controller Projection Arithmetic Error 9)
+    GaugeControllerProjectionArithmeticError9 = 12251,
+    /// (Gauge Controller Projection Arithmetic Error 10)
+    GaugeControllerProjectionArithmeticError10 = 12252,
+    /// (Gauge Controller Projection Arithmetic Error 11)
+    GaugeControllerProjectionArithmeticError11 = 12253,
+    /// (Gauge Controller Projection Arithmetic Error 12)
+    GaugeControllerProjectionArithmeticError12 = 12254,
+    /// (Gauge Controller Projection Arithmetic Error 13)
+    GaugeControllerProjectionArithmeticError13 = 12255,
+    /// (Gauge Controller Not Admin 7)
+    GaugeControllerNotAdmin7 = 12256,
+    /// (Gauge Controller Gauge Not Added 5)
+    GaugeControllerGaugeNotAdded5 = 12257,
+    /// (Gauge Controller Invalid Gauge Kind)
+    GaugeControllerInvalidGaugeKind = 12258,
+    /// (Gauge Controller Too Many Reward Tokens)
+    GaugeControllerTooManyRewardTokens = 12259,
+    /// (Gauge Controller Gauges Page Too Large)
+    GaugeControllerGaugesPageTooLarge = 12260,
+    /// (Gauge Controller Gauges Page Arithmetic Error 1)
+    GaugeControllerGaugesPageArithmeticError1 = 12261,
+    /// (Gauge Controller Gauges Page Arithmetic Error 2)
+    GaugeControllerGaugesPageArithmeticError2 = 12262,
+    /// (Gauge Controller Not Admin 8)
+    GaugeControllerNotAdmin8 = 12263,
+    /// (Gauge Controller Weight Cap Arithmetic Error 12)
+    GaugeControllerWeightCapArithmeticError12 = 12264,
+    /// (Gauge Controller Weight Cap Arithmetic Error 13)
+    GaugeControllerWeightCapArithmeticError13 = 12265,
+    /// (Gauge Controller Gauge Name Too Long)
+    GaugeControllerGaugeNameTooLong = 12266,
+    /// (Gauge Controller Gauges Page Arithmetic Error 3)
+    GaugeControllerGaugesPageArithmeticError3 = 12267,
+    /// (Gauge Controller Weight Cap Arithmetic Error 14)
+    GaugeControllerWeightCapArithmeticError14 = 12268,
+    /// (Gauge Controller Weight Cap Arithmetic Error 15)
+    GaugeControllerWeightCapArithmeticError15 = 12269,
+    /// (Gauge Controller Weight Cap Arithmetic Error 16)
+    GaugeControllerWeightCapArithmeticError16 = 12270,
+    /// (Gauge Controller Weight Cap Arithmetic Error 17)
+    GaugeControllerWeightCapArithmeticError17 = 12271,
+    /// (Gauge Controller Weight Cap Arithmetic Error 18)
+    GaugeControllerWeightCapArithmeticError18 = 12272,
+    /// (Gauge Controller Weight Cap Arithmetic Error 19)
+    GaugeControllerWeightCapArithmeticError19 = 12273,
+    /// (Gauge Controller Weight Cap Arithmetic Error 20)
+    GaugeControllerWeightCapArithmeticError20 = 12274,
+    /// (Gauge Bribe Invalid Week)
+    GaugeBribeInvalidWeek = 12301,
+    /// (Gauge Bribe Week In Past)
+    GaugeBribeWeekInPast = 12302,
+    /// (Gauge Bribe Zero Amount)
+    GaugeBribeZeroAmount = 12303,
+    /// (Gauge Bribe Claim Not Started)
+    GaugeBribeClaimNotStarted = 12304,
+    /// (Gauge Bribe Claim Expired)
+    GaugeBribeClaimExpired = 12305,
+    /// (Gauge Bribe Already Claimed)
+    GaugeBribeAlreadyClaimed = 12306,
+    /// (Gauge Bribe Vote Changed)
+    GaugeBribeVoteChanged = 12307,
+    /// (Gauge Bribe Not Expired)
+    GaugeBribeNotExpired = 12308,
+    /// (Gauge Bribe Nothing To Refund)
+    GaugeBribeNothingToRefund = 12309,
+    /// (Gauge Bribe Too Many Claims)
+    GaugeBribeTooManyClaims = 12310,
+    /// (Gauge Bribe Arithmetic Error 1)
+    GaugeBribeArithmeticError1 = 12311,
+    /// (Gauge Bribe Arithmetic Error 2)
+    GaugeBribeArithmeticError2 = 12312,
+    /// (Gauge Bribe Arithmetic Error 3)
+    GaugeBribeArithmeticError3 = 12313,
+    /// (Gauge Bribe Arithmetic Error 4)
+    GaugeBribeArithmeticError4 = 12314,
+    /// (Gauge Bribe Arithmetic Error 5)
+    GaugeBribeArithmeticError5 = 12315,
+    /// (Gauge Bribe Arithmetic Error 6)
+    GaugeBribeArithmeticError6 = 12316,
+    /// (Gauge Bribe Arithmetic Error 7)
+    GaugeBribeArithmeticError7 = 12317,
+    /// (Gauge Bribe Arithmetic Error 8)
+    GaugeBribeArithmeticError8 = 12318,
+    /// (Gauge Bribe Arithmetic Error 9)
+    GaugeBribeArithmeticError9 = 12319,
+    /// (Gauge Bribe Arithmetic Error 10)
+    GaugeBribeArithmeticError10 = 12320,
+    /// (Gauge Bribe Arithmetic Error 11)
+    GaugeBribeArithmeticError11 = 12321,
+    /// (Gauge Bribe Arithmetic Error 12)
+    GaugeBribeArithmeticError12 = 12322,
+    /// (Gauge Bribe Arithmetic Error 13)
+    GaugeBribeArithmeticError13 = 12323,
+    /// (Gauge Bribe Arithmetic Error 14)
+    GaugeBribeArithmeticError14 = 12324,
+    /// (Gauge Bribe Arithmetic Error 15)
+    GaugeBribeArithmeticError15 = 12325,
+    /// (Gauge Bribe Arithmetic Error 16)
+    GaugeBribeArithmeticError16 = 12326,
+    /// (Gauge Bribe Gauge Killed)
+    GaugeBribeGaugeKilled = 12327,
 }
 
 impl From<Error> for ApiError {
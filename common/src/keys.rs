@@ -49,6 +49,23 @@ pub const BALANCE_OF_AT: &str = "balance_of_at";
 pub const VE_BALANCE_OF: &str = "ve_balance_of";
 pub const TOTAL_SUPPLY_AT: &str = "total_supply_at";
 pub const VE_TOTAL_SUPPLY: &str = "ve_total_supply";
+pub const DELEGATED_BALANCE: &str = "delegated_balance";
+pub const RECEIVED_BALANCE: &str = "received_balance";
+pub const ADJUSTED_BALANCE_OF: &str = "adjusted_balance_of";
+pub const SMART_WALLET_CHECKER: &str = "smart_wallet_checker";
+pub const FUTURE_SMART_WALLET_CHECKER: &str = "future_smart_wallet_checker";
+pub const BLOCK_HEIGHT_FEEDER: &str = "block_height_feeder";
+pub const BLOCK_HEIGHT_EPOCH: &str = "block_height_epoch";
+pub const BALANCE_OF_AT_TIME: &str = "balance_of_at_time";
+pub const TOTAL_SUPPLY_AT_TIME: &str = "total_supply_at_time";
+pub const BALANCE_OF_AT_TIMES: &str = "balance_of_at_times";
+pub const BALANCE_OF_RANGE: &str = "balance_of_range";
+pub const TOTAL_SUPPLY_AT_TIMES: &str = "total_supply_at_times";
+pub const TOTAL_SUPPLY_RANGE: &str = "total_supply_range";
+pub const EARLY_WITHDRAW_ENABLED: &str = "early_withdraw_enabled";
+pub const PENALTY_RECEIVER: &str = "penalty_receiver";
+pub const UNLOCKED: &str = "unlocked";
+pub const UNLOCKED_AT: &str = "unlocked_at";
 // Fee Distributor
 pub const VE_FOR_AT: &str = "ve_for_at";
 pub const CLAIM: &str = "claim";
@@ -60,6 +77,8 @@ pub const START_EPOCH_TIME_WRITE: &str = "start_epoch_time_write";
 pub const AVAILABLE_SUPPLY: &str = "available_supply";
 pub const MINT: &str = "mint";
 pub const MINTABLE_IN_TIMEFRAME: &str = "mintable_in_timeframe";
+pub const EMISSION_SCHEDULE: &str = "emission_schedule";
+pub const SUPPLY_REACHED_AT: &str = "supply_reached_at";
 // ERC-20 CRV
 pub const ADMIN_WHITELIST: &str = "admin_whitelist";
 pub const ALLOWANCE_CRV: &str = "allowance_crv";
@@ -68,6 +87,20 @@ pub const IS_UPDATED: &str = "is_updated";
 pub const START_EPOCH_TIME: &str = "start_epoch_time";
 pub const RATE: &str = "rate";
 pub const START_EPOCH_SUPPLY: &str = "start_epoch_supply";
+pub const MINTER_SHARES_DICT: &str = "minter_shares";
+pub const MINTER_CAPS_DICT: &str = "minter_caps";
+pub const TOTAL_MINTER_SHARES: &str = "total_minter_shares";
+pub const MINTED_IN_EPOCH_DICT: &str = "minted_in_epoch";
+pub const MINTER_SPENT_DICT: &str = "minter_spent";
+pub const MINTER_ALLOWANCE: &str = "minter_allowance";
+pub const MINTER_QUOTA: &str = "minter_quota";
+pub const BALANCE_HISTORY_START: &str = "balance_history_start";
+pub const BALANCE_CHECKPOINTS_DICT: &str = "balance_checkpoints";
+pub const BALANCE_CHECKPOINT_COUNTS_DICT: &str = "balance_checkpoint_counts";
+pub const SUPPLY_CHECKPOINTS_DICT: &str = "supply_checkpoints";
+pub const SUPPLY_CHECKPOINT_COUNT: &str = "supply_checkpoint_count";
+pub const ERC20_CRV_BALANCE_OF_AT: &str = "erc20_crv_balance_of_at";
+pub const ERC20_CRV_TOTAL_SUPPLY_AT: &str = "erc20_crv_total_supply_at";
 // Fee Distributor
 pub const TIME_CURSOR: &str = "time_cursor";
 pub const LAST_TOKEN_TIME: &str = "last_token_time";
@@ -78,6 +111,10 @@ pub const EMERGENCY_RETURN: &str = "emergency_return";
 // Minter
 pub const MINTED_DICT: &str = "minted";
 pub const ALLOWED_TO_MINT_FOR_DICT: &str = "allowed_to_mint_for";
+pub const MINTED_FOR_TOKEN_DICT: &str = "minted_for_token";
+pub const EMISSION_TOKENS_DICT: &str = "emission_tokens";
+pub const GAUGE_TOKENS_DICT: &str = "gauge_tokens";
+pub const CLAIMABLE_BY_GAUGE: &str = "claimable_by_gauge";
 // Reward Only Gauge
 pub const BALANCES_DICT: &str = "balances";
 pub const NONCES_DICT: &str = "nonces";
@@ -135,11 +172,22 @@ pub const CHANGES_SUM_DICT: &str = "changes_sum";
 pub const POINTS_TOTAL_DICT: &str = "points_total";
 pub const POINTS_TYPE_WEIGHT_DICT: &str = "points_type_weight";
 pub const TIME_TYPE_WEIGHT_DICT: &str = "time_type_weight";
+pub const GAUGE_IS_KILLED_DICT: &str = "gauge_is_killed";
+pub const VOTE_DELEGATES_DICT: &str = "vote_delegates";
+pub const POINTS_WEIGHT_CAP_DICT: &str = "points_weight_cap";
+pub const POINTS_CAPPED_WEIGHT_DICT: &str = "points_capped_weight";
+pub const CAPPED_WEIGHTS_CHECKPOINTED_DICT: &str = "capped_weights_checkpointed";
+pub const POINTS_WEIGHT_CAPS_ENABLED_DICT: &str = "points_weight_caps_enabled";
+pub const GAUGE_INDEX_DICT: &str = "gauge_index";
+pub const GAUGE_METADATA_DICT: &str = "gauge_metadata";
 pub const OWNER: &str = "owner";
 pub const TIME_TOTAL: &str = "time_total";
 pub const N_GAUGE_TYPES: &str = "n_gauge_types";
 pub const N_GAUGES: &str = "n_gauges";
 pub const LAST_USER_VOTE: &str = "last_user_vote";
+pub const WEIGHT_CAPS_ENABLED: &str = "weight_caps_enabled";
+pub const TIME_WEIGHT_CAPS_ENABLED: &str = "time_weight_caps_enabled";
+pub const TIME_CAPPED_WEIGHTS: &str = "time_capped_weights";
 // Gauge Proxy
 pub const OWNERSHIP_ADMIN: &str = "ownership_admin";
 pub const EMERGENCY_ADMIN: &str = "emergency_admin";
@@ -181,6 +229,8 @@ pub const GET_GAUGE_WEIGHT: &str = "get_gauge_weight";
 pub const GET_TYPE_WEIGHT: &str = "get_type_weight";
 pub const GET_TOTAL_WEIGHT: &str = "get_total_weight";
 pub const GET_WEIGHTS_SUM_PER_TYPE: &str = "get_weights_sum_per_type";
+pub const PROJECTED_GAUGE_WEIGHTS: &str = "projected_gauge_weights";
+pub const GAUGES_PAGE: &str = "gauges_page";
 //IRewardDistributionRecipient
 pub const REWARDDISTRIBUTION: &str = "reward_distribution";
 //LpTokenWrapper
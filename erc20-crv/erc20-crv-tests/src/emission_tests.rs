@@ -0,0 +1,206 @@
+use casper_types::U256;
+use common::errors::Error;
+use erc20_crv::{
+    data::*,
+    emission::{Epoch, Schedule},
+};
+
+const INFLATION_START: u64 = 1_600_000_000_000;
+const MILLI_SECONDS_IN_DAY: u64 = 86_400_000;
+const TEN_E_NINE: u64 = 1_000_000_000;
+
+// (rate, supply cap at epoch start) of the first epochs, 9 decimals, per millisecond
+const GOLDEN_EPOCHS: [(u64, u64); 5] = [
+    (8714335, 1303030303000000000),
+    (7327853, 1577845571560000000),
+    (6161965, 1808936743768000000),
+    (5181574, 2003260472008000000),
+    (4357167, 2166666589672000000),
+];
+
+// Yearly emission of the upstream Curve DAO token in whole CRV
+const UPSTREAM_YEARLY_EMISSION: [u64; 8] = [
+    274815282, 231091186, 194323750, 163406144, 137407641, 115545593, 97161875, 81703072,
+];
+
+fn schedule() -> Schedule {
+    Schedule::new(
+        INFLATION_START.into(),
+        INITIAL_SUPPLY * U256::from(TEN_E_NINE),
+    )
+}
+
+fn epoch_start(epoch: u64) -> U256 {
+    U256::from(INFLATION_START) + RATE_REDUCTION_TIME * epoch
+}
+
+#[test]
+fn test_golden_epochs() {
+    let schedule = schedule();
+    for (i, (rate, supply)) in GOLDEN_EPOCHS.iter().enumerate() {
+        let start = epoch_start(i as u64);
+        assert_eq!(
+            schedule.epoch_at(start).unwrap(),
+            Some(Epoch {
+                start,
+                rate: (*rate).into(),
+                supply: (*supply).into(),
+            })
+        );
+        assert_eq!(schedule.rate_at(start).unwrap(), (*rate).into());
+        assert_eq!(schedule.rate_at(start - 1).unwrap(), {
+            if i == 0 {
+                0.into()
+            } else {
+                GOLDEN_EPOCHS[i - 1].0.into()
+            }
+        });
+        assert_eq!(schedule.supply_cap_at(start).unwrap(), (*supply).into());
+    }
+    let (rate, supply) = (U256::from(1540489), U256::from(2724961138216000000_u64));
+    assert_eq!(schedule.rate_at(epoch_start(10)).unwrap(), rate);
+    assert_eq!(schedule.supply_cap_at(epoch_start(10)).unwrap(), supply);
+}
+
+#[test]
+fn test_matches_upstream_curve() {
+    let schedule = schedule();
+    for (i, upstream) in UPSTREAM_YEARLY_EMISSION.iter().enumerate() {
+        let emitted = schedule
+            .mintable_between(epoch_start(i as u64), epoch_start(i as u64 + 1))
+            .unwrap()
+            / U256::from(TEN_E_NINE);
+        let upstream = U256::from(*upstream);
+        // Within one part per million of the upstream 18 decimals curve
+        assert!(upstream - emitted <= upstream / 1_000_000);
+    }
+}
+
+#[test]
+fn test_rate_decays_to_zero() {
+    let schedule = schedule();
+    let final_supply = U256::from(3030294676984000000_u64);
+    assert_eq!(schedule.rate_at(epoch_start(84)).unwrap(), 1.into());
+    assert_eq!(schedule.rate_at(epoch_start(85)).unwrap(), 0.into());
+    assert_eq!(
+        schedule.supply_cap_at(epoch_start(85)).unwrap(),
+        final_supply
+    );
+    assert_eq!(
+        schedule.supply_cap_at(epoch_start(1000)).unwrap(),
+        final_supply
+    );
+    assert_eq!(
+        schedule
+            .epochs_between(0.into(), epoch_start(1000))
+            .unwrap()
+            .len(),
+        85
+    );
+}
+
+#[test]
+fn test_supply_cap_before_inflation() {
+    let schedule = schedule();
+    let start = U256::from(INFLATION_START);
+    assert_eq!(schedule.epoch_at(start - 1).unwrap(), None);
+    assert_eq!(schedule.rate_at(0.into()).unwrap(), 0.into());
+    assert_eq!(
+        schedule.supply_cap_at(start - 1).unwrap(),
+        schedule.init_supply
+    );
+    assert_eq!(
+        schedule.mintable_between(0.into(), start).unwrap(),
+        0.into()
+    );
+    assert!(schedule.epochs_between(0.into(), start).unwrap().is_empty());
+}
+
+#[test]
+fn test_mintable_across_epochs() {
+    let schedule = schedule();
+    let day = U256::from(MILLI_SECONDS_IN_DAY);
+    let boundary = epoch_start(1);
+    let epochs = schedule
+        .epochs_between(boundary - day, boundary + day)
+        .unwrap();
+    assert_eq!(epochs.len(), 2);
+    assert_eq!(epochs[0].start, epoch_start(0));
+    assert_eq!(epochs[1].start, boundary);
+    let to_mint = schedule
+        .mintable_between(boundary - day, boundary + day)
+        .unwrap();
+    assert_eq!(to_mint, 1386045043200000_u64.into());
+    assert_eq!(
+        to_mint,
+        schedule.supply_cap_at(boundary + day).unwrap()
+            - schedule.supply_cap_at(boundary - day).unwrap()
+    );
+    assert_eq!(
+        schedule
+            .supply_cap_at(epoch_start(0) + RATE_REDUCTION_TIME / 2)
+            .unwrap(),
+        1440437937280000000_u64.into()
+    );
+}
+
+#[test]
+fn test_epochs_from() {
+    let schedule = schedule();
+    let epochs = schedule.epochs_from(0.into(), GOLDEN_EPOCHS.len()).unwrap();
+    assert_eq!(epochs.len(), GOLDEN_EPOCHS.len());
+    for (i, epoch) in epochs.iter().enumerate() {
+        assert_eq!(epoch.start, epoch_start(i as u64));
+        assert_eq!(epoch.rate, GOLDEN_EPOCHS[i].0.into());
+        assert_eq!(epoch.supply, GOLDEN_EPOCHS[i].1.into());
+    }
+    let epochs = schedule
+        .epochs_from(epoch_start(2) + MILLI_SECONDS_IN_DAY, 2)
+        .unwrap();
+    assert_eq!(epochs[0].start, epoch_start(2));
+    assert_eq!(epochs[1].rate, GOLDEN_EPOCHS[3].0.into());
+    // The listing keeps going once nothing is emitted anymore
+    let epochs = schedule.epochs_from(epoch_start(84), 3).unwrap();
+    assert_eq!(epochs[1].rate, 0.into());
+    assert_eq!(epochs[2].start, epoch_start(86));
+    assert_eq!(epochs[2].supply, epochs[1].supply);
+}
+
+#[test]
+fn test_supply_reached_at() {
+    let schedule = schedule();
+    assert_eq!(
+        schedule.supply_reached_at(schedule.init_supply).unwrap(),
+        Some(INFLATION_START.into())
+    );
+    for (i, (_, supply)) in GOLDEN_EPOCHS.iter().enumerate().skip(1) {
+        assert_eq!(
+            schedule.supply_reached_at((*supply).into()).unwrap(),
+            Some(epoch_start(i as u64))
+        );
+    }
+    let supply = U256::from(2_000_000_000_u64) * TEN_E_NINE;
+    let reached = epoch_start(2) + 31006871385_u64;
+    assert_eq!(schedule.supply_reached_at(supply).unwrap(), Some(reached));
+    assert!(schedule.supply_cap_at(reached).unwrap() >= supply);
+    assert!(schedule.supply_cap_at(reached - 1).unwrap() < supply);
+    let final_supply = U256::from(3030294676984000000_u64);
+    assert!(schedule.supply_reached_at(final_supply).unwrap().is_some());
+    assert_eq!(schedule.supply_reached_at(final_supply + 1).unwrap(), None);
+}
+
+#[test]
+fn test_overflow_is_returned() {
+    let epoch = Epoch {
+        start: U256::MAX,
+        rate: 1.into(),
+        supply: 0.into(),
+    };
+    assert_eq!(epoch.end(), Err(Error::Erc20CRVOverFlow25));
+    assert_eq!(epoch.next(), Err(Error::Erc20CRVOverFlow25));
+    let schedule = Schedule::new(U256::MAX - 1, 0.into());
+    assert_eq!(
+        schedule.epochs_from(0.into(), 2),
+        Err(Error::Erc20CRVOverFlow25)
+    );
+}
@@ -40,6 +40,28 @@ impl ERC20CRVInstance {
             0,
         );
     }
+    pub fn add_minter(&self, sender: AccountHash, minter: Key, share: U256, cap: U256) {
+        self.0.call_contract(
+            sender,
+            "add_minter",
+            runtime_args! {
+                "minter" => minter,
+                "share" => share,
+                "cap" => cap
+            },
+            0,
+        );
+    }
+    pub fn remove_minter(&self, sender: AccountHash, minter: Key) {
+        self.0.call_contract(
+            sender,
+            "remove_minter",
+            runtime_args! {
+                "minter" => minter
+            },
+            0,
+        );
+    }
     pub fn burn(&self, sender: AccountHash, value: U256) {
         self.0.call_contract(
             sender,
@@ -50,6 +72,37 @@ impl ERC20CRVInstance {
             0,
         );
     }
+    pub fn burn_at(&self, sender: AccountHash, value: U256, time_now: u64) {
+        self.0.call_contract(
+            sender,
+            "burn",
+            runtime_args! {
+                "value"=>value
+            },
+            time_now,
+        );
+    }
+    pub fn transfer_at(
+        &self,
+        sender: AccountHash,
+        recipient: Address,
+        amount: U256,
+        time_now: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "transfer",
+            runtime_args! {
+                "recipient"=>recipient,
+                "amount"=>amount
+            },
+            time_now,
+        );
+    }
+    pub fn enable_balance_history(&self, sender: AccountHash, time_now: u64) {
+        self.0
+            .call_contract(sender, "enable_balance_history", runtime_args! {}, time_now);
+    }
     pub fn set_admin(&self, sender: AccountHash, admin: Key) {
         self.0.call_contract(
             sender,
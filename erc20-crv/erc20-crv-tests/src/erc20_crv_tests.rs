@@ -4,6 +4,7 @@ use casperlabs_test_env::{TestContract, TestEnv};
 use common::{keys::*, utils::key_to_str};
 use crv20::Address;
 use erc20_crv::data::*;
+use std::collections::BTreeMap;
 
 pub const TEN_E_NINE: u128 = 1000000000;
 const MILLI_SECONDS_IN_DAY: u64 = 86_400_000;
@@ -103,6 +104,11 @@ fn test_set_minter() {
     let minter: Key = Key::Account(env.next_user());
     contract.set_minter(owner, minter);
     assert_eq!(contract.get_minter(), minter);
+    // The minter gets every share not granted to other minters
+    assert_eq!(
+        minter_quota(&env, &contract, owner, minter, 0),
+        (RATE_DENOMINATOR, 0.into())
+    );
 }
 #[test]
 fn test_update_mining_parameters() {
@@ -187,7 +193,8 @@ fn test_total_supply() {
 fn test_mintable_in_timeframe() {
     let (env, owner, contract, time_now) = deploy();
     contract.update_mining_parameters(owner, time_now + MILLI_SECONDS_IN_DAY);
-    let start: U256 = U256::from(time_now);
+    // Inflation starts one day after deployment
+    let start: U256 = U256::from(time_now) + MILLI_SECONDS_IN_DAY;
     let end: U256 = start + MILLI_SECONDS_IN_DAY;
     TestContract::new(
         &env,
@@ -207,6 +214,81 @@ fn test_mintable_in_timeframe() {
     assert_eq!(_ret, 752918544000000_i128.into());
 }
 #[test]
+fn test_emission_schedule() {
+    let (env, owner, contract, time_now) = deploy();
+    let count: U256 = 3.into();
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(EMISSION_SCHEDULE),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "count" => count
+        },
+        time_now,
+    );
+    let ret: Vec<(U256, U256, U256)> =
+        env.query_account_named_key(owner, &[EMISSION_SCHEDULE.into()]);
+    let inflation_start: U256 = U256::from(time_now) + INFLATION_DELAY;
+    assert_eq!(
+        ret,
+        vec![
+            (inflation_start, INITIAL_RATE, contract.get_init_supply()),
+            (
+                inflation_start + RATE_REDUCTION_TIME,
+                7327853.into(),
+                1577845571560000000_u64.into()
+            ),
+            (
+                inflation_start + RATE_REDUCTION_TIME * 2,
+                6161965.into(),
+                1808936743768000000_u64.into()
+            ),
+        ]
+    );
+}
+#[test]
+#[should_panic]
+fn test_emission_schedule_too_long() {
+    let (env, owner, contract, time_now) = deploy();
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(EMISSION_SCHEDULE),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "count" => MAX_SCHEDULE_EPOCHS + 1
+        },
+        time_now,
+    );
+}
+#[test]
+fn test_supply_reached_at() {
+    let (env, owner, contract, time_now) = deploy();
+    let supply: U256 = 1577845571560000000_u64.into();
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(SUPPLY_REACHED_AT),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "supply" => supply
+        },
+        time_now,
+    );
+    let ret: Option<U256> = env.query_account_named_key(owner, &[SUPPLY_REACHED_AT.into()]);
+    assert_eq!(
+        ret,
+        Some(U256::from(time_now) + INFLATION_DELAY + RATE_REDUCTION_TIME)
+    );
+}
+#[test]
 fn test_mint() {
     let (env, owner, contract, time_now) = deploy();
     let to = env.next_user();
@@ -235,6 +317,206 @@ fn test_mint() {
     let ret: U256 = env.query_account_named_key(to, &[BALANCE_OF.into()]);
     assert_eq!(ret, U256::from(10 * TEN_E_NINE));
 }
+fn minter_allowance(
+    env: &TestEnv,
+    contract: &ERC20CRVInstance,
+    minter: AccountHash,
+    time: u64,
+) -> U256 {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        minter,
+        runtime_args! {
+            "entrypoint" => String::from(MINTER_ALLOWANCE),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "minter" => Key::Account(minter)
+        },
+        time,
+    );
+    env.query_account_named_key(minter, &[MINTER_ALLOWANCE.into()])
+}
+fn minter_quota(
+    env: &TestEnv,
+    contract: &ERC20CRVInstance,
+    sender: AccountHash,
+    minter: Key,
+    time: u64,
+) -> (U256, U256) {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        sender,
+        runtime_args! {
+            "entrypoint" => String::from(MINTER_QUOTA),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "minter" => minter
+        },
+        time,
+    );
+    env.query_account_named_key(sender, &[MINTER_QUOTA.into()])
+}
+#[test]
+fn test_add_minter_with_cap() {
+    let (env, owner, contract, time_now) = deploy();
+    let fund = env.next_user();
+    let to = env.next_user();
+    let cap: U256 = U256::from(100 * TEN_E_NINE);
+    let time = time_now + 2 * MILLI_SECONDS_IN_DAY;
+    contract.add_minter(owner, Key::Account(fund), 0.into(), cap);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(MINTER_QUOTA),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "minter" => Key::Account(fund)
+        },
+        time,
+    );
+    let ret: (U256, U256) = env.query_account_named_key(owner, &[MINTER_QUOTA.into()]);
+    assert_eq!(ret, (0.into(), cap));
+    contract.mint(
+        fund,
+        Address::Account(to),
+        U256::from(40 * TEN_E_NINE),
+        time,
+    );
+    assert_eq!(
+        minter_allowance(&env, &contract, fund, time),
+        U256::from(60 * TEN_E_NINE)
+    );
+    // The cap is granted again in the next epoch
+    assert_eq!(
+        minter_allowance(&env, &contract, fund, time + YEAR.as_u64()),
+        cap
+    );
+}
+#[test]
+fn test_add_minter_with_share() {
+    let (env, owner, contract, time_now) = deploy();
+    let fund = env.next_user();
+    // 10% of the emission
+    let share: U256 = RATE_DENOMINATOR / 10;
+    contract.add_minter(owner, Key::Account(fund), share, 0.into());
+    assert_eq!(minter_allowance(&env, &contract, fund, time_now), 0.into());
+    // A day after the inflation start
+    let time = time_now + 2 * MILLI_SECONDS_IN_DAY;
+    assert_eq!(
+        minter_allowance(&env, &contract, fund, time),
+        INITIAL_RATE * MILLI_SECONDS_IN_DAY / 10
+    );
+    // The unminted share of the first epoch carries over into the next one
+    assert_eq!(
+        minter_allowance(&env, &contract, fund, time + YEAR.as_u64()),
+        (INITIAL_RATE * RATE_REDUCTION_TIME + U256::from(7327853) * MILLI_SECONDS_IN_DAY) / 10
+    );
+}
+#[test]
+#[should_panic]
+fn test_mint_exceeds_minter_allowance() {
+    let (env, owner, contract, time_now) = deploy();
+    let fund = env.next_user();
+    let cap: U256 = U256::from(100 * TEN_E_NINE);
+    let time = time_now + 2 * MILLI_SECONDS_IN_DAY;
+    contract.add_minter(owner, Key::Account(fund), 0.into(), cap);
+    contract.mint(fund, Address::Account(fund), cap, time);
+    contract.mint(fund, Address::Account(fund), 1.into(), time);
+}
+#[test]
+#[should_panic]
+fn test_mint_after_remove_minter() {
+    let (env, owner, contract, time_now) = deploy();
+    let fund = env.next_user();
+    let time = time_now + 2 * MILLI_SECONDS_IN_DAY;
+    contract.add_minter(owner, Key::Account(fund), 0.into(), TEN_E_NINE.into());
+    contract.remove_minter(owner, Key::Account(fund));
+    contract.mint(fund, Address::Account(fund), 1.into(), time);
+}
+#[test]
+fn test_minter_shares_split_epoch_emission() {
+    let (env, owner, contract, time_now) = deploy();
+    let fund = env.next_user();
+    let to = env.next_user();
+    let share: U256 = RATE_DENOMINATOR / 10;
+    contract.add_minter(owner, Key::Account(fund), share, 0.into());
+    contract.set_minter(owner, Key::Account(owner));
+    assert_eq!(
+        minter_quota(&env, &contract, owner, Key::Account(owner), 0),
+        (RATE_DENOMINATOR - share, 0.into())
+    );
+    // Halfway through the first epoch
+    let time = time_now + YEAR.as_u64() / 2;
+    let emitted: U256 = INITIAL_RATE * (YEAR / 2 - MILLI_SECONDS_IN_DAY);
+    let fund_quota: U256 = emitted / 10;
+    contract.mint(fund, Address::Account(to), fund_quota, time);
+    assert_eq!(minter_allowance(&env, &contract, fund, time), 0.into());
+    // The fund spending its share does not eat into the quota of the minter
+    let amount: U256 = U256::from(10 * TEN_E_NINE);
+    contract.mint(owner, Address::Account(to), amount, time);
+    assert_eq!(
+        minter_allowance(&env, &contract, owner, time),
+        emitted * (RATE_DENOMINATOR - share) / RATE_DENOMINATOR - amount
+    );
+}
+#[test]
+fn test_minter_mints_past_epoch_rewards() {
+    let (env, owner, contract, time_now) = deploy();
+    let to = env.next_user();
+    contract.set_minter(owner, Key::Account(owner));
+    // Rewards of the whole first epoch claimed a day into the second one, which
+    // emits less than the first
+    let time = time_now + 2 * MILLI_SECONDS_IN_DAY + YEAR.as_u64();
+    let amount: U256 = INITIAL_RATE * RATE_REDUCTION_TIME;
+    contract.mint(owner, Address::Account(to), amount, time);
+    assert_eq!(
+        minter_allowance(&env, &contract, owner, time),
+        U256::from(7327853) * MILLI_SECONDS_IN_DAY
+    );
+}
+#[test]
+#[should_panic]
+fn test_cap_minter_cannot_mint_reserved_share() {
+    let (env, owner, contract, time_now) = deploy();
+    let fund = env.next_user();
+    contract.set_minter(owner, Key::Account(owner));
+    contract.add_minter(
+        owner,
+        Key::Account(fund),
+        0.into(),
+        U256::from(100 * TEN_E_NINE),
+    );
+    // Everything emitted so far belongs to the share of the minter
+    let time = time_now + 2 * MILLI_SECONDS_IN_DAY;
+    contract.mint(fund, Address::Account(fund), TEN_E_NINE.into(), time);
+}
+#[test]
+#[should_panic]
+fn test_add_minter_exceeds_total_share() {
+    let (env, owner, contract, _) = deploy();
+    let fund = env.next_user();
+    // The minter already holds the whole emission
+    contract.set_minter(owner, Key::Account(env.next_user()));
+    contract.add_minter(owner, Key::Account(fund), RATE_DENOMINATOR / 10, 0.into());
+}
+#[test]
+#[should_panic]
+fn test_add_minter_with_share_and_cap() {
+    let (env, owner, contract, _) = deploy();
+    let fund = env.next_user();
+    contract.add_minter(owner, Key::Account(fund), 1.into(), 1.into());
+}
+#[test]
+#[should_panic]
+fn test_add_minter_not_admin() {
+    let (env, _, contract, _) = deploy();
+    let fund = env.next_user();
+    contract.add_minter(fund, Key::Account(fund), 0.into(), 1.into());
+}
 #[test]
 fn test_increase_allowance() {
     let (env, owner, contract, _) = deploy();
@@ -380,3 +662,128 @@ fn test_allowance() {
     let ret: U256 = env.query_account_named_key(owner, &[ALLOWANCE.into()]);
     assert_eq!(ret, U256::from(50 * TEN_E_NINE));
 }
+enum Op {
+    Transfer(AccountHash, AccountHash, u128),
+    Mint(AccountHash, u128),
+    Burn(AccountHash, u128),
+}
+fn balance_of_at(env: &TestEnv, contract: &ERC20CRVInstance, owner: AccountHash, t: u64) -> U256 {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(ERC20_CRV_BALANCE_OF_AT),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "owner" => Address::Account(owner),
+            "t" => U256::from(t)
+        },
+        t,
+    );
+    env.query_account_named_key(owner, &[ERC20_CRV_BALANCE_OF_AT.into()])
+}
+fn total_supply_at(
+    env: &TestEnv,
+    contract: &ERC20CRVInstance,
+    sender: AccountHash,
+    t: u64,
+) -> U256 {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        sender,
+        runtime_args! {
+            "entrypoint" => String::from(ERC20_CRV_TOTAL_SUPPLY_AT),
+            "package_hash" => Key::Hash(contract.package_hash()),
+            "t" => U256::from(t)
+        },
+        t,
+    );
+    env.query_account_named_key(sender, &[ERC20_CRV_TOTAL_SUPPLY_AT.into()])
+}
+#[test]
+fn test_balance_history_replay() {
+    let (env, owner, contract, time_now) = deploy();
+    let (alice, bob, carol) = (env.next_user(), env.next_user(), env.next_user());
+    let accounts = [owner, alice, bob, carol];
+    contract.set_minter(owner, Key::Account(owner));
+    let history_start = time_now + 2 * MILLI_SECONDS_IN_DAY;
+    contract.enable_balance_history(owner, history_start);
+    // (time offset from the history start, operation), carol is never touched
+    let ops = [
+        (1000, Op::Transfer(owner, alice, 500 * TEN_E_NINE)),
+        (2000, Op::Transfer(alice, bob, 200 * TEN_E_NINE)),
+        (2000, Op::Transfer(bob, alice, 50 * TEN_E_NINE)),
+        (3000, Op::Mint(bob, 1000 * TEN_E_NINE)),
+        (4000, Op::Burn(alice, 100 * TEN_E_NINE)),
+        (5000, Op::Transfer(bob, owner, 1150 * TEN_E_NINE)),
+        (6000, Op::Burn(owner, 10 * TEN_E_NINE)),
+        (7000, Op::Transfer(alice, bob, 250 * TEN_E_NINE)),
+    ];
+    let mut balances: BTreeMap<AccountHash, u128> = BTreeMap::new();
+    balances.insert(owner, 1303030303 * TEN_E_NINE);
+    let mut total_supply: u128 = 1303030303 * TEN_E_NINE;
+    let mut expected: Vec<(u64, BTreeMap<AccountHash, u128>, u128)> =
+        vec![(history_start, balances.clone(), total_supply)];
+    for (offset, op) in ops.iter() {
+        let time = history_start + offset;
+        match op {
+            Op::Transfer(from, to, amount) => {
+                contract.transfer_at(*from, Address::Account(*to), (*amount).into(), time);
+                *balances.entry(*from).or_default() -= amount;
+                *balances.entry(*to).or_default() += amount;
+            }
+            Op::Mint(to, amount) => {
+                contract.mint(owner, Address::Account(*to), (*amount).into(), time);
+                *balances.entry(*to).or_default() += amount;
+                total_supply += amount;
+            }
+            Op::Burn(from, amount) => {
+                contract.burn_at(*from, (*amount).into(), time);
+                *balances.entry(*from).or_default() -= amount;
+                total_supply -= amount;
+            }
+        }
+        if expected.last().unwrap().0 == time {
+            expected.pop();
+        }
+        expected.push((time, balances.clone(), total_supply));
+    }
+    for (time, balances, total_supply) in expected.iter() {
+        // At the checkpoint itself and halfway to the next one
+        for t in [*time, time + 500] {
+            for account in accounts.iter() {
+                assert_eq!(
+                    balance_of_at(&env, &contract, *account, t),
+                    balances.get(account).copied().unwrap_or_default().into()
+                );
+            }
+            assert_eq!(
+                total_supply_at(&env, &contract, owner, t),
+                (*total_supply).into()
+            );
+        }
+    }
+}
+#[test]
+#[should_panic]
+fn test_balance_of_at_before_history_start() {
+    let (env, owner, contract, time_now) = deploy();
+    contract.enable_balance_history(owner, time_now + 1000);
+    balance_of_at(&env, &contract, owner, time_now);
+}
+#[test]
+#[should_panic]
+fn test_total_supply_at_history_disabled() {
+    let (env, owner, contract, time_now) = deploy();
+    total_supply_at(&env, &contract, owner, time_now);
+}
+#[test]
+#[should_panic]
+fn test_enable_balance_history_twice() {
+    let (_, owner, contract, time_now) = deploy();
+    contract.enable_balance_history(owner, time_now);
+    contract.enable_balance_history(owner, time_now + 1000);
+}
@@ -3,3 +3,6 @@ pub mod erc20_crv_tests;
 
 #[cfg(test)]
 pub mod erc20_crv_instance;
+
+#[cfg(test)]
+pub mod emission_tests;
@@ -2,7 +2,7 @@
 #![no_std]
 extern crate alloc;
 
-use alloc::{collections::BTreeSet, format, string::String, vec};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
@@ -58,6 +58,30 @@ fn remove_admin() {
     Erc20Crv::default().remove_admin(admin);
 }
 #[no_mangle]
+fn add_minter() {
+    let minter: Key = runtime::get_named_arg("minter");
+    let share: U256 = runtime::get_named_arg("share");
+    let cap: U256 = runtime::get_named_arg("cap");
+    Erc20Crv::default().add_minter(minter, share, cap);
+}
+#[no_mangle]
+fn remove_minter() {
+    let minter: Key = runtime::get_named_arg("minter");
+    Erc20Crv::default().remove_minter(minter);
+}
+#[no_mangle]
+fn minter_allowance() {
+    let minter: Key = runtime::get_named_arg("minter");
+    let ret = Erc20Crv::default().minter_allowance(minter);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
+fn minter_quota() {
+    let minter: Key = runtime::get_named_arg("minter");
+    let ret = Erc20Crv::default().minter_quota(minter);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
 fn start_epoch_time_write() {
     let ret = Erc20Crv::default().start_epoch_time_write();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
@@ -79,6 +103,18 @@ fn mintable_in_timeframe() {
     let ret = Erc20Crv::default().mintable_in_timeframe(start, end);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
+#[no_mangle]
+fn emission_schedule() {
+    let count: U256 = runtime::get_named_arg("count");
+    let ret = Erc20Crv::default().emission_schedule(count);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
+fn supply_reached_at() {
+    let supply: U256 = runtime::get_named_arg("supply");
+    let ret = Erc20Crv::default().supply_reached_at(supply);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
 
 #[no_mangle]
 fn update_mining_parameters() {
@@ -95,7 +131,7 @@ fn transfer_from() {
     let owner: Address = runtime::get_named_arg("owner");
     let recipient: Address = runtime::get_named_arg("recipient");
     let amount: U256 = runtime::get_named_arg("amount");
-    CURVEERC20::transfer_from(&Erc20Crv::default(), owner, recipient, amount).unwrap_or_revert();
+    ERC20CRV::transfer_from(&Erc20Crv::default(), owner, recipient, amount).unwrap_or_revert();
 }
 #[no_mangle]
 fn approve() {
@@ -107,7 +143,24 @@ fn approve() {
 fn transfer() {
     let recipient: Address = runtime::get_named_arg("recipient");
     let amount: U256 = runtime::get_named_arg("amount");
-    CURVEERC20::transfer(&Erc20Crv::default(), recipient, amount).unwrap_or_revert();
+    ERC20CRV::transfer(&Erc20Crv::default(), recipient, amount).unwrap_or_revert();
+}
+#[no_mangle]
+fn enable_balance_history() {
+    Erc20Crv::default().enable_balance_history();
+}
+#[no_mangle]
+fn balance_of_at() {
+    let owner: Address = runtime::get_named_arg("owner");
+    let t: U256 = runtime::get_named_arg("t");
+    let ret: U256 = Erc20Crv::default().balance_of_at(owner, t);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
+fn total_supply_at() {
+    let t: U256 = runtime::get_named_arg("t");
+    let ret: U256 = Erc20Crv::default().total_supply_at(t);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 #[no_mangle]
 fn total_supply() {
@@ -215,6 +268,62 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "enable_balance_history",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_at",
+        vec![
+            Parameter::new("owner", Address::cl_type()),
+            Parameter::new("t", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply_at",
+        vec![Parameter::new("t", U256::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_minter",
+        vec![
+            Parameter::new("minter", Key::cl_type()),
+            Parameter::new("share", U256::cl_type()),
+            Parameter::new("cap", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "remove_minter",
+        vec![Parameter::new("minter", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "minter_allowance",
+        vec![Parameter::new("minter", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "minter_quota",
+        vec![Parameter::new("minter", Key::cl_type())],
+        <(U256, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "update_mining_parameters",
         vec![],
@@ -247,6 +356,20 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "emission_schedule",
+        vec![Parameter::new("count", U256::cl_type())],
+        CLType::List(Box::new(<(U256, U256, U256)>::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "supply_reached_at",
+        vec![Parameter::new("supply", U256::cl_type())],
+        CLType::Option(Box::new(U256::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
     entry_points.add_entry_point(EntryPoint::new(
         "mint",
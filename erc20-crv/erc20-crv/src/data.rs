@@ -6,7 +6,12 @@ use casper_types::{
     bytesrepr::ToBytes, CLTyped, ContractHash, ContractPackageHash, Key, U128, U256,
 };
 
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 use core::convert::TryInto;
+use crv20::Address;
 
 use casperlabs_contract_utils::{get_key, set_key, Dict};
 use common::{keys::*, utils::*};
@@ -18,6 +23,7 @@ pub const RATE_REDUCTION_COEFFICIENT: U256 = U256([1189207115, 0, 0, 0]); //2 **
 
 pub const RATE_DENOMINATOR: U256 = U256([1000000000, 0, 0, 0]); //10^9
 pub const INFLATION_DELAY: U256 = U256([86400000, 0, 0, 0]);
+pub const MAX_SCHEDULE_EPOCHS: U256 = U256([100, 0, 0, 0]);
 
 pub struct AdminWhitelist {
     dict: Dict,
@@ -43,6 +49,200 @@ impl AdminWhitelist {
     }
 }
 
+pub struct MinterShares {
+    dict: Dict,
+}
+
+impl MinterShares {
+    pub fn instance() -> MinterShares {
+        MinterShares {
+            dict: Dict::instance(MINTER_SHARES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(MINTER_SHARES_DICT)
+    }
+
+    pub fn get(&self, key: &Key) -> U256 {
+        self.dict.get_by_key(key).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &Key, value: U256) {
+        self.dict.set_by_key(key, value);
+    }
+}
+
+pub struct MinterCaps {
+    dict: Dict,
+}
+
+impl MinterCaps {
+    pub fn instance() -> MinterCaps {
+        MinterCaps {
+            dict: Dict::instance(MINTER_CAPS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(MINTER_CAPS_DICT)
+    }
+
+    pub fn get(&self, key: &Key) -> U256 {
+        self.dict.get_by_key(key).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &Key, value: U256) {
+        self.dict.set_by_key(key, value);
+    }
+}
+
+pub struct MinterSpent {
+    dict: Dict,
+}
+
+impl MinterSpent {
+    pub fn instance() -> MinterSpent {
+        MinterSpent {
+            dict: Dict::instance(MINTER_SPENT_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(MINTER_SPENT_DICT)
+    }
+
+    pub fn get(&self, key: &Key) -> U256 {
+        self.dict.get_by_key(key).unwrap_or_default()
+    }
+
+    pub fn set(&self, key: &Key, value: U256) {
+        self.dict.set_by_key(key, value);
+    }
+}
+
+fn minted_in_epoch_key(minter: &Key, epoch_start: U256) -> String {
+    hash(format!(
+        "{}_{}_{}",
+        MINTED_IN_EPOCH_DICT,
+        minter.to_formatted_string(),
+        epoch_start
+    ))
+}
+
+pub struct MintedInEpoch {
+    dict: Dict,
+}
+
+impl MintedInEpoch {
+    pub fn instance() -> MintedInEpoch {
+        MintedInEpoch {
+            dict: Dict::instance(MINTED_IN_EPOCH_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(MINTED_IN_EPOCH_DICT)
+    }
+
+    pub fn get(&self, minter: &Key, epoch_start: U256) -> U256 {
+        self.dict
+            .get(&minted_in_epoch_key(minter, epoch_start))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, minter: &Key, epoch_start: U256, value: U256) {
+        self.dict
+            .set(&minted_in_epoch_key(minter, epoch_start), value);
+    }
+}
+
+fn balance_checkpoint_key(owner: &Address, index: U256) -> String {
+    hash(format!("{}_{}_{}", BALANCE_CHECKPOINTS_DICT, owner, index))
+}
+
+pub struct BalanceCheckpoints {
+    dict: Dict,
+}
+
+impl BalanceCheckpoints {
+    pub fn instance() -> BalanceCheckpoints {
+        BalanceCheckpoints {
+            dict: Dict::instance(BALANCE_CHECKPOINTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BALANCE_CHECKPOINTS_DICT)
+    }
+
+    pub fn get(&self, owner: &Address, index: U256) -> (U256, U256) {
+        self.dict
+            .get(&balance_checkpoint_key(owner, index))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Address, index: U256, value: (U256, U256)) {
+        self.dict.set(&balance_checkpoint_key(owner, index), value);
+    }
+}
+
+pub struct BalanceCheckpointCounts {
+    dict: Dict,
+}
+
+impl BalanceCheckpointCounts {
+    pub fn instance() -> BalanceCheckpointCounts {
+        BalanceCheckpointCounts {
+            dict: Dict::instance(BALANCE_CHECKPOINT_COUNTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(BALANCE_CHECKPOINT_COUNTS_DICT)
+    }
+
+    pub fn get(&self, owner: &Address) -> U256 {
+        self.dict
+            .get(&hash(format!(
+                "{}_{}",
+                BALANCE_CHECKPOINT_COUNTS_DICT, owner
+            )))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Address, value: U256) {
+        self.dict.set(
+            &hash(format!("{}_{}", BALANCE_CHECKPOINT_COUNTS_DICT, owner)),
+            value,
+        );
+    }
+}
+
+pub struct SupplyCheckpoints {
+    dict: Dict,
+}
+
+impl SupplyCheckpoints {
+    pub fn instance() -> SupplyCheckpoints {
+        SupplyCheckpoints {
+            dict: Dict::instance(SUPPLY_CHECKPOINTS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(SUPPLY_CHECKPOINTS_DICT)
+    }
+
+    pub fn get(&self, index: &U256) -> (U256, U256) {
+        self.dict.get(&index.to_string()).unwrap_or_default()
+    }
+
+    pub fn set(&self, index: &U256, value: (U256, U256)) {
+        self.dict.set(&index.to_string(), value);
+    }
+}
+
 pub fn set_result<T: ToBytes + CLTyped>(value: T) {
     match runtime::get_key(RESULT) {
         Some(key) => {
@@ -87,6 +287,12 @@ pub fn get_minter() -> Key {
 pub fn set_minter(minter: Key) {
     set_key(MINTER, minter);
 }
+pub fn get_total_minter_shares() -> U256 {
+    get_key(TOTAL_MINTER_SHARES).unwrap_or_default()
+}
+pub fn set_total_minter_shares(total_minter_shares: U256) {
+    set_key(TOTAL_MINTER_SHARES, total_minter_shares);
+}
 pub fn get_mining_epoch() -> U128 {
     get_key(MINING_EPOCH).unwrap_or_default()
 }
@@ -111,3 +317,15 @@ pub fn set_package_hash(package_hash: ContractPackageHash) {
 pub fn get_package_hash() -> ContractPackageHash {
     get_key(SELF_CONTRACT_PACKAGE_HASH).unwrap_or_default()
 }
+pub fn get_balance_history_start() -> U256 {
+    get_key(BALANCE_HISTORY_START).unwrap_or_default()
+}
+pub fn set_balance_history_start(balance_history_start: U256) {
+    set_key(BALANCE_HISTORY_START, balance_history_start);
+}
+pub fn get_supply_checkpoint_count() -> U256 {
+    get_key(SUPPLY_CHECKPOINT_COUNT).unwrap_or_default()
+}
+pub fn set_supply_checkpoint_count(supply_checkpoint_count: U256) {
+    set_key(SUPPLY_CHECKPOINT_COUNT, supply_checkpoint_count);
+}
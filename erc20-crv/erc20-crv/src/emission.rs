@@ -0,0 +1,207 @@
+use crate::data::{
+    INITIAL_RATE, RATE_DENOMINATOR, RATE_REDUCTION_COEFFICIENT, RATE_REDUCTION_TIME,
+};
+use alloc::vec::Vec;
+use casper_types::U256;
+use common::errors::*;
+
+/// @notice A single mining epoch of the CRV emission curve
+/// @dev `supply` is the supply cap at `start`, `rate` is emitted per millisecond.
+///      The emission math does not revert, arithmetic errors are returned to the
+///      contract, which reverts with them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Epoch {
+    pub start: U256,
+    pub rate: U256,
+    pub supply: U256,
+}
+
+impl Epoch {
+    /// @notice End of the epoch (exclusive)
+    pub fn end(&self) -> Result<U256, Error> {
+        self.start
+            .checked_add(RATE_REDUCTION_TIME)
+            .ok_or(Error::Erc20CRVOverFlow25)
+    }
+
+    /// @notice The epoch following this one
+    /// @dev The rate is reduced as `rate * RATE_DENOMINATOR / RATE_REDUCTION_COEFFICIENT`,
+    ///      rounding down once per epoch exactly like the upstream Curve token
+    pub fn next(&self) -> Result<Epoch, Error> {
+        Ok(Epoch {
+            start: self.end()?,
+            rate: self
+                .rate
+                .checked_mul(RATE_DENOMINATOR)
+                .ok_or(Error::Erc20CRVOverFlow26)?
+                .checked_div(RATE_REDUCTION_COEFFICIENT)
+                .ok_or(Error::Erc20CRVAirthmeticError6)?,
+            supply: self
+                .rate
+                .checked_mul(RATE_REDUCTION_TIME)
+                .ok_or(Error::Erc20CRVOverFlow27)?
+                .checked_add(self.supply)
+                .ok_or(Error::Erc20CRVOverFlow28)?,
+        })
+    }
+
+    /// @notice Supply cap reached at `t`, assuming `t` lies within this epoch
+    fn supply_at(&self, t: U256) -> Result<U256, Error> {
+        t.checked_sub(self.start)
+            .ok_or(Error::Erc20CRVUnderFlow6)?
+            .checked_mul(self.rate)
+            .ok_or(Error::Erc20CRVOverFlow29)?
+            .checked_add(self.supply)
+            .ok_or(Error::Erc20CRVOverFlow30)
+    }
+}
+
+/// @notice The CRV emission curve, fully determined by the inflation start and the initial supply
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Schedule {
+    pub inflation_start: U256,
+    pub init_supply: U256,
+}
+
+impl Schedule {
+    pub fn new(inflation_start: U256, init_supply: U256) -> Schedule {
+        Schedule {
+            inflation_start,
+            init_supply,
+        }
+    }
+
+    /// @notice The first mining epoch, emitting at `INITIAL_RATE`
+    pub fn genesis(&self) -> Epoch {
+        Epoch {
+            start: self.inflation_start,
+            rate: INITIAL_RATE,
+            supply: self.init_supply,
+        }
+    }
+
+    /// @notice Mining epoch containing timestamp `t`
+    /// @dev Returns `None` before the inflation start
+    pub fn epoch_at(&self, t: U256) -> Result<Option<Epoch>, Error> {
+        if t < self.inflation_start {
+            return Ok(None);
+        }
+        let hops: U256 = t
+            .checked_sub(self.inflation_start)
+            .ok_or(Error::Erc20CRVUnderFlow7)?
+            .checked_div(RATE_REDUCTION_TIME)
+            .ok_or(Error::Erc20CRVAirthmeticError7)?;
+        let mut epoch: Epoch = self.genesis();
+        let mut hop: U256 = 0.into();
+        while hop < hops {
+            if epoch.rate == 0.into() {
+                // Nothing is emitted anymore, skip straight to the target epoch
+                epoch.start = hops
+                    .checked_mul(RATE_REDUCTION_TIME)
+                    .ok_or(Error::Erc20CRVOverFlow31)?
+                    .checked_add(self.inflation_start)
+                    .ok_or(Error::Erc20CRVOverFlow32)?;
+                break;
+            }
+            epoch = epoch.next()?;
+            hop = hop.checked_add(1.into()).ok_or(Error::Erc20CRVOverFlow33)?;
+        }
+        Ok(Some(epoch))
+    }
+
+    /// @notice Emission rate per millisecond at timestamp `t`
+    pub fn rate_at(&self, t: U256) -> Result<U256, Error> {
+        Ok(self
+            .epoch_at(t)?
+            .map(|epoch| epoch.rate)
+            .unwrap_or_default())
+    }
+
+    /// @notice Maximum supply allowed to exist at timestamp `t`
+    pub fn supply_cap_at(&self, t: U256) -> Result<U256, Error> {
+        match self.epoch_at(t)? {
+            Some(epoch) => epoch.supply_at(t),
+            None => Ok(self.init_supply),
+        }
+    }
+
+    /// @notice Mining epochs emitting within `[start, end)`
+    /// @dev Epochs after the rate has decayed to zero are left out
+    pub fn epochs_between(&self, start: U256, end: U256) -> Result<Vec<Epoch>, Error> {
+        let mut epochs: Vec<Epoch> = Vec::new();
+        let first: U256 = core::cmp::max(start, self.inflation_start);
+        if first >= end {
+            return Ok(epochs);
+        }
+        // `first` is never before the inflation start
+        let mut epoch: Epoch = self.epoch_at(first)?.unwrap_or_else(|| self.genesis());
+        while epoch.start < end && epoch.rate > 0.into() {
+            epochs.push(epoch);
+            epoch = epoch.next()?;
+        }
+        Ok(epochs)
+    }
+
+    /// @notice Tokens emitted within `[start, end)`
+    pub fn mintable_between(&self, start: U256, end: U256) -> Result<U256, Error> {
+        let mut to_mint: U256 = 0.into();
+        for epoch in self.epochs_between(start, end)? {
+            let current_start: U256 = core::cmp::max(start, epoch.start);
+            let current_end: U256 = core::cmp::min(end, epoch.end()?);
+            to_mint = current_end
+                .checked_sub(current_start)
+                .ok_or(Error::Erc20CRVUnderFlow8)?
+                .checked_mul(epoch.rate)
+                .ok_or(Error::Erc20CRVOverFlow34)?
+                .checked_add(to_mint)
+                .ok_or(Error::Erc20CRVOverFlow35)?;
+        }
+        Ok(to_mint)
+    }
+
+    /// @notice `count` consecutive mining epochs, starting with the one containing `from`
+    /// @dev Before the inflation start the listing begins with the first mining epoch
+    pub fn epochs_from(&self, from: U256, count: usize) -> Result<Vec<Epoch>, Error> {
+        let mut epochs: Vec<Epoch> = Vec::new();
+        let mut epoch: Epoch = self.epoch_at(from)?.unwrap_or_else(|| self.genesis());
+        while epochs.len() < count {
+            epochs.push(epoch);
+            epoch = epoch.next()?;
+        }
+        Ok(epochs)
+    }
+
+    /// @notice Earliest timestamp at which the supply cap reaches `supply`
+    /// @dev Supplies up to the initial supply resolve to the inflation start,
+    ///      `None` is returned for supplies above the final cap
+    pub fn supply_reached_at(&self, supply: U256) -> Result<Option<U256>, Error> {
+        if supply <= self.init_supply {
+            return Ok(Some(self.inflation_start));
+        }
+        let mut epoch: Epoch = self.genesis();
+        while epoch.rate > 0.into() {
+            let next: Epoch = epoch.next()?;
+            if supply <= next.supply {
+                let missing: U256 = supply
+                    .checked_sub(epoch.supply)
+                    .ok_or(Error::Erc20CRVUnderFlow10)?;
+                // Round up, the cap only reaches `supply` once the full amount was emitted
+                let elapsed: U256 = missing
+                    .checked_add(epoch.rate)
+                    .ok_or(Error::Erc20CRVOverFlow37)?
+                    .checked_sub(1.into())
+                    .ok_or(Error::Erc20CRVUnderFlow11)?
+                    .checked_div(epoch.rate)
+                    .ok_or(Error::Erc20CRVAirthmeticError8)?;
+                return Ok(Some(
+                    epoch
+                        .start
+                        .checked_add(elapsed)
+                        .ok_or(Error::Erc20CRVOverFlow38)?,
+                ));
+            }
+            epoch = next;
+        }
+        Ok(None)
+    }
+}
@@ -1,8 +1,15 @@
-use crate::data::{self, AdminWhitelist};
+use crate::{
+    data::{
+        self, AdminWhitelist, BalanceCheckpointCounts, BalanceCheckpoints, MintedInEpoch,
+        MinterCaps, MinterShares, MinterSpent, SupplyCheckpoints,
+    },
+    emission::{Epoch, Schedule},
+};
 
 use alloc::{
     collections::BTreeMap,
     string::{String, ToString},
+    vec::Vec,
 };
 use casper_contract::{
     contract_api::{runtime, storage},
@@ -35,6 +42,17 @@ pub enum Erc20CrvEvent {
     RemoveAdmin {
         admin: Key,
     },
+    MinterGranted {
+        minter: Key,
+        share: U256,
+        cap: U256,
+    },
+    MinterRevoked {
+        minter: Key,
+    },
+    EnableBalanceHistory {
+        time: U256,
+    },
 }
 
 impl Erc20CrvEvent {
@@ -53,6 +71,13 @@ impl Erc20CrvEvent {
             Erc20CrvEvent::SetMinter { minter: _ } => "set_minter",
             Erc20CrvEvent::SetAdmin { admin: _ } => "set_admin",
             Erc20CrvEvent::RemoveAdmin { admin: _ } => "remove_admin",
+            Erc20CrvEvent::MinterGranted {
+                minter: _,
+                share: _,
+                cap: _,
+            } => "minter_granted",
+            Erc20CrvEvent::MinterRevoked { minter: _ } => "minter_revoked",
+            Erc20CrvEvent::EnableBalanceHistory { time: _ } => "enable_balance_history",
         }
         .to_string()
     }
@@ -69,6 +94,13 @@ pub trait ERC20CRV<Storage: ContractStorage>:
         data::set_hash(contract_hash);
         data::set_package_hash(package_hash);
         AdminWhitelist::init();
+        MinterShares::init();
+        MinterCaps::init();
+        MintedInEpoch::init();
+        MinterSpent::init();
+        BalanceCheckpoints::init();
+        BalanceCheckpointCounts::init();
+        SupplyCheckpoints::init();
         AdminWhitelist::instance().set(&self.get_caller(), true);
         CURVEERC20::init(self, data::get_hash(), data::get_package_hash());
         CURVEERC20::mint(
@@ -97,49 +129,55 @@ pub trait ERC20CRV<Storage: ContractStorage>:
     /// @dev Update mining rate and supply at the start of the epoch
     /// Any modifying mining call must also call this
     fn _update_mining_parameters(&self) {
-        let mut rate: U256 = data::get_rate();
-        let mut start_epoch_supply = data::get_start_epoch_supply();
-        data::set_start_epoch_time(
-            data::get_start_epoch_time()
-                .checked_add(data::RATE_REDUCTION_TIME)
-                .unwrap_or_revert_with(Error::Erc20CRVOverFlow9),
-        );
-
-        if data::get_is_updated() {
+        let current: Epoch = Epoch {
+            start: data::get_start_epoch_time(),
+            rate: data::get_rate(),
+            supply: data::get_start_epoch_supply(),
+        };
+        let next: Epoch = if data::get_is_updated() {
             data::set_mining_epoch(
                 data::get_mining_epoch()
                     .checked_add(1.into())
                     .unwrap_or_revert_with(Error::Erc20CRVOverFlow10),
             );
+            current.next().unwrap_or_revert()
         } else {
             data::set_is_updated(true);
-        }
-
-        if rate == 0.into() {
-            rate = data::INITIAL_RATE;
-        } else {
-            start_epoch_supply = start_epoch_supply
-                .checked_add(
-                    rate.checked_mul(data::RATE_REDUCTION_TIME)
-                        .unwrap_or_revert_with(Error::Erc20CRVOverFlow22),
-                )
-                .unwrap_or_revert_with(Error::Erc20CRVAirthmeticError4);
-            data::set_start_epoch_supply(start_epoch_supply);
-            rate = (rate
-                .checked_mul(data::RATE_DENOMINATOR)
-                .unwrap_or_revert_with(Error::Erc20CRVOverFlow23))
-            .checked_div(data::RATE_REDUCTION_COEFFICIENT)
-            .unwrap_or_revert_with(Error::Erc20CRVAirthmeticError5);
-        }
-        data::set_rate(rate);
+            Epoch {
+                start: current.end().unwrap_or_revert(),
+                rate: data::INITIAL_RATE,
+                supply: current.supply,
+            }
+        };
+        data::set_start_epoch_time(next.start);
+        data::set_rate(next.rate);
+        data::set_start_epoch_supply(next.supply);
         let blocktime: u64 = runtime::get_blocktime().into();
         self.erc20_crv_emit(&Erc20CrvEvent::UpdateMiningParameters {
             time: U256::from(blocktime),
-            rate,
-            supply: data::get_start_epoch_supply(),
+            rate: next.rate,
+            supply: next.supply,
         });
     }
 
+    /// @dev Emission schedule derived from the stored mining parameters
+    fn _schedule(&self) -> Schedule {
+        let inflation_start: U256 = if data::get_is_updated() {
+            data::get_start_epoch_time()
+                .checked_sub(
+                    U256::from(data::get_mining_epoch().as_u128())
+                        .checked_mul(data::RATE_REDUCTION_TIME)
+                        .unwrap_or_revert_with(Error::Erc20CRVOverFlow36),
+                )
+                .unwrap_or_revert_with(Error::Erc20CRVUnderFlow9)
+        } else {
+            data::get_start_epoch_time()
+                .checked_add(data::RATE_REDUCTION_TIME)
+                .unwrap_or_revert_with(Error::Erc20CRVOverFlow9)
+        };
+        Schedule::new(inflation_start, data::get_init_supply())
+    }
+
     /// @notice Update mining rate and supply at the start of the epoch
     /// @dev Callable by any address, but only once per epochTotal supply becomes slightly larger if this function is called late
     /// Total supply becomes slightly larger if this function is called late
@@ -195,15 +233,9 @@ pub trait ERC20CRV<Storage: ContractStorage>:
 
     fn _available_supply(&self) -> U256 {
         let blocktime: u64 = runtime::get_blocktime().into();
-        let blocktime_sub_st_epoch: U256 = U256::from(blocktime)
-            .checked_sub(data::get_start_epoch_time())
-            .unwrap_or_revert_with(Error::Erc20CRVUnderFlow4);
-        let ans: U256 = blocktime_sub_st_epoch
-            .checked_mul(data::get_rate())
-            .unwrap_or_revert_with(Error::Erc20CRVOverFlow24);
-        data::get_start_epoch_supply()
-            .checked_add(ans)
-            .unwrap_or_revert_with(Error::Erc20CRVOverFlow16)
+        self._schedule()
+            .supply_cap_at(U256::from(blocktime))
+            .unwrap_or_revert()
     }
     ///@notice Current number of tokens in existence (claimed or unclaimed)
     fn available_supply(&self) -> U256 {
@@ -217,87 +249,39 @@ pub trait ERC20CRV<Storage: ContractStorage>:
         if start > end {
             runtime::revert(ApiError::from(Error::Erc20CRVStartGreaterThanEnd));
         }
-        let mut to_mint: U256 = 0.into();
-        let mut current_epoch_time: U256 = data::get_start_epoch_time();
-        let mut current_rate: U256 = data::get_rate();
-        if end
-            > current_epoch_time
-                .checked_add(data::RATE_REDUCTION_TIME)
-                .unwrap_or_revert_with(Error::Erc20CRVOverFlow1)
-        {
-            current_epoch_time = current_epoch_time
-                .checked_add(data::RATE_REDUCTION_TIME)
-                .unwrap_or_revert_with(Error::Erc20CRVOverFlow2);
-            current_rate = current_rate
-                .checked_mul(
-                    data::RATE_DENOMINATOR
-                        .checked_div(data::RATE_REDUCTION_COEFFICIENT)
-                        .unwrap_or_revert_with(Error::Erc20CRVUnderFlow2),
-                )
-                .unwrap_or_revert_with(Error::Erc20CRVAirthmeticError1);
-        }
-        if end
-            > current_epoch_time
-                .checked_add(data::RATE_REDUCTION_TIME)
-                .unwrap_or_revert_with(Error::Erc20CRVOverFlow3)
-        {
-            runtime::revert(ApiError::from(Error::Erc20CRVTooFarInFuture));
-        }
-        let mut current_end: U256;
-        let mut current_start: U256;
-
-        for _i in 0..999 {
-            if end >= current_epoch_time {
-                current_end = end;
-                if current_end
-                    > current_epoch_time
-                        .checked_add(data::RATE_REDUCTION_TIME)
-                        .unwrap_or_revert_with(Error::Erc20CRVOverFlow4)
-                {
-                    current_end = current_epoch_time
-                        .checked_add(data::RATE_REDUCTION_TIME)
-                        .unwrap_or_revert_with(Error::Erc20CRVOverFlow5);
-                }
-                current_start = start;
-                if current_start
-                    >= current_epoch_time
-                        .checked_add(data::RATE_REDUCTION_TIME)
-                        .unwrap_or_revert_with(Error::Erc20CRVOverFlow6)
-                {
-                    break;
-                } else if current_start < current_epoch_time {
-                    current_start = current_epoch_time;
-                }
-                let current_end_sub_current_st: U256 = current_end
-                    .checked_sub(current_start)
-                    .unwrap_or_revert_with(Error::Erc20CRVOverFlow7);
-                to_mint = to_mint
-                    .checked_add(current_rate)
-                    .unwrap_or_revert_with(Error::Erc20CRVOverFlow21)
-                    .checked_mul(current_end_sub_current_st)
-                    .unwrap_or_revert_with(Error::Erc20CRVAirthmeticError2);
-                if start >= current_epoch_time {
-                    break;
-                }
-            }
-            current_epoch_time = current_epoch_time
-                .checked_sub(data::RATE_REDUCTION_TIME)
-                .unwrap_or_revert_with(Error::Erc20CRVUnderFlow1);
-            current_rate = current_rate
-                .checked_mul(
-                    data::RATE_REDUCTION_COEFFICIENT
-                        .checked_div(data::RATE_DENOMINATOR)
-                        .unwrap_or_revert_with(Error::Erc20CRVUnderFlow5),
-                )
-                .unwrap_or_revert_with(Error::Erc20CRVAirthmeticError3);
-            if current_rate > data::INITIAL_RATE {
-                runtime::revert(ApiError::from(Error::Erc20CRVCurrRateLessThanInitRate));
-            }
+        self._schedule()
+            .mintable_between(start, end)
+            .unwrap_or_revert()
+    }
+    ///@notice Upcoming mining epochs of the emission curve
+    ///@dev The listing starts with the current epoch, or the first one before inflation starts
+    ///@param count Number of epochs to return
+    ///@return (epoch start, rate, supply cap at epoch start) of each epoch
+    fn emission_schedule(&self, count: U256) -> Vec<(U256, U256, U256)> {
+        if count > data::MAX_SCHEDULE_EPOCHS {
+            runtime::revert(ApiError::from(Error::Erc20CRVScheduleTooLong));
         }
-        to_mint
+        let blocktime: u64 = runtime::get_blocktime().into();
+        self._schedule()
+            .epochs_from(U256::from(blocktime), count.as_usize())
+            .unwrap_or_revert()
+            .into_iter()
+            .map(|epoch| (epoch.start, epoch.rate, epoch.supply))
+            .collect()
+    }
+    ///@notice Timestamp at which the supply cap reaches `supply`
+    ///@param supply Supply level to look up
+    ///@return Earliest timestamp with enough supply, `None` if it is never reached
+    fn supply_reached_at(&self, supply: U256) -> Option<U256> {
+        self._schedule()
+            .supply_reached_at(supply)
+            .unwrap_or_revert()
     }
     ///@notice Set the minter address
-    ///@dev Only callable once, when minter has not yet been set
+    ///@dev The minter is registered with the share of the emission not granted to other
+    ///     minters, taking over the share of the previous minter. It is not held to that
+    ///     share, gauges accrue at the full rate, but the unminted part of it is kept out
+    ///     of reach of the other minters
     ///@param minter Address of the minter
     fn set_minter(&self, minter: Key) {
         if !AdminWhitelist::instance().get(&self.get_caller()) {
@@ -306,8 +290,27 @@ pub trait ERC20CRV<Storage: ContractStorage>:
         if minter == zero_address() || minter == account_zero_address() {
             runtime::revert(ApiError::from(Error::Erc20CRVZeroAddress1));
         }
+        let previous: Key = data::get_minter();
+        if previous != zero_address() {
+            self._set_minter_quota(&previous, 0.into(), 0.into());
+        }
+        let others: U256 = data::get_total_minter_shares()
+            .checked_sub(MinterShares::instance().get(&minter))
+            .unwrap_or_revert_with(Error::Erc20CRVUnderFlow16);
+        let share: U256 = data::RATE_DENOMINATOR
+            .checked_sub(others)
+            .unwrap_or_revert_with(Error::Erc20CRVUnderFlow17);
+        if share == 0.into() {
+            runtime::revert(ApiError::from(Error::Erc20CRVNoShareLeft));
+        }
+        self._set_minter_quota(&minter, share, 0.into());
         data::set_minter(minter);
         self.erc20_crv_emit(&Erc20CrvEvent::SetMinter { minter });
+        self.erc20_crv_emit(&Erc20CrvEvent::MinterGranted {
+            minter,
+            share,
+            cap: 0.into(),
+        });
     }
     ///@notice Set the new admin.
     ///@dev After all is set up, admin only can change the token name
@@ -335,12 +338,148 @@ pub trait ERC20CRV<Storage: ContractStorage>:
         AdminWhitelist::instance().set(&admin, false);
         self.erc20_crv_emit(&Erc20CrvEvent::RemoveAdmin { admin });
     }
+    ///@notice Grant `minter` a minting quota
+    ///@dev Exactly one of `share` and `cap` must be set, granting again replaces the quota.
+    ///     The shares of all minters, including the one set with `set_minter`, add up to
+    ///     at most `RATE_DENOMINATOR`
+    ///@param minter Address allowed to mint
+    ///@param share Share of the emission from now on, scaled by `RATE_DENOMINATOR`
+    ///@param cap Absolute amount mintable per epoch
+    fn add_minter(&self, minter: Key, share: U256, cap: U256) {
+        if !AdminWhitelist::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::Erc20CRVAdminOnly3));
+        }
+        if minter == zero_address() || minter == account_zero_address() {
+            runtime::revert(ApiError::from(Error::Erc20CRVZeroAddress2));
+        }
+        if (share == 0.into()) == (cap == 0.into()) {
+            runtime::revert(ApiError::from(Error::Erc20CRVInvalidQuota));
+        }
+        if share > data::RATE_DENOMINATOR {
+            runtime::revert(ApiError::from(Error::Erc20CRVShareTooLarge));
+        }
+        self._set_minter_quota(&minter, share, cap);
+        self.erc20_crv_emit(&Erc20CrvEvent::MinterGranted { minter, share, cap });
+    }
+    ///@notice Revoke the minting quota of `minter`
+    ///@param minter Address to remove from the minter registry
+    fn remove_minter(&self, minter: Key) {
+        if !AdminWhitelist::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::Erc20CRVAdminOnly4));
+        }
+        if !self._is_minter(&minter) {
+            runtime::revert(ApiError::from(Error::Erc20CRVMinterNotFound));
+        }
+        self._set_minter_quota(&minter, 0.into(), 0.into());
+        self.erc20_crv_emit(&Erc20CrvEvent::MinterRevoked { minter });
+    }
+    ///@dev Store the quota of `minter`, keeping the total of all shares within `RATE_DENOMINATOR`
+    fn _set_minter_quota(&self, minter: &Key, share: U256, cap: U256) {
+        let total_shares: U256 = data::get_total_minter_shares()
+            .checked_sub(MinterShares::instance().get(minter))
+            .unwrap_or_revert_with(Error::Erc20CRVUnderFlow16)
+            .checked_add(share)
+            .unwrap_or_revert_with(Error::Erc20CRVOverFlow46);
+        if total_shares > data::RATE_DENOMINATOR {
+            runtime::revert(ApiError::from(Error::Erc20CRVTotalShareTooLarge));
+        }
+        data::set_total_minter_shares(total_shares);
+        MinterShares::instance().set(minter, share);
+        MinterCaps::instance().set(minter, cap);
+        // The emission up to now is not part of the share
+        MinterSpent::instance().set(minter, self._share_of_emission(share));
+    }
+    fn _is_minter(&self, minter: &Key) -> bool {
+        MinterShares::instance().get(minter) > 0.into()
+            || MinterCaps::instance().get(minter) > 0.into()
+    }
+    ///@dev Mining epoch minter quotas are accounted against
+    fn _current_epoch(&self) -> Epoch {
+        let blocktime: u64 = runtime::get_blocktime().into();
+        self._schedule()
+            .epoch_at(U256::from(blocktime))
+            .unwrap_or_revert()
+            .unwrap_or(Epoch {
+                start: data::get_start_epoch_time(),
+                rate: 0.into(),
+                supply: data::get_init_supply(),
+            })
+    }
+    ///@dev Part of the tokens emitted so far that falls to `share`
+    fn _share_of_emission(&self, share: U256) -> U256 {
+        self._available_supply()
+            .saturating_sub(data::get_init_supply())
+            .checked_mul(share)
+            .unwrap_or_revert_with(Error::Erc20CRVOverFlow39)
+            .checked_div(data::RATE_DENOMINATOR)
+            .unwrap_or_revert_with(Error::Erc20CRVAirthmeticError9)
+    }
+    ///@dev Amount accounted against the quota of `minter`, since the grant for a share
+    ///     and within `epoch` for a cap
+    fn _minter_spent(&self, minter: &Key, epoch: &Epoch) -> U256 {
+        if MinterShares::instance().get(minter) > 0.into() {
+            MinterSpent::instance().get(minter)
+        } else {
+            MintedInEpoch::instance().get(minter, epoch.start)
+        }
+    }
+    fn _minter_allowance(&self, minter: &Key, epoch: &Epoch) -> U256 {
+        let share: U256 = MinterShares::instance().get(minter);
+        let quota: U256 = if share > 0.into() {
+            self._share_of_emission(share)
+        } else {
+            MinterCaps::instance().get(minter)
+        };
+        quota.saturating_sub(self._minter_spent(minter, epoch))
+    }
+    fn _spend_minter_allowance(&self, minter: &Key, amount: U256) {
+        let epoch: Epoch = self._current_epoch();
+        // Gauges accrue at the full rate and are claimed late, only the supply bounds them
+        if *minter != data::get_minter() && amount > self._minter_allowance(minter, &epoch) {
+            runtime::revert(ApiError::from(Error::Erc20CRVExceedsMinterAllowance));
+        }
+        let spent: U256 = self
+            ._minter_spent(minter, &epoch)
+            .checked_add(amount)
+            .unwrap_or_revert_with(Error::Erc20CRVOverFlow41);
+        if MinterShares::instance().get(minter) > 0.into() {
+            MinterSpent::instance().set(minter, spent);
+        } else {
+            MintedInEpoch::instance().set(minter, epoch.start, spent);
+        }
+    }
+    ///@dev Unminted share of the minter set with `set_minter`, unavailable to other minters
+    fn _reserved_supply(&self) -> U256 {
+        let minter: Key = data::get_minter();
+        if minter == zero_address() {
+            return 0.into();
+        }
+        self._minter_allowance(&minter, &self._current_epoch())
+    }
+    ///@notice Amount `minter` can still mint
+    ///@dev Accrues continuously for a share and resets every epoch for a cap. The minter
+    ///     set with `set_minter` may mint past it, up to the available supply
+    ///@param minter Address to check
+    ///@return Remaining allowance of `minter`
+    fn minter_allowance(&self, minter: Key) -> U256 {
+        self._minter_allowance(&minter, &self._current_epoch())
+    }
+    ///@notice Quota granted to `minter`
+    ///@param minter Address to check
+    ///@return (share, cap) of `minter`, both zero when not registered
+    fn minter_quota(&self, minter: Key) -> (U256, U256) {
+        (
+            MinterShares::instance().get(&minter),
+            MinterCaps::instance().get(&minter),
+        )
+    }
     ///@notice Mint `amount` tokens and assign them to `to`
     ///@dev Emits a Transfer event originating from 0x00
     ///@param to The account that will receive the created tokens
     ///@param amount The amount that will be created
     fn mint(&self, to: Address, amount: U256) -> Result<(), Error> {
-        if Address::from(self.get_caller()) != Address::from(data::get_minter()) {
+        let caller: Key = self.get_caller();
+        if !self._is_minter(&caller) {
             runtime::revert(ApiError::from(Error::Erc20CRVMinterOnly));
         }
         if to == Address::from(zero_address()) {
@@ -354,13 +493,21 @@ pub trait ERC20CRV<Storage: ContractStorage>:
         {
             self._update_mining_parameters();
         }
+        self._spend_minter_allowance(&caller, amount);
         let total_supply: U256 = CURVEERC20::total_supply(self)
             .checked_add(amount)
             .unwrap_or_revert_with(Error::Erc20CRVOverFlow18);
-        if total_supply > self.available_supply() {
+        let mut available_supply: U256 = self.available_supply();
+        if caller != data::get_minter() {
+            available_supply = available_supply.saturating_sub(self._reserved_supply());
+        }
+        if total_supply > available_supply {
             runtime::revert(ApiError::from(Error::Erc20CRVExceedsAllowableMint));
         }
+        self._seed_balance_history(&to);
         CURVEERC20::mint(self, to, amount).unwrap_or_revert();
+        self._checkpoint_balance(&to);
+        self._checkpoint_total_supply();
 
         self.erc20_crv_emit(&Erc20CrvEvent::Transfer {
             from: Address::from(zero_address()),
@@ -384,7 +531,11 @@ pub trait ERC20CRV<Storage: ContractStorage>:
     ///@dev Emits a Transfer event with a destination of 0x00
     ///@param value The amount that will be burned
     fn burn(&self, value: U256) -> Result<(), Error> {
-        CURVEERC20::burn(self, Address::from(self.get_caller()), value).unwrap_or_revert();
+        let owner: Address = Address::from(self.get_caller());
+        self._seed_balance_history(&owner);
+        CURVEERC20::burn(self, owner, value).unwrap_or_revert();
+        self._checkpoint_balance(&owner);
+        self._checkpoint_total_supply();
         self.erc20_crv_emit(&Erc20CrvEvent::Transfer {
             from: Address::from(self.get_caller()),
             to: Address::from(zero_address()),
@@ -392,6 +543,171 @@ pub trait ERC20CRV<Storage: ContractStorage>:
         });
         Ok(())
     }
+    ///@notice Transfer `amount` tokens from the caller to `recipient`
+    ///@dev Records balance checkpoints once the balance history is enabled
+    fn transfer(&self, recipient: Address, amount: U256) -> Result<(), Erc20Error> {
+        let owner: Address = Address::from(self.get_caller());
+        self._seed_balance_history(&owner);
+        self._seed_balance_history(&recipient);
+        CURVEERC20::transfer(self, recipient, amount)?;
+        self._checkpoint_balance(&owner);
+        self._checkpoint_balance(&recipient);
+        Ok(())
+    }
+    ///@notice Transfer `amount` tokens from `owner` to `recipient` using the caller's allowance
+    ///@dev Records balance checkpoints once the balance history is enabled
+    fn transfer_from(
+        &self,
+        owner: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<(), Erc20Error> {
+        self._seed_balance_history(&owner);
+        self._seed_balance_history(&recipient);
+        CURVEERC20::transfer_from(self, owner, recipient, amount)?;
+        self._checkpoint_balance(&owner);
+        self._checkpoint_balance(&recipient);
+        Ok(())
+    }
+    ///@notice Start recording balance and total supply checkpoints
+    ///@dev Only callable once by an admin, history is available from this point on
+    fn enable_balance_history(&self) {
+        if !AdminWhitelist::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::Erc20CRVAdminOnly5));
+        }
+        if data::get_balance_history_start() != 0.into() {
+            runtime::revert(ApiError::from(Error::Erc20CRVHistoryAlreadyEnabled));
+        }
+        let blocktime: u64 = runtime::get_blocktime().into();
+        data::set_balance_history_start(U256::from(blocktime));
+        self._checkpoint_total_supply();
+        self.erc20_crv_emit(&Erc20CrvEvent::EnableBalanceHistory {
+            time: U256::from(blocktime),
+        });
+    }
+    ///@dev Record the balance `owner` held when the history was enabled, before its first change
+    fn _seed_balance_history(&self, owner: &Address) {
+        let history_start: U256 = data::get_balance_history_start();
+        if history_start == 0.into() || BalanceCheckpointCounts::instance().get(owner) > 0.into() {
+            return;
+        }
+        let balance: U256 = CURVEERC20::balance_of(self, *owner);
+        BalanceCheckpoints::instance().set(owner, 0.into(), (history_start, balance));
+        BalanceCheckpointCounts::instance().set(owner, 1.into());
+    }
+    ///@dev Record the current balance of `owner`, replacing a checkpoint from the same block
+    fn _checkpoint_balance(&self, owner: &Address) {
+        if data::get_balance_history_start() == 0.into() {
+            return;
+        }
+        let blocktime: u64 = runtime::get_blocktime().into();
+        let checkpoint: (U256, U256) =
+            (U256::from(blocktime), CURVEERC20::balance_of(self, *owner));
+        let count: U256 = BalanceCheckpointCounts::instance().get(owner);
+        let last: U256 = count
+            .checked_sub(1.into())
+            .unwrap_or_revert_with(Error::Erc20CRVUnderFlow12);
+        if BalanceCheckpoints::instance().get(owner, last).0 == checkpoint.0 {
+            BalanceCheckpoints::instance().set(owner, last, checkpoint);
+        } else {
+            BalanceCheckpoints::instance().set(owner, count, checkpoint);
+            BalanceCheckpointCounts::instance().set(
+                owner,
+                count
+                    .checked_add(1.into())
+                    .unwrap_or_revert_with(Error::Erc20CRVOverFlow42),
+            );
+        }
+    }
+    ///@dev Record the current total supply, replacing a checkpoint from the same block
+    fn _checkpoint_total_supply(&self) {
+        if data::get_balance_history_start() == 0.into() {
+            return;
+        }
+        let blocktime: u64 = runtime::get_blocktime().into();
+        let checkpoint: (U256, U256) = (U256::from(blocktime), CURVEERC20::total_supply(self));
+        let count: U256 = data::get_supply_checkpoint_count();
+        if count > 0.into() {
+            let last: U256 = count
+                .checked_sub(1.into())
+                .unwrap_or_revert_with(Error::Erc20CRVUnderFlow13);
+            if SupplyCheckpoints::instance().get(&last).0 == checkpoint.0 {
+                SupplyCheckpoints::instance().set(&last, checkpoint);
+                return;
+            }
+        }
+        SupplyCheckpoints::instance().set(&count, checkpoint);
+        data::set_supply_checkpoint_count(
+            count
+                .checked_add(1.into())
+                .unwrap_or_revert_with(Error::Erc20CRVOverFlow43),
+        );
+    }
+    ///@dev Binary search for the value of the last checkpoint at or before `t`
+    ///     The first checkpoint is always at the history start, so `t` must not precede it
+    fn _find_checkpoint<F: Fn(U256) -> (U256, U256)>(
+        &self,
+        count: U256,
+        t: U256,
+        checkpoint: F,
+    ) -> U256 {
+        let mut min: U256 = 0.into();
+        let mut max: U256 = count
+            .checked_sub(1.into())
+            .unwrap_or_revert_with(Error::Erc20CRVUnderFlow14);
+        while min < max {
+            let mid: U256 = min
+                .checked_add(max)
+                .unwrap_or_revert_with(Error::Erc20CRVOverFlow44)
+                .checked_add(1.into())
+                .unwrap_or_revert_with(Error::Erc20CRVOverFlow45)
+                / 2;
+            if checkpoint(mid).0 <= t {
+                min = mid;
+            } else {
+                max = mid
+                    .checked_sub(1.into())
+                    .unwrap_or_revert_with(Error::Erc20CRVUnderFlow15);
+            }
+        }
+        checkpoint(min).1
+    }
+    ///@notice Balance of `owner` at timestamp `t`
+    ///@param owner Address to look up
+    ///@param t Timestamp, not before the balance history was enabled
+    ///@return Balance of `owner` at `t`
+    fn balance_of_at(&self, owner: Address, t: U256) -> U256 {
+        let history_start: U256 = data::get_balance_history_start();
+        if history_start == 0.into() {
+            runtime::revert(ApiError::from(Error::Erc20CRVHistoryDisabled1));
+        }
+        if t < history_start {
+            runtime::revert(ApiError::from(Error::Erc20CRVBeforeHistoryStart1));
+        }
+        let count: U256 = BalanceCheckpointCounts::instance().get(&owner);
+        if count == 0.into() {
+            // Untouched since the history was enabled
+            return CURVEERC20::balance_of(self, owner);
+        }
+        self._find_checkpoint(count, t, |index| {
+            BalanceCheckpoints::instance().get(&owner, index)
+        })
+    }
+    ///@notice Total supply at timestamp `t`
+    ///@param t Timestamp, not before the balance history was enabled
+    ///@return Total supply at `t`
+    fn total_supply_at(&self, t: U256) -> U256 {
+        let history_start: U256 = data::get_balance_history_start();
+        if history_start == 0.into() {
+            runtime::revert(ApiError::from(Error::Erc20CRVHistoryDisabled2));
+        }
+        if t < history_start {
+            runtime::revert(ApiError::from(Error::Erc20CRVBeforeHistoryStart2));
+        }
+        self._find_checkpoint(data::get_supply_checkpoint_count(), t, |index| {
+            SupplyCheckpoints::instance().get(&index)
+        })
+    }
     fn named_keys_erc20crv(
         &self,
         name: String,
@@ -442,6 +758,29 @@ pub trait ERC20CRV<Storage: ContractStorage>:
                 event.insert("admin", admin.to_string());
                 storage::new_uref(event);
             }
+            Erc20CrvEvent::MinterGranted { minter, share, cap } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", erc20_crv_event.type_name());
+                event.insert("minter", minter.to_string());
+                event.insert("share", share.to_string());
+                event.insert("cap", cap.to_string());
+                storage::new_uref(event);
+            }
+            Erc20CrvEvent::MinterRevoked { minter } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", erc20_crv_event.type_name());
+                event.insert("minter", minter.to_string());
+                storage::new_uref(event);
+            }
+            Erc20CrvEvent::EnableBalanceHistory { time } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", package.to_string());
+                event.insert("event_type", erc20_crv_event.type_name());
+                event.insert("time", time.to_string());
+                storage::new_uref(event);
+            }
         };
     }
 }
@@ -1,5 +1,6 @@
 #![no_std]
 extern crate alloc;
 pub mod data;
+pub mod emission;
 mod erc20_crv;
 pub use erc20_crv::ERC20CRV;
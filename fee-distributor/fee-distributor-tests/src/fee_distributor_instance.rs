@@ -3,7 +3,6 @@ use std::time::SystemTime;
 use casper_types::{
     account::AccountHash, bytesrepr::FromBytes, runtime_args, CLTyped, Key, RuntimeArgs, U256,
 };
-use casperlabs_contract_utils::key_to_str;
 use casperlabs_test_env::{TestContract, TestEnv};
 
 pub struct FEEDISTRIBUTORInstance(TestContract);
@@ -100,9 +99,9 @@ impl FEEDISTRIBUTORInstance {
         self.0.query_named_key(String::from("last_token_time"))
     }
 
-    pub fn ve_supply<T: Into<Key>>(&self, week: T) -> U256 {
+    pub fn ve_supply(&self, week: U256) -> U256 {
         self.0
-            .query_dictionary("ve_supply", key_to_str(&week.into()))
+            .query_dictionary("ve_supply", week.to_string())
             .unwrap_or_default()
     }
     pub fn future_admin(&self) -> Key {
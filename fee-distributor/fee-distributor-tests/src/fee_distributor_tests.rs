@@ -63,6 +63,18 @@ fn deploy() -> (
     FEEDISTRIBUTORInstance,
     TestContract,
     u64,
+) {
+    let (env, owner, instance, erc20, _, time_now) = deploy_with_voting_escrow();
+    (env, owner, instance, erc20, time_now)
+}
+
+fn deploy_with_voting_escrow() -> (
+    TestEnv,
+    AccountHash,
+    FEEDISTRIBUTORInstance,
+    TestContract,
+    TestContract,
+    u64,
 ) {
     let env = TestEnv::new();
     let owner = env.next_user();
@@ -98,7 +110,7 @@ fn deploy() -> (
         time_now,
     );
 
-    (env, owner, instance, erc20, time_now)
+    (env, owner, instance, erc20, voting_escrow, time_now)
 }
 
 #[test]
@@ -142,6 +154,44 @@ fn test_ve_for_at() {
     ); //depends on time
 }
 
+#[test]
+fn test_ve_for_at_across_split() {
+    let (env, owner, instance, _, voting_escrow, time_now) = deploy_with_voting_escrow();
+    voting_escrow.call_contract(
+        owner,
+        "split_lock",
+        runtime_args! {
+            "lock_id" => U256::from(0),
+            "value" => U256::from(2000 * TEN_E_NINE)
+        },
+        time_now,
+    );
+    let week: U256 = (U256::from(time_now) / WEEK + 1) * WEEK;
+    let later: u64 = week.as_u64();
+    instance.checkpoint_total_supply(owner, later);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_FOR_AT),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "user" => Key::from(owner),
+            "timestamp" => week
+        },
+        later,
+    );
+    let ve_for_at: U256 = env.query_account_named_key(owner, &[VE_FOR_AT.into()]);
+    assert!(ve_for_at > 0.into());
+    // The split off lock neither earns fees nor dilutes the supply they are shared by
+    assert_eq!(
+        ve_for_at,
+        instance.ve_supply(week),
+        "Split lock dilutes the supply"
+    );
+}
+
 #[test]
 fn test_checkpoint_total_supply() {
     let (_, owner, instance, _, time_now) = deploy();
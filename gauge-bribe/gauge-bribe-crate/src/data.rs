@@ -0,0 +1,184 @@
+use alloc::{format, string::String};
+use casper_types::{ContractHash, ContractPackageHash, Key, U256};
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
+use casperlabs_contract_utils::{get_key, set_key, Dict};
+use common::{keys::*, utils::*};
+
+pub const WEEK: U256 = U256([604800000, 0, 0, 0]);
+/// Rewards for a week stay claimable for this long after the week starts
+pub const CLAIM_WINDOW: U256 = U256([2419200000, 0, 0, 0]);
+pub const MAX_BATCH_CLAIMS: usize = 50;
+
+/// Mirror of the GaugeController `Point` returned by `points_weight`
+#[derive(Clone, Copy, CLTyped, ToBytes, FromBytes, Default)]
+pub struct Point {
+    pub bias: U256,
+    pub slope: U256,
+}
+
+/// Mirror of the GaugeController `VotedSlope` returned by `vote_user_slopes`
+#[derive(Clone, Copy, CLTyped, ToBytes, FromBytes, Default)]
+pub struct VotedSlope {
+    pub slope: U256,
+    pub power: U256,
+    pub end: U256,
+}
+
+fn bribe_key(dict: &str, gauge: &Key, token: &Key, week: &U256) -> String {
+    hash(format!(
+        "{}_{}_{}_{}",
+        dict,
+        gauge.to_formatted_string(),
+        token.to_formatted_string(),
+        week
+    ))
+}
+
+fn user_bribe_key(dict: &str, user: &Key, gauge: &Key, token: &Key, week: &U256) -> String {
+    hash(format!(
+        "{}_{}_{}_{}_{}",
+        dict,
+        user.to_formatted_string(),
+        gauge.to_formatted_string(),
+        token.to_formatted_string(),
+        week
+    ))
+}
+
+pub const REWARD_TOTAL: &str = "reward_total";
+pub struct RewardTotal {
+    dict: Dict,
+}
+
+impl RewardTotal {
+    pub fn instance() -> RewardTotal {
+        RewardTotal {
+            dict: Dict::instance(REWARD_TOTAL),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(REWARD_TOTAL)
+    }
+
+    pub fn get(&self, gauge: &Key, token: &Key, week: &U256) -> U256 {
+        self.dict
+            .get(&bribe_key(REWARD_TOTAL, gauge, token, week))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, gauge: &Key, token: &Key, week: &U256, value: U256) {
+        self.dict
+            .set(&bribe_key(REWARD_TOTAL, gauge, token, week), value);
+    }
+}
+
+pub const CLAIMED_TOTAL: &str = "claimed_total";
+pub struct ClaimedTotal {
+    dict: Dict,
+}
+
+impl ClaimedTotal {
+    pub fn instance() -> ClaimedTotal {
+        ClaimedTotal {
+            dict: Dict::instance(CLAIMED_TOTAL),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CLAIMED_TOTAL)
+    }
+
+    pub fn get(&self, gauge: &Key, token: &Key, week: &U256) -> U256 {
+        self.dict
+            .get(&bribe_key(CLAIMED_TOTAL, gauge, token, week))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, gauge: &Key, token: &Key, week: &U256, value: U256) {
+        self.dict
+            .set(&bribe_key(CLAIMED_TOTAL, gauge, token, week), value);
+    }
+}
+
+pub const CLAIMED: &str = "claimed";
+pub struct Claimed {
+    dict: Dict,
+}
+
+impl Claimed {
+    pub fn instance() -> Claimed {
+        Claimed {
+            dict: Dict::instance(CLAIMED),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CLAIMED)
+    }
+
+    pub fn get(&self, user: &Key, gauge: &Key, token: &Key, week: &U256) -> bool {
+        self.dict
+            .get(&user_bribe_key(CLAIMED, user, gauge, token, week))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, user: &Key, gauge: &Key, token: &Key, week: &U256, value: bool) {
+        self.dict
+            .set(&user_bribe_key(CLAIMED, user, gauge, token, week), value);
+    }
+}
+
+pub const DEPOSITS: &str = "deposits";
+pub struct Deposits {
+    dict: Dict,
+}
+
+impl Deposits {
+    pub fn instance() -> Deposits {
+        Deposits {
+            dict: Dict::instance(DEPOSITS),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(DEPOSITS)
+    }
+
+    pub fn get(&self, depositor: &Key, gauge: &Key, token: &Key, week: &U256) -> U256 {
+        self.dict
+            .get(&user_bribe_key(DEPOSITS, depositor, gauge, token, week))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, depositor: &Key, gauge: &Key, token: &Key, week: &U256, value: U256) {
+        self.dict.set(
+            &user_bribe_key(DEPOSITS, depositor, gauge, token, week),
+            value,
+        );
+    }
+}
+
+pub fn get_controller() -> Key {
+    get_key(CONTROLLER).unwrap_or_else(zero_address)
+}
+
+pub fn set_controller(controller: Key) {
+    set_key(CONTROLLER, controller);
+}
+
+pub fn get_contract_hash() -> ContractHash {
+    get_key(SELF_CONTRACT_HASH).unwrap_or_default()
+}
+
+pub fn set_contract_hash(contract_hash: ContractHash) {
+    set_key(SELF_CONTRACT_HASH, contract_hash);
+}
+
+pub fn get_package_hash() -> ContractPackageHash {
+    get_key(SELF_CONTRACT_PACKAGE_HASH).unwrap_or_default()
+}
+
+pub fn set_package_hash(package_hash: ContractPackageHash) {
+    set_key(SELF_CONTRACT_PACKAGE_HASH, package_hash);
+}
@@ -0,0 +1,37 @@
+use alloc::string::{String, ToString};
+use casper_types::{Key, U256};
+
+pub enum GaugeBribeEvent {
+    RewardDeposited {
+        depositor: Key,
+        gauge: Key,
+        token: Key,
+        week: U256,
+        amount: U256,
+    },
+    RewardClaimed {
+        user: Key,
+        gauge: Key,
+        token: Key,
+        week: U256,
+        amount: U256,
+    },
+    RewardRefunded {
+        depositor: Key,
+        gauge: Key,
+        token: Key,
+        week: U256,
+        amount: U256,
+    },
+}
+
+impl GaugeBribeEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            GaugeBribeEvent::RewardDeposited { .. } => "rewardDeposited",
+            GaugeBribeEvent::RewardClaimed { .. } => "rewardClaimed",
+            GaugeBribeEvent::RewardRefunded { .. } => "rewardRefunded",
+        }
+        .to_string()
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{data::*, event::GaugeBribeEvent};
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use casper_contract::{
+    contract_api::{
+        runtime::{self, get_blocktime},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs, U128, U256};
+use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use common::errors::*;
+use crv20::{self, Address};
+
+/// @notice Vote incentive marketplace on top of the GaugeController
+/// @dev Anyone can deposit reward tokens for a (gauge, week). Once the week starts,
+///      voters claim pro rata to the bias their vote contributes to the gauge at
+///      that week, i.e. `slope * (end - week)` over the gauge's `points_weight` bias.
+///      Whatever is not claimed within `CLAIM_WINDOW` is refundable to depositors.
+pub trait GAUGEBRIBE<Storage: ContractStorage>: ContractContext<Storage> {
+    /// @notice Contract constructor
+    /// @param controller GaugeController contract package address
+    fn init(
+        &self,
+        controller: Key,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        RewardTotal::init();
+        ClaimedTotal::init();
+        Claimed::init();
+        Deposits::init();
+        set_controller(controller);
+        set_contract_hash(contract_hash);
+        set_package_hash(package_hash);
+    }
+
+    /// @notice Deposit `amount` of `token` as a reward for voters of `gauge` during `week`
+    /// @param gauge Gauge address, must be added to the GaugeController and not killed
+    /// @param token Reward token address
+    /// @param week Start of the rewarded week, rounded down to a whole week
+    /// @param amount Amount of `token` to deposit
+    fn deposit_reward(&mut self, gauge: Key, token: Key, week: U256, amount: U256) {
+        if amount == 0.into() {
+            runtime::revert(Error::GaugeBribeZeroAmount);
+        }
+        let rounded_week: U256 = week
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError1)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError2);
+        if rounded_week != week {
+            runtime::revert(Error::GaugeBribeInvalidWeek);
+        }
+        let current_week: U256 = U256::from(u64::from(get_blocktime()))
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError3)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError4);
+        if week < current_week {
+            runtime::revert(Error::GaugeBribeWeekInPast);
+        }
+        // Reverts if the gauge is not added to the controller
+        let _gauge_type: (bool, U128) = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "gauge_types",
+            runtime_args! {"addr" => gauge},
+        );
+        // A killed gauge has no weight left to vote for
+        let killed: bool = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "gauge_is_killed",
+            runtime_args! {"addr" => gauge},
+        );
+        if killed {
+            runtime::revert(Error::GaugeBribeGaugeKilled);
+        }
+
+        let depositor: Key = self.get_caller();
+        let () = runtime::call_versioned_contract(
+            token.into_hash().unwrap_or_revert().into(),
+            None,
+            "transfer_from",
+            runtime_args! {
+                "owner" => Address::from(depositor),
+                "recipient" => Address::from(Key::from(get_package_hash())),
+                "amount" => amount
+            },
+        );
+        let reward_total: U256 = RewardTotal::instance()
+            .get(&gauge, &token, &week)
+            .checked_add(amount)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError5);
+        RewardTotal::instance().set(&gauge, &token, &week, reward_total);
+        let deposit: U256 = Deposits::instance()
+            .get(&depositor, &gauge, &token, &week)
+            .checked_add(amount)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError6);
+        Deposits::instance().set(&depositor, &gauge, &token, &week, deposit);
+        self.emit(&GaugeBribeEvent::RewardDeposited {
+            depositor,
+            gauge,
+            token,
+            week,
+            amount,
+        });
+    }
+
+    /// @notice Bias `user` contributes to `gauge` at `week`
+    /// @dev Reads the user's current vote, which is only the one in force at `week`
+    ///      when it was cast before `week`
+    fn _user_bias(&self, user: Key, gauge: Key, week: U256) -> U256 {
+        let last_vote: U256 = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "last_user_vote",
+            runtime_args! {"owner" => user, "spender" => gauge},
+        );
+        if last_vote >= week {
+            return 0.into();
+        }
+        let vote: VotedSlope = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "vote_user_slopes",
+            runtime_args! {"owner" => user, "spender" => gauge},
+        );
+        if vote.end <= week {
+            return 0.into();
+        }
+        vote.slope
+            .checked_mul(
+                vote.end
+                    .checked_sub(week)
+                    .unwrap_or_revert_with(Error::GaugeBribeArithmeticError7),
+            )
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError8)
+    }
+
+    fn _claimable(&self, user: Key, gauge: Key, token: Key, week: U256) -> U256 {
+        if Claimed::instance().get(&user, &gauge, &token, &week) {
+            return 0.into();
+        }
+        let reward_total: U256 = RewardTotal::instance().get(&gauge, &token, &week);
+        if reward_total == 0.into() {
+            return 0.into();
+        }
+        let user_bias: U256 = self._user_bias(user, gauge, week);
+        if user_bias == 0.into() {
+            return 0.into();
+        }
+        let gauge_point: Point = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "points_weight",
+            runtime_args! {"owner" => gauge, "spender" => week},
+        );
+        if gauge_point.bias == 0.into() {
+            return 0.into();
+        }
+        reward_total
+            .checked_mul(user_bias)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError9)
+            .checked_div(gauge_point.bias)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError10)
+    }
+
+    fn _claim(&mut self, user: Key, gauge: Key, token: Key, week: U256) -> U256 {
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        if now < week {
+            runtime::revert(Error::GaugeBribeClaimNotStarted);
+        }
+        if now
+            >= week
+                .checked_add(CLAIM_WINDOW)
+                .unwrap_or_revert_with(Error::GaugeBribeArithmeticError11)
+        {
+            runtime::revert(Error::GaugeBribeClaimExpired);
+        }
+        if Claimed::instance().get(&user, &gauge, &token, &week) {
+            runtime::revert(Error::GaugeBribeAlreadyClaimed);
+        }
+        let last_vote: U256 = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "last_user_vote",
+            runtime_args! {"owner" => user, "spender" => gauge},
+        );
+        if last_vote >= week {
+            // dev: vote was changed after the week started, claim before re-voting
+            runtime::revert(Error::GaugeBribeVoteChanged);
+        }
+        // Make sure points_weight is filled up to `week`
+        let () = runtime::call_versioned_contract(
+            get_controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "checkpoint_gauge",
+            runtime_args! {"addr" => gauge},
+        );
+        let amount: U256 = self._claimable(user, gauge, token, week);
+        if amount == 0.into() {
+            return amount;
+        }
+        Claimed::instance().set(&user, &gauge, &token, &week, true);
+        let claimed_total: U256 = ClaimedTotal::instance()
+            .get(&gauge, &token, &week)
+            .checked_add(amount)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError12);
+        ClaimedTotal::instance().set(&gauge, &token, &week, claimed_total);
+        let () = runtime::call_versioned_contract(
+            token.into_hash().unwrap_or_revert().into(),
+            None,
+            "transfer",
+            runtime_args! {
+                "recipient" => Address::from(user),
+                "amount" => amount
+            },
+        );
+        self.emit(&GaugeBribeEvent::RewardClaimed {
+            user,
+            gauge,
+            token,
+            week,
+            amount,
+        });
+        amount
+    }
+
+    /// @notice Claim the caller's share of `token` rewards for voting `gauge` in `week`
+    /// @param gauge Gauge address
+    /// @param token Reward token address
+    /// @param week Start of the rewarded week
+    /// @return Amount of `token` claimed
+    fn claim_reward(&mut self, gauge: Key, token: Key, week: U256) -> U256 {
+        let user: Key = self.get_caller();
+        self._claim(user, gauge, token, week)
+    }
+
+    /// @notice Claim several (gauge, token, week) rewards in one call
+    /// @param claims List of (gauge, token, week), at most `MAX_BATCH_CLAIMS`
+    /// @return Amount claimed for each entry
+    fn claim_many(&mut self, claims: Vec<(Key, Key, U256)>) -> Vec<U256> {
+        if claims.len() > MAX_BATCH_CLAIMS {
+            runtime::revert(Error::GaugeBribeTooManyClaims);
+        }
+        let user: Key = self.get_caller();
+        let mut amounts: Vec<U256> = Vec::new();
+        for (gauge, token, week) in claims {
+            amounts.push(self._claim(user, gauge, token, week));
+        }
+        amounts
+    }
+
+    /// @notice Return the caller's share of rewards nobody claimed before expiry
+    /// @param gauge Gauge address
+    /// @param token Reward token address
+    /// @param week Start of the rewarded week
+    /// @return Amount of `token` refunded
+    fn refund(&mut self, gauge: Key, token: Key, week: U256) -> U256 {
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        if now
+            < week
+                .checked_add(CLAIM_WINDOW)
+                .unwrap_or_revert_with(Error::GaugeBribeArithmeticError13)
+        {
+            runtime::revert(Error::GaugeBribeNotExpired);
+        }
+        let depositor: Key = self.get_caller();
+        let deposit: U256 = Deposits::instance().get(&depositor, &gauge, &token, &week);
+        if deposit == 0.into() {
+            runtime::revert(Error::GaugeBribeNothingToRefund);
+        }
+        let reward_total: U256 = RewardTotal::instance().get(&gauge, &token, &week);
+        let unclaimed: U256 = reward_total
+            .checked_sub(ClaimedTotal::instance().get(&gauge, &token, &week))
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError14);
+        let amount: U256 = deposit
+            .checked_mul(unclaimed)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError15)
+            .checked_div(reward_total)
+            .unwrap_or_revert_with(Error::GaugeBribeArithmeticError16);
+        Deposits::instance().set(&depositor, &gauge, &token, &week, 0.into());
+        if amount > 0.into() {
+            let () = runtime::call_versioned_contract(
+                token.into_hash().unwrap_or_revert().into(),
+                None,
+                "transfer",
+                runtime_args! {
+                    "recipient" => Address::from(depositor),
+                    "amount" => amount
+                },
+            );
+        }
+        self.emit(&GaugeBribeEvent::RewardRefunded {
+            depositor,
+            gauge,
+            token,
+            week,
+            amount,
+        });
+        amount
+    }
+
+    /// @notice Amount of `token` `user` can claim for voting `gauge` in `week`
+    /// @dev Reads the controller without checkpointing, so it may return 0 until
+    ///      the gauge has been checkpointed past `week`
+    fn claimable(&self, user: Key, gauge: Key, token: Key, week: U256) -> U256 {
+        self._claimable(user, gauge, token, week)
+    }
+
+    fn emit(&self, gauge_bribe_event: &GaugeBribeEvent) {
+        match gauge_bribe_event {
+            GaugeBribeEvent::RewardDeposited {
+                depositor: user,
+                gauge,
+                token,
+                week,
+                amount,
+            }
+            | GaugeBribeEvent::RewardClaimed {
+                user,
+                gauge,
+                token,
+                week,
+                amount,
+            }
+            | GaugeBribeEvent::RewardRefunded {
+                depositor: user,
+                gauge,
+                token,
+                week,
+                amount,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_bribe_event.type_name());
+                event.insert("user", user.to_string());
+                event.insert("gauge", gauge.to_string());
+                event.insert("token", token.to_string());
+                event.insert("week", week.to_string());
+                event.insert("amount", amount.to_string());
+                storage::new_uref(event);
+            }
+        };
+    }
+}
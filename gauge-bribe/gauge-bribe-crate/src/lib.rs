@@ -0,0 +1,10 @@
+#![no_std]
+
+extern crate alloc;
+
+pub mod data;
+pub mod event;
+mod gauge_bribe;
+
+pub use common::utils;
+pub use gauge_bribe::GAUGEBRIBE;
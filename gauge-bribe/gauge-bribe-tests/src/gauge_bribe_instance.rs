@@ -0,0 +1,181 @@
+use casper_types::{
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, CLTyped, Key, RuntimeArgs, U128, U256,
+};
+use casperlabs_test_env::{TestContract, TestEnv};
+use std::time::SystemTime;
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+pub fn deploy_erc20(
+    env: &TestEnv,
+    contract_name: &str,
+    sender: AccountHash,
+    time: u64,
+) -> TestContract {
+    TestContract::new(
+        env,
+        "curve-erc20.wasm",
+        contract_name,
+        sender,
+        runtime_args! {
+            "initial_supply" => U256::from(0),
+            "name" => "Token",
+            "symbol" => "ERC20",
+            "decimals" => 9_u8
+        },
+        time,
+    )
+}
+
+pub fn deploy_voting_escrow(
+    env: &TestEnv,
+    sender: AccountHash,
+    token_addr: Key,
+    time: u64,
+) -> TestContract {
+    TestContract::new(
+        env,
+        "voting-escrow.wasm",
+        "Voting Escrow",
+        sender,
+        runtime_args! {
+            "token_addr" => token_addr,
+            "name" => String::from("VotingEscrow"),
+            "symbol" => String::from("VE"),
+            "version" => String::from("1"),
+        },
+        time,
+    )
+}
+
+pub fn deploy_gauge_controller(
+    env: &TestEnv,
+    sender: AccountHash,
+    token: Key,
+    voting_escrow: Key,
+    time: u64,
+) -> TestContract {
+    TestContract::new(
+        env,
+        "gauge-controller-token.wasm",
+        "Gauge Controller",
+        sender,
+        runtime_args! {
+            "voting_escrow" => voting_escrow,
+            "token" => token,
+        },
+        time,
+    )
+}
+
+pub fn add_gauge(gauge_controller: &TestContract, sender: AccountHash, gauge: Key, time: u64) {
+    gauge_controller.call_contract(
+        sender,
+        "add_gauge",
+        runtime_args! {
+            "addr" => gauge,
+            "gauge_type" => (false, U128::from(0)),
+            "weight" => None::<U256>
+        },
+        time,
+    );
+}
+
+pub struct GAUGEBRIBEInstance(TestContract);
+
+impl GAUGEBRIBEInstance {
+    pub fn new_deploy(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        controller: Key,
+        time: u64,
+    ) -> GAUGEBRIBEInstance {
+        GAUGEBRIBEInstance(TestContract::new(
+            env,
+            "gauge-bribe.wasm",
+            contract_name,
+            sender,
+            runtime_args! {
+                "controller" => controller
+            },
+            time,
+        ))
+    }
+
+    pub fn deposit_reward(
+        &self,
+        sender: AccountHash,
+        gauge: Key,
+        token: Key,
+        week: U256,
+        amount: U256,
+        time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "deposit_reward",
+            runtime_args! {
+                "gauge" => gauge,
+                "token" => token,
+                "week" => week,
+                "amount" => amount
+            },
+            time,
+        );
+    }
+
+    pub fn claim_reward(&self, sender: AccountHash, gauge: Key, token: Key, week: U256, time: u64) {
+        self.0.call_contract(
+            sender,
+            "claim_reward",
+            runtime_args! {
+                "gauge" => gauge,
+                "token" => token,
+                "week" => week
+            },
+            time,
+        );
+    }
+
+    pub fn claim_many(&self, sender: AccountHash, claims: Vec<(Key, Key, U256)>, time: u64) {
+        self.0.call_contract(
+            sender,
+            "claim_many",
+            runtime_args! {
+                "claims" => claims
+            },
+            time,
+        );
+    }
+
+    pub fn refund(&self, sender: AccountHash, gauge: Key, token: Key, week: U256, time: u64) {
+        self.0.call_contract(
+            sender,
+            "refund",
+            runtime_args! {
+                "gauge" => gauge,
+                "token" => token,
+                "week" => week
+            },
+            time,
+        );
+    }
+
+    pub fn package_hash(&self) -> [u8; 32] {
+        self.0.package_hash()
+    }
+
+    pub fn key_value<T: CLTyped + FromBytes>(&self, key: String) -> T {
+        self.0.query_named_key(key)
+    }
+
+    pub fn controller(&self) -> Key {
+        self.0.query_named_key(String::from("controller"))
+    }
+}
@@ -0,0 +1,371 @@
+use crate::gauge_bribe_instance::*;
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256};
+use casperlabs_test_env::{TestContract, TestEnv};
+use common::keys::*;
+
+const NAME: &str = "GAUGEBRIBE";
+const WEEK: u64 = 604800000;
+const YEAR: u64 = 31536000000;
+const CLAIM_WINDOW: u64 = 4 * WEEK;
+const LOCK_AMOUNT: u128 = 1000000000000000;
+const BRIBE_AMOUNT: u128 = 1000000000000;
+
+struct TestSetup {
+    env: TestEnv,
+    bribe: GAUGEBRIBEInstance,
+    owner: AccountHash,
+    voter_1: AccountHash,
+    voter_2: AccountHash,
+    reward_token: TestContract,
+    gauge_controller: TestContract,
+    controller: Key,
+    gauge: Key,
+    week: U256,
+    block_time: u64,
+}
+
+fn lock(
+    token: &TestContract,
+    voting_escrow: &TestContract,
+    owner: AccountHash,
+    voter: AccountHash,
+    unlock_time: u64,
+    block_time: u64,
+) {
+    let value: U256 = LOCK_AMOUNT.into();
+    token.call_contract(
+        owner,
+        "mint",
+        runtime_args! {
+            "to" => Key::Account(voter),
+            "amount" => value
+        },
+        block_time,
+    );
+    token.call_contract(
+        voter,
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(voting_escrow.package_hash()),
+            "amount" => value
+        },
+        block_time,
+    );
+    voting_escrow.call_contract(
+        voter,
+        "create_lock",
+        runtime_args! {
+            "value" => value,
+            "unlock_time" => U256::from(unlock_time)
+        },
+        block_time,
+    );
+}
+
+/// Two voters put all of their power on the same gauge, the first one locked for
+/// twice as long, and the owner funds a bribe for the following week
+fn deploy() -> TestSetup {
+    let block_time = now();
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let voter_1 = env.next_user();
+    let voter_2 = env.next_user();
+    let token = deploy_erc20(&env, "token", owner, block_time);
+    let reward_token = deploy_erc20(&env, "reward_token", owner, block_time);
+    let voting_escrow =
+        deploy_voting_escrow(&env, owner, Key::Hash(token.package_hash()), block_time);
+    let gauge_controller = deploy_gauge_controller(
+        &env,
+        owner,
+        Key::Hash(token.package_hash()),
+        Key::Hash(voting_escrow.package_hash()),
+        block_time,
+    );
+    let bribe = GAUGEBRIBEInstance::new_deploy(
+        &env,
+        NAME,
+        owner,
+        Key::Hash(gauge_controller.package_hash()),
+        block_time,
+    );
+    let gauge = Key::Hash([7u8; 32]);
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => String::from("type"),
+            "weight" => None::<U256>
+        },
+        block_time,
+    );
+    add_gauge(&gauge_controller, owner, gauge, block_time);
+
+    lock(
+        &token,
+        &voting_escrow,
+        owner,
+        voter_1,
+        block_time + 2 * YEAR,
+        block_time,
+    );
+    lock(
+        &token,
+        &voting_escrow,
+        owner,
+        voter_2,
+        block_time + YEAR,
+        block_time,
+    );
+    for voter in [voter_1, voter_2] {
+        gauge_controller.call_contract(
+            voter,
+            "vote_for_gauge_weights",
+            runtime_args! {
+                "gauge_addr" => gauge,
+                "user_weight" => U256::from(10000)
+            },
+            block_time,
+        );
+    }
+
+    let bribe_amount: U256 = BRIBE_AMOUNT.into();
+    reward_token.call_contract(
+        owner,
+        "mint",
+        runtime_args! {
+            "to" => Key::Account(owner),
+            "amount" => bribe_amount
+        },
+        block_time,
+    );
+    reward_token.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(bribe.package_hash()),
+            "amount" => bribe_amount
+        },
+        block_time,
+    );
+    let week: U256 = U256::from((block_time / WEEK + 1) * WEEK);
+    bribe.deposit_reward(
+        owner,
+        gauge,
+        Key::Hash(reward_token.package_hash()),
+        week,
+        bribe_amount,
+        block_time,
+    );
+    TestSetup {
+        env,
+        bribe,
+        owner,
+        voter_1,
+        voter_2,
+        reward_token,
+        controller: Key::Hash(gauge_controller.package_hash()),
+        gauge_controller,
+        gauge,
+        week,
+        block_time,
+    }
+}
+
+fn balance_of(env: &TestEnv, token: &TestContract, user: AccountHash, block_time: u64) -> U256 {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        user,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF),
+            "package_hash" => Key::Hash(token.package_hash()),
+            "owner" => Key::Account(user)
+        },
+        block_time,
+    );
+    env.query_account_named_key(user, &[BALANCE_OF.into()])
+}
+
+#[test]
+fn test_deploy() {
+    let setup = deploy();
+    assert_eq!(setup.bribe.controller(), setup.controller);
+    // The whole bribe sits in the contract until it is claimed
+    assert_eq!(
+        balance_of(
+            &setup.env,
+            &setup.reward_token,
+            setup.owner,
+            setup.block_time
+        ),
+        U256::zero()
+    );
+}
+
+#[test]
+fn test_claim_pro_rata() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    let claim_time = setup.week.as_u64() + WEEK / 7;
+    setup.bribe.claim_reward(
+        setup.voter_1,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        claim_time,
+    );
+    setup.bribe.claim_reward(
+        setup.voter_2,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        claim_time,
+    );
+    let claimed_1 = balance_of(&setup.env, &setup.reward_token, setup.voter_1, claim_time);
+    let claimed_2 = balance_of(&setup.env, &setup.reward_token, setup.voter_2, claim_time);
+    // Same slope, but the first lock ends roughly one year later
+    assert!(claimed_1 > claimed_2);
+    assert!(claimed_2 > U256::zero());
+    let total: U256 = BRIBE_AMOUNT.into();
+    assert!(claimed_1 + claimed_2 <= total);
+    assert!(claimed_1 + claimed_2 >= total - 2);
+}
+
+#[test]
+fn test_claim_many() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    let claim_time = setup.week.as_u64() + WEEK / 7;
+    setup.bribe.claim_many(
+        setup.voter_1,
+        vec![(setup.gauge, reward_token, setup.week)],
+        claim_time,
+    );
+    assert!(balance_of(&setup.env, &setup.reward_token, setup.voter_1, claim_time) > U256::zero());
+}
+
+#[test]
+fn test_refund_unclaimed() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    let claim_time = setup.week.as_u64() + WEEK / 7;
+    setup.bribe.claim_reward(
+        setup.voter_1,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        claim_time,
+    );
+    let claimed_1 = balance_of(&setup.env, &setup.reward_token, setup.voter_1, claim_time);
+    let refund_time = setup.week.as_u64() + CLAIM_WINDOW;
+    setup.bribe.refund(
+        setup.owner,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        refund_time,
+    );
+    let total: U256 = BRIBE_AMOUNT.into();
+    assert_eq!(
+        balance_of(&setup.env, &setup.reward_token, setup.owner, refund_time),
+        total - claimed_1
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_claim_twice() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    let claim_time = setup.week.as_u64() + WEEK / 7;
+    for _ in 0..2 {
+        setup.bribe.claim_reward(
+            setup.voter_1,
+            setup.gauge,
+            reward_token,
+            setup.week,
+            claim_time,
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_claim_before_week() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    setup.bribe.claim_reward(
+        setup.voter_1,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        setup.block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_claim_after_expiry() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    setup.bribe.claim_reward(
+        setup.voter_1,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        setup.week.as_u64() + CLAIM_WINDOW,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_refund_before_expiry() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    setup.bribe.refund(
+        setup.owner,
+        setup.gauge,
+        reward_token,
+        setup.week,
+        setup.week.as_u64() + WEEK,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_deposit_unaligned_week() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    setup.bribe.deposit_reward(
+        setup.owner,
+        setup.gauge,
+        reward_token,
+        setup.week + 1,
+        U256::from(1),
+        setup.block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_deposit_killed_gauge() {
+    let setup = deploy();
+    let reward_token = Key::Hash(setup.reward_token.package_hash());
+    setup.gauge_controller.call_contract(
+        setup.owner,
+        "kill_gauge",
+        runtime_args! {
+            "addr" => setup.gauge
+        },
+        setup.block_time,
+    );
+    setup.bribe.deposit_reward(
+        setup.owner,
+        setup.gauge,
+        reward_token,
+        setup.week + WEEK,
+        U256::from(1),
+        setup.block_time,
+    );
+}
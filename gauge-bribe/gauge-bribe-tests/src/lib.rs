@@ -0,0 +1,5 @@
+#[cfg(test)]
+pub mod gauge_bribe_tests;
+
+#[cfg(test)]
+pub mod gauge_bribe_instance;
@@ -0,0 +1,357 @@
+#![no_main]
+#![no_std]
+extern crate alloc;
+use alloc::{collections::BTreeSet, format, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+};
+use casperlabs_contract_utils::{ContractContext, OnChainContractStorage};
+use gauge_bribe_crate::{self, data, GAUGEBRIBE};
+
+#[derive(Default)]
+struct GaugeBribe(OnChainContractStorage);
+impl ContractContext<OnChainContractStorage> for GaugeBribe {
+    fn storage(&self) -> &OnChainContractStorage {
+        &self.0
+    }
+}
+
+impl GAUGEBRIBE<OnChainContractStorage> for GaugeBribe {}
+impl GaugeBribe {
+    fn constructor(
+        &mut self,
+        controller: Key,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) {
+        GAUGEBRIBE::init(self, controller, contract_hash, package_hash);
+    }
+}
+
+/// @notice Contract constructor
+/// @param controller GaugeController contract package address
+#[no_mangle]
+fn constructor() {
+    let controller: Key = runtime::get_named_arg("controller");
+    let package_hash: ContractPackageHash = runtime::get_named_arg("package_hash");
+    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
+    GaugeBribe::default().constructor(controller, contract_hash, package_hash);
+}
+
+/// @notice Deposit `amount` of `token` as a reward for voters of `gauge` during `week`
+/// @param gauge Gauge address, must be added to the GaugeController
+/// @param token Reward token address
+/// @param week Start of the rewarded week, rounded down to a whole week
+/// @param amount Amount of `token` to deposit
+#[no_mangle]
+fn deposit_reward() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let amount: U256 = runtime::get_named_arg("amount");
+    GaugeBribe::default().deposit_reward(gauge, token, week, amount);
+}
+
+/// @notice Claim the caller's share of `token` rewards for voting `gauge` in `week`
+/// @param gauge Gauge address
+/// @param token Reward token address
+/// @param week Start of the rewarded week
+/// @return Amount of `token` claimed
+#[no_mangle]
+fn claim_reward() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: U256 = GaugeBribe::default().claim_reward(gauge, token, week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Claim several (gauge, token, week) rewards in one call
+/// @param claims List of (gauge, token, week)
+/// @return Amount claimed for each entry
+#[no_mangle]
+fn claim_many() {
+    let claims: Vec<(Key, Key, U256)> = runtime::get_named_arg("claims");
+    let ret: Vec<U256> = GaugeBribe::default().claim_many(claims);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Return the caller's share of rewards nobody claimed before expiry
+/// @param gauge Gauge address
+/// @param token Reward token address
+/// @param week Start of the rewarded week
+/// @return Amount of `token` refunded
+#[no_mangle]
+fn refund() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: U256 = GaugeBribe::default().refund(gauge, token, week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Amount of `token` `user` can claim for voting `gauge` in `week`
+#[no_mangle]
+fn claimable() {
+    let user: Key = runtime::get_named_arg("user");
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: U256 = GaugeBribe::default().claimable(user, gauge, token, week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+// Variables
+
+#[no_mangle]
+fn reward_total() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: U256 = data::RewardTotal::instance().get(&gauge, &token, &week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn claimed_total() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: U256 = data::ClaimedTotal::instance().get(&gauge, &token, &week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn claimed() {
+    let user: Key = runtime::get_named_arg("user");
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: bool = data::Claimed::instance().get(&user, &gauge, &token, &week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn deposits() {
+    let depositor: Key = runtime::get_named_arg("depositor");
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let week: U256 = runtime::get_named_arg("week");
+    let ret: U256 = data::Deposits::instance().get(&depositor, &gauge, &token, &week);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn controller() {
+    runtime::ret(CLValue::from_t(data::get_controller()).unwrap_or_revert())
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "constructor",
+        vec![
+            Parameter::new("controller", Key::cl_type()),
+            Parameter::new("package_hash", ContractPackageHash::cl_type()),
+            Parameter::new("contract_hash", ContractHash::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Groups(vec![Group::new("constructor")]),
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "deposit_reward",
+        vec![
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claim_reward",
+        vec![
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claim_many",
+        vec![Parameter::new("claims", Vec::<(Key, Key, U256)>::cl_type())],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "refund",
+        vec![
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claimable",
+        vec![
+            Parameter::new("user", Key::cl_type()),
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reward_total",
+        vec![
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claimed_total",
+        vec![
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claimed",
+        vec![
+            Parameter::new("user", Key::cl_type()),
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "deposits",
+        vec![
+            Parameter::new("depositor", Key::cl_type()),
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("week", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "controller",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+#[no_mangle]
+fn call() {
+    // Store contract in the account's named keys. Contract name must be same for all new versions of the contracts
+    let contract_name: alloc::string::String = runtime::get_named_arg("contract_name");
+
+    // If this is the first deployment
+    if !runtime::has_key(&format!("{}_package_hash", contract_name)) {
+        // Build new package.
+        let (package_hash, access_token) = storage::create_contract_package_at_hash();
+        // add a first version to this package
+        let (contract_hash, _): (ContractHash, _) =
+            storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+
+        let controller: Key = runtime::get_named_arg("controller");
+        let constructor_args = runtime_args! {
+            "controller" => controller,
+            "package_hash" => package_hash,
+            "contract_hash" => contract_hash,
+        };
+
+        // Add the constructor group to the package hash with a single URef.
+        let constructor_access: URef =
+            storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+                .unwrap_or_revert()
+                .pop()
+                .unwrap_or_revert();
+
+        // Call the constructor entry point
+        let _: () =
+            runtime::call_versioned_contract(package_hash, None, "constructor", constructor_args);
+
+        // Remove all URefs from the constructor group, so no one can call it for the second time.
+        let mut urefs = BTreeSet::new();
+        urefs.insert(constructor_access);
+        storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+            .unwrap_or_revert();
+
+        runtime::put_key(
+            &format!("{}_package_hash", contract_name),
+            package_hash.into(),
+        );
+        runtime::put_key(
+            &format!("{}_package_hash_wrapped", contract_name),
+            storage::new_uref(package_hash).into(),
+        );
+        runtime::put_key(
+            &format!("{}_contract_hash", contract_name),
+            contract_hash.into(),
+        );
+        runtime::put_key(
+            &format!("{}_contract_hash_wrapped", contract_name),
+            storage::new_uref(contract_hash).into(),
+        );
+        runtime::put_key(
+            &format!("{}_package_access_token", contract_name),
+            access_token.into(),
+        );
+    }
+    // If contract package did already exist
+    else {
+        // get the package
+        let package_hash: ContractPackageHash =
+            runtime::get_key(&format!("{}_package_hash", contract_name))
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap()
+                .into();
+        // create new version and install it
+        let (contract_hash, _): (ContractHash, _) =
+            storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+
+        // update contract hash
+        runtime::put_key(
+            &format!("{}_contract_hash", contract_name),
+            contract_hash.into(),
+        );
+        runtime::put_key(
+            &format!("{}_contract_hash_wrapped", contract_name),
+            storage::new_uref(contract_hash).into(),
+        );
+    }
+}
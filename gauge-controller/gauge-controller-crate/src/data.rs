@@ -4,7 +4,7 @@ use alloc::{
     vec::Vec,
 };
 use casper_contract::{contract_api::runtime::get_call_stack, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{system::CallStackElement, ContractPackageHash, Key, U256};
+use casper_types::{system::CallStackElement, ContractPackageHash, Key, U128, U256};
 use casper_types_derive::{CLTyped, FromBytes, ToBytes};
 use casperlabs_contract_utils::*;
 use common::{
@@ -16,6 +16,18 @@ use common::{
 pub const WEEK: U256 = U256([604800000, 0, 0, 0]); // all future times are rounded by week
 pub const WEIGHT_VOTE_DELAY: U256 = U256([86400000, 0, 0, 0]);
 pub const MULTIPLIER: U256 = U256([1000000000, 0, 0, 0]);
+pub const MAX_BATCH_VOTES: usize = 50;
+pub const MAX_VOTE_WEEKS: usize = 209; // votes end with their lock, at most 4 years ahead
+pub const MAX_PROJECTION_WEEKS: U256 = U256([52, 0, 0, 0]);
+pub const MAX_GAUGES_PAGE: U256 = U256([50, 0, 0, 0]);
+pub const MAX_REWARD_TOKENS: usize = 8;
+pub const MAX_GAUGE_NAME_LENGTH: usize = 64; // bytes
+
+// gauge kinds stored in `GaugeMetadata::kind`
+pub const GAUGE_KIND_OTHER: u8 = 0;
+pub const GAUGE_KIND_LIQUIDITY_V3: u8 = 1;
+pub const GAUGE_KIND_REWARD_ONLY: u8 = 2;
+pub const GAUGE_KIND_WRAPPER: u8 = 3;
 
 #[derive(Clone, Copy, CLTyped, ToBytes, FromBytes, Default)]
 pub struct Point {
@@ -30,6 +42,32 @@ pub struct VotedSlope {
     pub end: U256,
 }
 
+#[derive(Clone, CLTyped, ToBytes, FromBytes)]
+pub struct GaugeMetadata {
+    pub name: String,
+    pub kind: u8,
+    pub lp_token: Key,
+    pub reward_tokens: Vec<Key>,
+}
+
+impl Default for GaugeMetadata {
+    fn default() -> Self {
+        GaugeMetadata {
+            name: String::new(),
+            kind: GAUGE_KIND_OTHER,
+            lp_token: zero_address(),
+            reward_tokens: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, CLTyped, ToBytes, FromBytes)]
+pub struct GaugeInfo {
+    pub addr: Key,
+    pub gauge_type: (bool, U128),
+    pub metadata: GaugeMetadata,
+}
+
 pub struct GaugeTypeNames {
     dict: Dict,
 }
@@ -310,6 +348,130 @@ impl PointsWeight {
     }
 }
 
+pub struct PointsWeightCap {
+    dict: Dict,
+}
+
+impl PointsWeightCap {
+    pub fn instance() -> PointsWeightCap {
+        PointsWeightCap {
+            dict: Dict::instance(POINTS_WEIGHT_CAP_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(POINTS_WEIGHT_CAP_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, recipient: &U256) -> U256 {
+        let key_: String = key_and_value_to_str(owner, recipient);
+        self.dict.get(key_.as_str()).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, recipient: &U256, value: U256) {
+        let key_: String = key_and_value_to_str(owner, recipient);
+        self.dict.set(key_.as_str(), value);
+    }
+}
+
+pub struct PointsCappedWeight {
+    dict: Dict,
+}
+
+impl PointsCappedWeight {
+    pub fn instance() -> PointsCappedWeight {
+        PointsCappedWeight {
+            dict: Dict::instance(POINTS_CAPPED_WEIGHT_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(POINTS_CAPPED_WEIGHT_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, recipient: &U256) -> U256 {
+        let key_: String = key_and_value_to_str(owner, recipient);
+        self.dict.get(key_.as_str()).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, recipient: &U256, value: U256) {
+        let key_: String = key_and_value_to_str(owner, recipient);
+        self.dict.set(key_.as_str(), value);
+    }
+}
+
+pub struct CappedWeightsCheckpointed {
+    dict: Dict,
+}
+
+impl CappedWeightsCheckpointed {
+    pub fn instance() -> CappedWeightsCheckpointed {
+        CappedWeightsCheckpointed {
+            dict: Dict::instance(CAPPED_WEIGHTS_CHECKPOINTED_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(CAPPED_WEIGHTS_CHECKPOINTED_DICT)
+    }
+
+    pub fn get(&self, owner: &U256) -> bool {
+        self.dict.get(&owner.to_string()).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &U256, value: bool) {
+        self.dict.set(&owner.to_string(), value);
+    }
+}
+
+pub struct PointsWeightCapsEnabled {
+    dict: Dict,
+}
+
+impl PointsWeightCapsEnabled {
+    pub fn instance() -> PointsWeightCapsEnabled {
+        PointsWeightCapsEnabled {
+            dict: Dict::instance(POINTS_WEIGHT_CAPS_ENABLED_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(POINTS_WEIGHT_CAPS_ENABLED_DICT)
+    }
+
+    pub fn get(&self, owner: &U256) -> bool {
+        self.dict.get(&owner.to_string()).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &U256, value: bool) {
+        self.dict.set(&owner.to_string(), value);
+    }
+}
+
+pub struct GaugeIndex {
+    dict: Dict,
+}
+
+impl GaugeIndex {
+    pub fn instance() -> GaugeIndex {
+        GaugeIndex {
+            dict: Dict::instance(GAUGE_INDEX_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(GAUGE_INDEX_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict.get(&key_to_str(owner)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set(&key_to_str(owner), value);
+    }
+}
+
 pub struct ChangesWeight {
     dict: Dict,
 }
@@ -605,6 +767,80 @@ impl TimeTypeWeight {
     }
 }
 
+pub struct GaugeIsKilled {
+    dict: Dict,
+}
+
+impl GaugeIsKilled {
+    pub fn instance() -> GaugeIsKilled {
+        GaugeIsKilled {
+            dict: Dict::instance(GAUGE_IS_KILLED_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(GAUGE_IS_KILLED_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> bool {
+        self.dict.get(&key_to_str(owner)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: bool) {
+        self.dict.set(&key_to_str(owner), value);
+    }
+}
+
+pub struct VoteDelegates {
+    dict: Dict,
+}
+
+impl VoteDelegates {
+    pub fn instance() -> VoteDelegates {
+        VoteDelegates {
+            dict: Dict::instance(VOTE_DELEGATES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(VOTE_DELEGATES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> Key {
+        self.dict
+            .get(&key_to_str(owner))
+            .unwrap_or_else(zero_address)
+    }
+
+    pub fn set(&self, owner: &Key, value: Key) {
+        self.dict.set(&key_to_str(owner), value);
+    }
+}
+
+pub struct GaugeMetadataRegistry {
+    dict: Dict,
+}
+
+impl GaugeMetadataRegistry {
+    pub fn instance() -> GaugeMetadataRegistry {
+        GaugeMetadataRegistry {
+            dict: Dict::instance(GAUGE_METADATA_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(GAUGE_METADATA_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> GaugeMetadata {
+        self.dict.get(&key_to_str(owner)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: GaugeMetadata) {
+        self.dict.set(&key_to_str(owner), value);
+    }
+}
+
 pub fn time_total() -> U256 {
     get_key(TIME_TOTAL).unwrap_or_default()
 }
@@ -652,6 +888,30 @@ pub fn set_n_gauges(n_gauges: i128) {
     set_key(N_GAUGES, i128_to_tuple(n_gauges));
 }
 
+pub fn weight_caps_enabled() -> bool {
+    get_key(WEIGHT_CAPS_ENABLED).unwrap_or_default()
+}
+
+pub fn set_weight_caps_enabled(weight_caps_enabled: bool) {
+    set_key(WEIGHT_CAPS_ENABLED, weight_caps_enabled);
+}
+
+pub fn time_weight_caps_enabled() -> U256 {
+    get_key(TIME_WEIGHT_CAPS_ENABLED).unwrap_or_default()
+}
+
+pub fn set_time_weight_caps_enabled(time_weight_caps_enabled: U256) {
+    set_key(TIME_WEIGHT_CAPS_ENABLED, time_weight_caps_enabled);
+}
+
+pub fn time_capped_weights() -> U256 {
+    get_key(TIME_CAPPED_WEIGHTS).unwrap_or_default()
+}
+
+pub fn set_time_capped_weights(time_capped_weights: U256) {
+    set_key(TIME_CAPPED_WEIGHTS, time_capped_weights);
+}
+
 pub fn voting_escrow() -> Key {
     get_key(VOTING_ESCROW).unwrap_or_else(zero_address)
 }
@@ -1,12 +1,15 @@
 use crate::alloc::string::ToString;
 use crate::data::{
-    self, get_package_hash, ChangesSum, ChangesWeight, GaugeTypeNames, GaugeTypes_, Gauges,
-    LastUserVote, Point, PointsSum, PointsTotal, PointsTypeWeight, PointsWeight, TimeSum,
-    TimeTypeWeight, TimeWeight, VoteUserPower, VoteUserSlopes, VotedSlope, MULTIPLIER, WEEK,
-    WEIGHT_VOTE_DELAY,
+    self, get_package_hash, CappedWeightsCheckpointed, ChangesSum, ChangesWeight, GaugeIndex,
+    GaugeInfo, GaugeIsKilled, GaugeMetadata, GaugeMetadataRegistry, GaugeTypeNames, GaugeTypes_,
+    Gauges, LastUserVote, Point, PointsCappedWeight, PointsSum, PointsTotal, PointsTypeWeight,
+    PointsWeight, PointsWeightCap, PointsWeightCapsEnabled, TimeSum, TimeTypeWeight, TimeWeight,
+    VoteDelegates, VoteUserPower, VoteUserSlopes, VotedSlope, GAUGE_KIND_WRAPPER, MAX_BATCH_VOTES,
+    MAX_GAUGES_PAGE, MAX_GAUGE_NAME_LENGTH, MAX_PROJECTION_WEEKS, MAX_REWARD_TOKENS,
+    MAX_VOTE_WEEKS, MULTIPLIER, WEEK, WEIGHT_VOTE_DELAY,
 };
 use alloc::collections::BTreeMap;
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use casper_contract::contract_api::storage;
 use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use casper_types::{runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, U128, U256};
@@ -52,6 +55,33 @@ pub enum GAUGECONLTROLLEREvent {
         gauge_type: i128,
         weight: U256,
     },
+    KillGauge {
+        addr: Key,
+        time: U256,
+        total_weight: U256,
+    },
+    NewGaugeWeightCap {
+        gauge_address: Key,
+        time: U256,
+        cap: U256,
+    },
+    SetWeightCapsEnabled {
+        enabled: bool,
+    },
+    SetVoteDelegate {
+        delegator: Key,
+        delegate: Key,
+    },
+    RevokeVoteDelegate {
+        delegator: Key,
+        delegate: Key,
+    },
+    SetGaugeMetadata {
+        addr: Key,
+        name: String,
+        kind: u8,
+        lp_token: Key,
+    },
 }
 
 impl GAUGECONLTROLLEREvent {
@@ -91,6 +121,31 @@ impl GAUGECONLTROLLEREvent {
                 gauge_type: _,
                 weight: _,
             } => "NewGauge",
+            GAUGECONLTROLLEREvent::KillGauge {
+                addr: _,
+                time: _,
+                total_weight: _,
+            } => "KillGauge",
+            GAUGECONLTROLLEREvent::NewGaugeWeightCap {
+                gauge_address: _,
+                time: _,
+                cap: _,
+            } => "NewGaugeWeightCap",
+            GAUGECONLTROLLEREvent::SetWeightCapsEnabled { enabled: _ } => "SetWeightCapsEnabled",
+            GAUGECONLTROLLEREvent::SetVoteDelegate {
+                delegator: _,
+                delegate: _,
+            } => "SetVoteDelegate",
+            GAUGECONLTROLLEREvent::RevokeVoteDelegate {
+                delegator: _,
+                delegate: _,
+            } => "RevokeVoteDelegate",
+            GAUGECONLTROLLEREvent::SetGaugeMetadata {
+                addr: _,
+                name: _,
+                kind: _,
+                lp_token: _,
+            } => "SetGaugeMetadata",
         }
         .to_string()
     }
@@ -143,6 +198,14 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
         PointsTotal::init();
         PointsTypeWeight::init();
         TimeTypeWeight::init();
+        GaugeIsKilled::init();
+        VoteDelegates::init();
+        PointsWeightCap::init();
+        PointsCappedWeight::init();
+        CappedWeightsCheckpointed::init();
+        PointsWeightCapsEnabled::init();
+        GaugeIndex::init();
+        GaugeMetadataRegistry::init();
     }
 
     fn commit_transfer_ownership(&mut self, addr: Key) {
@@ -180,11 +243,13 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
 
     fn checkpoint(&mut self) {
         self._get_total();
+        self._checkpoint_weight_caps();
     }
 
     fn checkpoint_gauge(&mut self, addr: Key) {
         self._get_weight(addr);
         self._get_total();
+        self._checkpoint_weight_caps();
     }
 
     /// @notice Fill historic gauge weights week-over-week for missed checkins
@@ -196,6 +261,7 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
         let mut t: U256 = self.time_weight(gauge_addr);
         if t > U256::from(0) {
             let mut pt: Point = self.points_weight(gauge_addr, t);
+            let cap: U256 = self.points_weight_cap(gauge_addr, t);
             for _ in 0..(500) {
                 if t > U256::from(u64::from(runtime::get_blocktime())) {
                     break;
@@ -223,6 +289,9 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                     pt.slope = 0.into();
                 }
                 PointsWeight::instance().set(&gauge_addr, &t, pt);
+                if cap > U256::from(0) {
+                    PointsWeightCap::instance().set(&gauge_addr, &t, cap);
+                }
                 if t > U256::from(u64::from(runtime::get_blocktime())) {
                     TimeWeight::instance().set(&gauge_addr, t);
                 }
@@ -310,7 +379,10 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                         .checked_sub(d_bias)
                         .unwrap_or_revert_with(Error::GaugeControllerUnderFlow5);
                     let d_slope: U256 = self.changes_sum(gauge_type, t);
-                    pt.slope = d_slope;
+                    pt.slope = pt
+                        .slope
+                        .checked_sub(d_slope)
+                        .unwrap_or_revert_with(Error::GaugeControllerUnderFlow25);
                 } else {
                     pt.bias = U256::from(0);
                     pt.slope = U256::from(0);
@@ -361,6 +433,194 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
     /// @return Value of relative weight normalized to 1e9
 
     fn _gauge_relative_weight(&mut self, addr: Key, time: U256) -> U256 {
+        let t: U256 = time
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError1)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError2);
+        // Capped weights are stored for every week up to the current one before reading them
+        self._checkpoint_weight_caps();
+        if CappedWeightsCheckpointed::instance().get(&t) {
+            return PointsCappedWeight::instance().get(&addr, &t);
+        }
+        if !self._weight_caps_enabled_at(t) {
+            return self._gauge_raw_relative_weight(addr, t);
+        }
+        // A future week cannot be checkpointed yet, clip it on the fly without storing
+        let index: U256 = GaugeIndex::instance().get(&addr);
+        if index == U256::from(0) {
+            return self._gauge_raw_relative_weight(addr, t);
+        }
+        self._capped_relative_weights(t)[index
+            .checked_sub(1.into())
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError15)
+            .as_usize()]
+    }
+
+    /// @notice Whether weight caps apply to week `t`
+    /// @dev The setting is constant after the last week it was changed for
+    /// @param t Week start
+    fn _weight_caps_enabled_at(&self, t: U256) -> bool {
+        PointsWeightCapsEnabled::instance()
+            .get(&core::cmp::min(t, data::time_weight_caps_enabled()))
+    }
+
+    /// @notice Turn weight caps on or off from next week on
+    /// @dev Weeks that already started keep the setting they started with
+    /// @param enabled Whether weight caps apply
+    fn _set_weight_caps_enabled(&mut self, enabled: bool) {
+        self._checkpoint_weight_caps();
+        let next_time: U256 = (U256::from(u64::from(runtime::get_blocktime()))
+            .checked_add(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError18))
+        .checked_div(WEEK)
+        .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError19)
+        .checked_mul(WEEK)
+        .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError20);
+        // Fill the weeks since the last change with the previous setting
+        let mut t: U256 = data::time_weight_caps_enabled();
+        if t > U256::from(0) {
+            let enabled_before: bool = PointsWeightCapsEnabled::instance().get(&t);
+            for _ in 0..(500) {
+                if t >= next_time {
+                    break;
+                }
+                t = t
+                    .checked_add(WEEK)
+                    .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError17);
+                PointsWeightCapsEnabled::instance().set(&t, enabled_before);
+            }
+        }
+        PointsWeightCapsEnabled::instance().set(&next_time, enabled);
+        data::set_time_weight_caps_enabled(next_time);
+        data::set_weight_caps_enabled(enabled);
+        // Every week before `next_time` is either checkpointed or uncapped by now
+        if data::time_capped_weights() < next_time {
+            data::set_time_capped_weights(next_time);
+        }
+    }
+
+    /// @notice Relative weights of all gauges at week `t`, clipped to their caps
+    /// @param t Week start
+    /// @return Capped relative weight of every gauge, in `gauges` order
+    fn _capped_relative_weights(&mut self, t: U256) -> Vec<U256> {
+        let mut points: Vec<(U256, U256)> = Vec::new();
+        for i in 0..data::n_gauges() {
+            let gauge: Key = self.gauges(U256::from(i));
+            let weight: U256 = self._gauge_raw_relative_weight(gauge, t);
+            let cap: U256 = self.points_weight_cap(gauge, t);
+            points.push((weight, cap));
+        }
+        self._apply_weight_caps(points)
+    }
+
+    /// @notice Clip relative weights to their caps
+    /// @param weights (relative weight, cap) of every gauge, a cap of 0 means no cap
+    /// @return Capped relative weight of every gauge, in the same order
+    fn _apply_weight_caps(&self, weights: Vec<(U256, U256)>) -> Vec<U256> {
+        // (relative weight, cap, capped) of every gauge
+        let mut points: Vec<(U256, U256, bool)> = weights
+            .into_iter()
+            .map(|(weight, cap)| (weight, cap, false))
+            .collect();
+        // Clip gauges to their caps and hand the excess to the uncapped gauges
+        // in proportion to their weight, until no gauge exceeds its cap
+        for _ in 0..points.len() {
+            let mut excess: U256 = 0.into();
+            for (weight, cap, capped) in points.iter_mut() {
+                if !*capped && *cap > U256::from(0) && *weight > *cap {
+                    excess =
+                        excess
+                            .checked_add(weight.checked_sub(*cap).unwrap_or_revert_with(
+                                Error::GaugeControllerWeightCapArithmeticError3,
+                            ))
+                            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError4);
+                    *weight = *cap;
+                    *capped = true;
+                }
+            }
+            if excess == U256::from(0) {
+                break;
+            }
+            let mut uncapped_sum: U256 = 0.into();
+            for (weight, _, capped) in points.iter() {
+                if !*capped {
+                    uncapped_sum = uncapped_sum
+                        .checked_add(*weight)
+                        .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError5);
+                }
+            }
+            if uncapped_sum == U256::from(0) {
+                break;
+            }
+            for (weight, _, capped) in points.iter_mut() {
+                if !*capped {
+                    *weight = weight
+                        .checked_add(
+                            excess
+                                .checked_mul(*weight)
+                                .unwrap_or_revert_with(
+                                    Error::GaugeControllerWeightCapArithmeticError6,
+                                )
+                                .checked_div(uncapped_sum)
+                                .unwrap_or_revert_with(
+                                    Error::GaugeControllerWeightCapArithmeticError7,
+                                ),
+                        )
+                        .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError8);
+                }
+            }
+        }
+        points.iter().map(|(weight, _, _)| *weight).collect()
+    }
+
+    /// @notice Store the capped relative weights of all gauges for every week up to the current one
+    /// @dev Weeks are checkpointed once and in order, weeks without caps are skipped.
+    ///      A week that already started no longer changes, so later reads of it use the stored weights
+    fn _checkpoint_weight_caps(&mut self) {
+        let mut t: U256 = data::time_capped_weights();
+        let current_week: U256 = U256::from(u64::from(runtime::get_blocktime()))
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError12)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError13);
+        if t == U256::from(0) || t > current_week {
+            return;
+        }
+        // Nothing left to cap until caps are turned on again
+        if !data::weight_caps_enabled() && t >= data::time_weight_caps_enabled() {
+            return;
+        }
+        let mut gauges: Vec<Key> = Vec::new();
+        for i in 0..data::n_gauges() {
+            let gauge: Key = self.gauges(U256::from(i));
+            self._get_weight(gauge);
+            gauges.push(gauge);
+        }
+        self._get_total();
+        for _ in 0..(500) {
+            if t > current_week {
+                break;
+            }
+            if self._weight_caps_enabled_at(t) {
+                let weights: Vec<U256> = self._capped_relative_weights(t);
+                for (gauge, weight) in gauges.iter().zip(weights) {
+                    PointsCappedWeight::instance().set(gauge, &t, weight);
+                }
+                CappedWeightsCheckpointed::instance().set(&t, true);
+            }
+            t = t
+                .checked_add(WEEK)
+                .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError16);
+        }
+        data::set_time_capped_weights(t);
+    }
+
+    /// @notice Get Gauge relative weight before weight caps are applied
+    /// @param addr Gauge address
+    /// @param time Relative weight at the specified timestamp in the past or present
+    /// @return Value of relative weight normalized to 1e9
+    fn _gauge_raw_relative_weight(&mut self, addr: Key, time: U256) -> U256 {
         let t: U256 = time
             .checked_div(WEEK)
             .unwrap_or_revert_with(Error::GaugeControllerDivide2)
@@ -505,6 +765,188 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
         self._gauge_relative_weight(addr, time_)
     }
 
+    /// @notice Advance a (bias, slope) point from `cursor` week by week up to `t`
+    ///         the same way `_get_weight`/`_get_sum` do, without writing anything
+    /// @dev Weeks before `cursor` are already filled in, so they are read as stored
+    fn _project_bias<F, G>(
+        &self,
+        cursor: &mut U256,
+        pt: &mut Point,
+        t: U256,
+        stored: F,
+        changes: G,
+    ) -> U256
+    where
+        F: Fn(U256) -> Point,
+        G: Fn(U256) -> U256,
+    {
+        if *cursor == U256::from(0) {
+            return U256::from(0);
+        }
+        if t < *cursor {
+            return stored(t).bias;
+        }
+        while *cursor < t {
+            *cursor = cursor
+                .checked_add(WEEK)
+                .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError1);
+            let d_bias: U256 = pt
+                .slope
+                .checked_mul(WEEK)
+                .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError2);
+            if pt.bias > d_bias {
+                pt.bias = pt
+                    .bias
+                    .checked_sub(d_bias)
+                    .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError3);
+                pt.slope = pt
+                    .slope
+                    .checked_sub(changes(*cursor))
+                    .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError4);
+            } else {
+                pt.bias = U256::from(0);
+                pt.slope = U256::from(0);
+            }
+        }
+        pt.bias
+    }
+
+    /// @notice Project the weight of gauge `addr` over the next `weeks` weeks,
+    ///         assuming nobody changes their votes
+    /// @dev Read-only counterpart of `_get_weight`/`_get_total`: walks the scheduled
+    ///      `changes_weight`/`changes_sum` without checkpointing. While weight caps
+    ///      are enabled every gauge is projected, so relative weights can be capped
+    ///      the same way `gauge_relative_weight` does.
+    /// @param addr Gauge address
+    /// @param weeks Number of weeks to project, starting with the current week
+    /// @return List of (week start, absolute weight, relative weight normalized to 1e9)
+    fn projected_gauge_weights(&self, addr: Key, weeks: U256) -> Vec<(U256, U256, U256)> {
+        if weeks > MAX_PROJECTION_WEEKS {
+            runtime::revert(Error::GaugeControllerTooManyProjectionWeeks);
+        }
+        if GaugeTypes_::instance().get(&addr) == 0 {
+            runtime::revert(Error::GaugeControllerGaugeNotAdded4);
+        }
+        let n_gauge_types: i128 = data::n_gauge_types();
+
+        let mut t: U256 = U256::from(u64::from(runtime::get_blocktime()))
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError6)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError7);
+        // Capping needs the weight of every gauge, otherwise `addr` is enough.
+        // The setting of later weeks is the current one or the latest change
+        let caps_enabled: bool = data::weight_caps_enabled() || self._weight_caps_enabled_at(t);
+        let mut gauges: Vec<Key> = Vec::new();
+        if caps_enabled {
+            for i in 0..data::n_gauges() {
+                gauges.push(Gauges::instance().get(&U256::from(i)));
+            }
+        } else {
+            gauges.push(addr);
+        }
+        let index: usize = gauges
+            .iter()
+            .position(|gauge| *gauge == addr)
+            .unwrap_or_revert_with(Error::GaugeControllerGaugeNotAdded4);
+        let mut gauge_types: Vec<usize> = Vec::new();
+        let mut gauge_cursors: Vec<U256> = Vec::new();
+        let mut gauge_pts: Vec<Point> = Vec::new();
+        for gauge in gauges.iter() {
+            let cursor: U256 = TimeWeight::instance().get(gauge);
+            gauge_types.push(
+                GaugeTypes_::instance()
+                    .get(gauge)
+                    .checked_sub(1)
+                    .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError5)
+                    as usize,
+            );
+            gauge_pts.push(PointsWeight::instance().get(gauge, &cursor));
+            gauge_cursors.push(cursor);
+        }
+        // Caps are constant after the last filled week
+        let cap_cursors: Vec<U256> = gauge_cursors.clone();
+        let mut sum_cursors: Vec<U256> = Vec::new();
+        let mut sum_pts: Vec<Point> = Vec::new();
+        let mut type_weight_cursors: Vec<U256> = Vec::new();
+        for type_id in 0..n_gauge_types {
+            let cursor: U256 = TimeSum::instance().get(&U256::from(type_id));
+            sum_pts.push(PointsSum::instance().get(&type_id, &cursor));
+            sum_cursors.push(cursor);
+            type_weight_cursors.push(TimeTypeWeight::instance().get(&U256::from(type_id)));
+        }
+
+        let mut projection: Vec<(U256, U256, U256)> = Vec::new();
+        for _ in 0..weeks.as_usize() {
+            let mut gauge_weights: Vec<U256> = Vec::new();
+            for (i, gauge) in gauges.iter().enumerate() {
+                gauge_weights.push(self._project_bias(
+                    &mut gauge_cursors[i],
+                    &mut gauge_pts[i],
+                    t,
+                    |week| PointsWeight::instance().get(gauge, &week),
+                    |week| ChangesWeight::instance().get(gauge, &week),
+                ));
+            }
+            // Type weights are stored up to their last scheduled change and constant after it
+            let mut type_weights: Vec<U256> = Vec::new();
+            for type_id in 0..n_gauge_types {
+                let cursor: U256 = type_weight_cursors[type_id as usize];
+                type_weights
+                    .push(PointsTypeWeight::instance().get(&type_id, &core::cmp::min(t, cursor)));
+            }
+            let mut total_weight: U256 = U256::from(0);
+            for type_id in 0..n_gauge_types {
+                let index: usize = type_id as usize;
+                let type_sum: U256 = self._project_bias(
+                    &mut sum_cursors[index],
+                    &mut sum_pts[index],
+                    t,
+                    |week| PointsSum::instance().get(&type_id, &week),
+                    |week| ChangesSum::instance().get(&type_id, &week),
+                );
+                total_weight = total_weight
+                    .checked_add(
+                        type_sum
+                            .checked_mul(type_weights[index])
+                            .unwrap_or_revert_with(
+                                Error::GaugeControllerProjectionArithmeticError8,
+                            ),
+                    )
+                    .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError9);
+            }
+            let mut relative_weights: Vec<(U256, U256)> = Vec::new();
+            for (i, gauge) in gauges.iter().enumerate() {
+                let relative_weight: U256 = if total_weight > U256::from(0) {
+                    MULTIPLIER
+                        .checked_mul(type_weights[gauge_types[i]])
+                        .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError10)
+                        .checked_mul(gauge_weights[i])
+                        .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError11)
+                        .checked_div(total_weight)
+                        .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError12)
+                } else {
+                    U256::from(0)
+                };
+                let cap: U256 =
+                    PointsWeightCap::instance().get(gauge, &core::cmp::min(t, cap_cursors[i]));
+                relative_weights.push((relative_weight, cap));
+            }
+            let relative_weight: U256 = if CappedWeightsCheckpointed::instance().get(&t) {
+                PointsCappedWeight::instance().get(&addr, &t)
+            } else if self._weight_caps_enabled_at(t) {
+                self._apply_weight_caps(relative_weights)[index]
+            } else {
+                relative_weights[index].0
+            };
+            projection.push((t, gauge_weights[index], relative_weight));
+            t = t
+                .checked_add(WEEK)
+                .unwrap_or_revert_with(Error::GaugeControllerProjectionArithmeticError13);
+        }
+        projection
+    }
+
     fn change_type_weight(&mut self, type_id: i128, weight: U256) {
         if self.get_caller() == self.admin() {
             self._change_type_weight(type_id, weight);
@@ -514,6 +956,9 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
     }
 
     fn change_gauge_weight(&mut self, addr: Key, weight: U256) {
+        if self.gauge_is_killed(addr) {
+            runtime::revert(Error::GaugeControllerGaugeIsKilled2);
+        }
         if self.get_caller() == self.admin() {
             self._change_gauge_weight(addr, weight);
         } else {
@@ -521,6 +966,118 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
         }
     }
 
+    /// @notice Set the maximum relative weight of gauge `addr` from next week on
+    /// @param addr Gauge address
+    /// @param cap Maximum relative weight normalized to 1e9, 0 removes the cap
+    fn set_gauge_weight_cap(&mut self, addr: Key, cap: U256) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::GaugeControllerNotAdmin6);
+        }
+        if self.gauge_types_(addr) == 0 {
+            runtime::revert(Error::GaugeControllerGaugeNotAdded3);
+        }
+        if cap > MULTIPLIER {
+            runtime::revert(Error::GaugeControllerInvalidWeightCap);
+        }
+        self._get_weight(addr);
+        let next_time: U256 = (U256::from(u64::from(runtime::get_blocktime()))
+            .checked_add(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError9))
+        .checked_div(WEEK)
+        .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError10)
+        .checked_mul(WEEK)
+        .unwrap_or_revert_with(Error::GaugeControllerWeightCapArithmeticError11);
+        PointsWeightCap::instance().set(&addr, &next_time, cap);
+        if cap > U256::from(0) && !data::weight_caps_enabled() {
+            self._set_weight_caps_enabled(true);
+        }
+        self.emit(&GAUGECONLTROLLEREvent::NewGaugeWeightCap {
+            gauge_address: addr,
+            time: next_time,
+            cap,
+        });
+    }
+
+    /// @notice Turn per-gauge weight caps on or off from next week on
+    /// @dev Weeks that already started keep their setting and capped weights.
+    ///      Setting a non-zero cap turns caps back on
+    /// @param enabled Whether `gauge_relative_weight` applies the caps
+    fn set_weight_caps_enabled(&mut self, enabled: bool) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::GaugeControllerNotAdmin8);
+        }
+        self._set_weight_caps_enabled(enabled);
+        self.emit(&GAUGECONLTROLLEREvent::SetWeightCapsEnabled { enabled });
+    }
+
+    /// @notice Set the metadata record of gauge `addr`
+    /// @param addr Gauge address
+    /// @param metadata Name, kind, LP token and reward tokens of the gauge
+    fn set_gauge_metadata(&mut self, addr: Key, metadata: GaugeMetadata) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::GaugeControllerNotAdmin7);
+        }
+        if self.gauge_types_(addr) == 0 {
+            runtime::revert(Error::GaugeControllerGaugeNotAdded5);
+        }
+        self._set_gauge_metadata(addr, metadata);
+    }
+
+    fn _set_gauge_metadata(&mut self, addr: Key, metadata: GaugeMetadata) {
+        if metadata.kind > GAUGE_KIND_WRAPPER {
+            runtime::revert(Error::GaugeControllerInvalidGaugeKind);
+        }
+        if metadata.name.len() > MAX_GAUGE_NAME_LENGTH {
+            runtime::revert(Error::GaugeControllerGaugeNameTooLong);
+        }
+        if metadata.reward_tokens.len() > MAX_REWARD_TOKENS {
+            runtime::revert(Error::GaugeControllerTooManyRewardTokens);
+        }
+        GaugeMetadataRegistry::instance().set(&addr, metadata.clone());
+        self.emit(&GAUGECONLTROLLEREvent::SetGaugeMetadata {
+            addr,
+            name: metadata.name,
+            kind: metadata.kind,
+            lp_token: metadata.lp_token,
+        });
+    }
+
+    /// @notice Get a page of gauges in the order they were added
+    /// @param start Index of the first gauge
+    /// @param count Maximum number of gauges to return
+    /// @return Gauge address, type and metadata for every gauge in the page
+    fn gauges_page(&mut self, start: U256, count: U256) -> Vec<GaugeInfo> {
+        if count > MAX_GAUGES_PAGE {
+            runtime::revert(Error::GaugeControllerGaugesPageTooLarge);
+        }
+        let n_gauges: U256 = U256::from(data::n_gauges());
+        let mut end: U256 = start
+            .checked_add(count)
+            .unwrap_or_revert_with(Error::GaugeControllerGaugesPageArithmeticError1);
+        if end > n_gauges {
+            end = n_gauges;
+        }
+        let mut page: Vec<GaugeInfo> = Vec::new();
+        let mut i: U256 = start;
+        while i < end {
+            let addr: Key = self.gauges(i);
+            // Listed gauges are always added, read the type without the reverting getter
+            let gauge_type: i128 = self
+                .gauge_types_(addr)
+                .checked_sub(1)
+                .unwrap_or_revert_with(Error::GaugeControllerGaugesPageArithmeticError3);
+            page.push(GaugeInfo {
+                addr,
+                gauge_type: i128_to_tuple(gauge_type),
+                metadata: self.gauge_metadata(addr),
+            });
+            i = i
+                .checked_add(U256::from(1))
+                .unwrap_or_revert_with(Error::GaugeControllerGaugesPageArithmeticError2);
+        }
+        page
+    }
+
     fn get_gauge_weight(&mut self, addr: Key) -> U256 {
         let time_weight = self.time_weight(addr);
         self.points_weight(addr, time_weight).bias
@@ -574,6 +1131,9 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
     fn points_weight(&mut self, owner: Key, spender: U256) -> Point {
         PointsWeight::instance().get(&owner, &spender)
     }
+    fn points_weight_cap(&mut self, owner: Key, spender: U256) -> U256 {
+        PointsWeightCap::instance().get(&owner, &spender)
+    }
     fn time_sum(&mut self, type_id: U256) -> U256 {
         TimeSum::instance().get(&type_id)
     }
@@ -589,6 +1149,15 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
     fn vote_user_slopes(&mut self, owner: Key, spender: Key) -> VotedSlope {
         VoteUserSlopes::instance().get(&owner, &spender)
     }
+    fn vote_delegates(&mut self, owner: Key) -> Key {
+        VoteDelegates::instance().get(&owner)
+    }
+    fn gauge_is_killed(&mut self, addr: Key) -> bool {
+        GaugeIsKilled::instance().get(&addr)
+    }
+    fn gauge_metadata(&mut self, addr: Key) -> GaugeMetadata {
+        GaugeMetadataRegistry::instance().get(&addr)
+    }
     /// TimeWeight, VoteUserPower, VoteUserSlopes,
     fn add_type(&mut self, _name: String, _weight: Option<U256>) {
         let weight: U256 = if let Some(..) = _weight {
@@ -634,6 +1203,12 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                             .unwrap_or_revert_with(Error::GaugeControllerOverFlow12),
                     );
                     Gauges::instance().set(&U256::from(n), addr);
+                    GaugeIndex::instance().set(
+                        &addr,
+                        U256::from(n).checked_add(1.into()).unwrap_or_revert_with(
+                            Error::GaugeControllerWeightCapArithmeticError14,
+                        ),
+                    );
                     GaugeTypes_::instance().set(
                         &addr,
                         gauge_type
@@ -680,6 +1255,8 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                     }
 
                     TimeWeight::instance().set(&addr, next_time);
+                    // Every gauge has a metadata record, `add_gauge_with_metadata` fills it in
+                    GaugeMetadataRegistry::instance().set(&addr, GaugeMetadata::default());
                     self.emit(&GAUGECONLTROLLEREvent::NewGauge {
                         addr,
                         gauge_type,
@@ -696,7 +1273,208 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
         }
     }
 
+    /// @notice Add gauge `addr` of type `gauge_type` together with its metadata record
+    /// @param addr Gauge address
+    /// @param gauge_type Gauge type
+    /// @param _weight Gauge weight
+    /// @param metadata Name, kind, LP token and reward tokens of the gauge
+    fn add_gauge_with_metadata(
+        &mut self,
+        addr: Key,
+        gauge_type: i128,
+        _weight: Option<U256>,
+        metadata: GaugeMetadata,
+    ) {
+        self.add_gauge(addr, gauge_type, _weight);
+        self._set_gauge_metadata(addr, metadata);
+    }
+
+    /// @notice Kill gauge `addr`. Its weight is removed from the type sum and
+    ///         the total from the next week on and no new votes are accepted
+    /// @param addr Gauge address
+    fn kill_gauge(&mut self, addr: Key) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::GaugeControllerNotAdmin5);
+        }
+        if self.gauge_types_(addr) == 0 {
+            runtime::revert(Error::GaugeControllerGaugeNotAdded2);
+        }
+        if self.gauge_is_killed(addr) {
+            runtime::revert(Error::GaugeControllerGaugeAlreadyKilled);
+        }
+        let gauge_type: i128 = self
+            .gauge_types_(addr)
+            .checked_sub(1)
+            .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError1);
+        let old_gauge_weight: U256 = self._get_weight(addr);
+        let type_weight: U256 = self._get_type_weight(gauge_type);
+        let old_sum: U256 = self._get_sum(gauge_type);
+        let old_total_weight: U256 = self._get_total();
+        let next_time: U256 = (U256::from(u64::from(runtime::get_blocktime()))
+            .checked_add(WEEK)
+            .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError2))
+        .checked_div(WEEK)
+        .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError3)
+        .checked_mul(WEEK)
+        .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError4);
+
+        // Remove the gauge point from its type sum
+        let old_gauge_slope: U256 = self.points_weight(addr, next_time).slope;
+        PointsWeight::instance().set(&addr, &next_time, Point::default());
+        TimeWeight::instance().set(&addr, next_time);
+        let mut point_sum: Point = self.points_sum(gauge_type, next_time);
+        point_sum.bias = old_sum
+            .checked_sub(old_gauge_weight)
+            .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError5);
+        point_sum.slope = point_sum
+            .slope
+            .checked_sub(old_gauge_slope)
+            .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError6);
+        PointsSum::instance().set(&gauge_type, &next_time, point_sum);
+        TimeSum::instance().set(&U256::from(gauge_type), next_time);
+
+        // The gauge slope left the type sum above, so the slope changes its votes still
+        // schedule must leave it too, or `_get_sum` would subtract them a second time
+        let mut t: U256 = next_time;
+        for _ in 0..MAX_VOTE_WEEKS {
+            t = t
+                .checked_add(WEEK)
+                .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError9);
+            let d_slope: U256 = self.changes_weight(addr, t);
+            if d_slope != U256::from(0) {
+                let changes_sum_result: U256 = self
+                    .changes_sum(gauge_type, t)
+                    .checked_sub(d_slope)
+                    .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError10);
+                ChangesSum::instance().set(&gauge_type, &t, changes_sum_result);
+                ChangesWeight::instance().set(&addr, &t, U256::from(0));
+            }
+        }
+
+        let total_weight: U256 = old_total_weight
+            .checked_sub(
+                old_gauge_weight
+                    .checked_mul(type_weight)
+                    .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError7),
+            )
+            .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError8);
+        PointsTotal::instance().set(&next_time, total_weight);
+        data::set_time_total(next_time);
+
+        GaugeIsKilled::instance().set(&addr, true);
+        self.emit(&GAUGECONLTROLLEREvent::KillGauge {
+            addr,
+            time: next_time,
+            total_weight,
+        });
+    }
+
+    /// @notice Return the voting power the caller allocated to killed gauge
+    ///         `_gauge_addr` back to `vote_user_power`
+    /// @param _gauge_addr Killed gauge address
+    fn reclaim_gauge_power(&mut self, _gauge_addr: Key) {
+        if !self.gauge_is_killed(_gauge_addr) {
+            runtime::revert(Error::GaugeControllerGaugeNotKilled);
+        }
+        let user: Key = self.get_caller();
+        let old_slope: VotedSlope = self.vote_user_slopes(user, _gauge_addr);
+        if old_slope.power == 0.into() {
+            runtime::revert(Error::GaugeControllerNothingToReclaim);
+        }
+        // The slope changes of the vote were already dropped by `kill_gauge`
+        let power_used: U256 = self
+            .vote_user_power(user)
+            .checked_sub(old_slope.power)
+            .unwrap_or_revert_with(Error::GaugeControllerKillArithmeticError12);
+        VoteUserPower::instance().set(&user, power_used);
+        VoteUserSlopes::instance().set(&user, &_gauge_addr, VotedSlope::default());
+
+        self.emit(&GAUGECONLTROLLEREvent::VoteForGauge {
+            time: U256::from(u64::from(runtime::get_blocktime())),
+            user,
+            gauge_addr: _gauge_addr,
+            weight: 0.into(),
+        });
+    }
+
+    /// @notice Allocate voting power for changing pool weights
+    /// @param _gauge_addr Gauge which `msg.sender` votes for
+    /// @param _user_weight Weight for a gauge in bps (units of 0.01%). Minimal is 0.01%. Ignored if 0
     fn vote_for_gauge_weights(&mut self, _gauge_addr: Key, _user_weight: U256) {
+        let user: Key = self.get_caller();
+        let (slope, lock_end): (U256, U256) = self._user_slope_and_lock_end(user);
+        self._vote_for_gauge_weights(user, slope, lock_end, _gauge_addr, _user_weight);
+        if self.vote_user_power(user) > 10000.into() {
+            runtime::revert(Error::GaugeControllerUsedTooMuchPower);
+        }
+    }
+
+    /// @notice Allocate voting power for several gauges at once. The total
+    ///         power used is only checked after all votes are applied
+    /// @param votes List of (gauge address, weight in bps) pairs
+    fn vote_for_many_gauge_weights(&mut self, votes: Vec<(Key, U256)>) {
+        if votes.len() > MAX_BATCH_VOTES {
+            runtime::revert(Error::GaugeControllerTooManyVotes);
+        }
+        let user: Key = self.get_caller();
+        let (slope, lock_end): (U256, U256) = self._user_slope_and_lock_end(user);
+        for (gauge_addr, user_weight) in votes {
+            self._vote_for_gauge_weights(user, slope, lock_end, gauge_addr, user_weight);
+        }
+        if self.vote_user_power(user) > 10000.into() {
+            runtime::revert(Error::GaugeControllerUsedTooMuchPower2);
+        }
+    }
+
+    /// @notice Allow `delegate` to vote for gauge weights on behalf of the caller
+    /// @param delegate Address voting with the caller's power
+    fn set_vote_delegate(&mut self, delegate: Key) {
+        let delegator: Key = self.get_caller();
+        if delegate == delegator || delegate == zero_address() || delegate == account_zero_address()
+        {
+            runtime::revert(Error::GaugeControllerInvalidVoteDelegate);
+        }
+        VoteDelegates::instance().set(&delegator, delegate);
+        self.emit(&GAUGECONLTROLLEREvent::SetVoteDelegate {
+            delegator,
+            delegate,
+        });
+    }
+
+    /// @notice Revoke the vote delegate of the caller
+    fn revoke_vote_delegate(&mut self) {
+        let delegator: Key = self.get_caller();
+        let delegate: Key = self.vote_delegates(delegator);
+        if delegate == zero_address() {
+            runtime::revert(Error::GaugeControllerNoVoteDelegate);
+        }
+        VoteDelegates::instance().set(&delegator, zero_address());
+        self.emit(&GAUGECONLTROLLEREvent::RevokeVoteDelegate {
+            delegator,
+            delegate,
+        });
+    }
+
+    /// @notice Allocate voting power of `user` for changing pool weights.
+    ///         Only callable by the vote delegate of `user`
+    /// @param user Delegator whose voting power is used
+    /// @param _gauge_addr Gauge which `user` votes for
+    /// @param _user_weight Weight for a gauge in bps (units of 0.01%)
+    fn vote_for_gauge_weights_for(&mut self, user: Key, _gauge_addr: Key, _user_weight: U256) {
+        if self.vote_delegates(user) != self.get_caller() {
+            runtime::revert(Error::GaugeControllerNotVoteDelegate);
+        }
+        let (slope, lock_end): (U256, U256) = self._user_slope_and_lock_end(user);
+        self._vote_for_gauge_weights(user, slope, lock_end, _gauge_addr, _user_weight);
+        if self.vote_user_power(user) > 10000.into() {
+            runtime::revert(Error::GaugeControllerUsedTooMuchPower3);
+        }
+    }
+
+    /// @notice Read the last slope and lock end of `user` from `VotingEscrow`
+    /// @param user User address
+    /// @return (slope, lock end)
+    fn _user_slope_and_lock_end(&mut self, user: Key) -> (U256, U256) {
         let escrow: Key = data::voting_escrow();
 
         //convert Key to ContractPackageHash
@@ -710,7 +1488,7 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
             escrow_package_hash,
             None,
             "get_last_user_slope",
-            runtime_args! {"addr" => self.get_caller()},
+            runtime_args! {"addr" => user},
         );
         let slope = U256::from(tuple_to_i128(_slope));
 
@@ -718,9 +1496,24 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
             escrow_package_hash,
             None,
             "locked_end",
-            runtime_args! {"addr" => self.get_caller()},
+            runtime_args! {"addr" => user},
         );
+        (slope, lock_end)
+    }
 
+    /// @notice Apply the vote of `user` for `_gauge_addr` and update the power
+    ///         used by `user`. The caller checks that power stays within 100%
+    fn _vote_for_gauge_weights(
+        &mut self,
+        user: Key,
+        slope: U256,
+        lock_end: U256,
+        _gauge_addr: Key,
+        _user_weight: U256,
+    ) {
+        if self.gauge_is_killed(_gauge_addr) {
+            runtime::revert(Error::GaugeControllerGaugeIsKilled1);
+        }
         let _n_gauges: i128 = data::n_gauges();
         let next_time: U256 = (U256::from(u64::from(runtime::get_blocktime()))
             .checked_add(WEEK)
@@ -734,7 +1527,7 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
             if _user_weight >= U256::from(0) && _user_weight <= U256::from(10000) {
                 if (U256::from(u64::from(runtime::get_blocktime())))
                     >= (self
-                        .last_user_vote(self.get_caller(), _gauge_addr)
+                        .last_user_vote(user, _gauge_addr)
                         .checked_add(WEIGHT_VOTE_DELAY)
                         .unwrap_or_revert_with(Error::GaugeControllerOverFlow18))
                 {
@@ -744,8 +1537,7 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                         .unwrap_or_revert_with(Error::GaugeControllerUnderFlow10);
                     if gauge_type >= 0 {
                         // Prepare slopes and biases in memory
-                        let old_slope: VotedSlope =
-                            self.vote_user_slopes(self.get_caller(), _gauge_addr);
+                        let old_slope: VotedSlope = self.vote_user_slopes(user, _gauge_addr);
                         let mut old_dt: U256 = 0.into();
                         if old_slope.end > next_time {
                             old_dt = old_slope
@@ -759,12 +1551,10 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                             .unwrap_or_revert_with(Error::GaugeControllerMultiply17);
                         let new_slope: VotedSlope = VotedSlope {
                             slope: slope
-                                .checked_mul(
-                                    _user_weight
-                                        .checked_div(U256::from(100000))
-                                        .unwrap_or_revert_with(Error::GaugeControllerDivide8),
-                                )
-                                .unwrap_or_revert_with(Error::GaugeControllerMultiply18),
+                                .checked_mul(_user_weight)
+                                .unwrap_or_revert_with(Error::GaugeControllerMultiply18)
+                                .checked_div(U256::from(10000))
+                                .unwrap_or_revert_with(Error::GaugeControllerDivide8),
                             end: lock_end,
                             power: _user_weight,
                         };
@@ -777,41 +1567,83 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                             .unwrap_or_revert_with(Error::GaugeControllerMultiply19);
 
                         // Check and update powers (weights) used
-                        let mut power_used: U256 = self.vote_user_power(self.get_caller());
+                        let mut power_used: U256 = self.vote_user_power(user);
                         power_used = power_used
-                            .checked_add(
-                                new_slope
-                                    .power
-                                    .checked_sub(old_slope.power)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow13),
-                            )
-                            .unwrap_or_revert_with(Error::GaugeControllerOverFlow19);
-                        VoteUserPower::instance().set(&self.get_caller(), power_used);
-
-                        if (power_used >= 0.into()) && (power_used <= 10000.into()) {
-                            // Remove old and schedule new slope changes
-                            // Remove slope changes for old slopes
-                            // Schedule recording of initial slope for next_time
-
-                            let old_weight_bias: U256 = self._get_weight(_gauge_addr);
-                            let old_weight_slope: U256 =
-                                self.points_weight(_gauge_addr, next_time).slope;
-                            let old_sum_bias: U256 = self._get_sum(gauge_type);
-                            let old_sum_slope: U256 = self.points_sum(gauge_type, next_time).slope;
-
-                            let max_weight_bias = old_weight_bias
-                                .checked_add(new_bias)
-                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow20);
-                            let max_sum_bias = old_sum_bias
-                                .checked_add(new_bias)
-                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow21);
-
-                            if max_weight_bias > old_bias {
+                            .checked_add(new_slope.power)
+                            .unwrap_or_revert_with(Error::GaugeControllerOverFlow19)
+                            .checked_sub(old_slope.power)
+                            .unwrap_or_revert_with(Error::GaugeControllerUnderFlow13);
+                        VoteUserPower::instance().set(&user, power_used);
+
+                        // Remove old and schedule new slope changes
+                        // Remove slope changes for old slopes
+                        // Schedule recording of initial slope for next_time
+
+                        let old_weight_bias: U256 = self._get_weight(_gauge_addr);
+                        let old_weight_slope: U256 =
+                            self.points_weight(_gauge_addr, next_time).slope;
+                        let old_sum_bias: U256 = self._get_sum(gauge_type);
+                        let old_sum_slope: U256 = self.points_sum(gauge_type, next_time).slope;
+
+                        let max_weight_bias = old_weight_bias
+                            .checked_add(new_bias)
+                            .unwrap_or_revert_with(Error::GaugeControllerOverFlow20);
+                        let max_sum_bias = old_sum_bias
+                            .checked_add(new_bias)
+                            .unwrap_or_revert_with(Error::GaugeControllerOverFlow21);
+
+                        if max_weight_bias > old_bias {
+                            let mut points_weight_result =
+                                self.points_weight(_gauge_addr, next_time);
+                            (points_weight_result).bias = max_weight_bias
+                                .checked_sub(old_bias)
+                                .unwrap_or_revert_with(Error::GaugeControllerUnderFlow14);
+                            PointsWeight::instance().set(
+                                &_gauge_addr,
+                                &next_time,
+                                points_weight_result,
+                            );
+                        } else {
+                            let mut points_weight_result =
+                                self.points_weight(_gauge_addr, next_time);
+                            (points_weight_result).bias = old_bias
+                                .checked_sub(old_bias)
+                                .unwrap_or_revert_with(Error::GaugeControllerUnderFlow15);
+                            PointsWeight::instance().set(
+                                &_gauge_addr,
+                                &next_time,
+                                points_weight_result,
+                            );
+                        }
+
+                        if max_sum_bias > old_bias {
+                            let mut points_sum_result = self.points_sum(gauge_type, next_time);
+                            (points_sum_result).bias = max_sum_bias
+                                .checked_sub(old_bias)
+                                .unwrap_or_revert_with(Error::GaugeControllerUnderFlow16);
+                            PointsSum::instance().set(&gauge_type, &next_time, points_sum_result);
+                        } else {
+                            let mut points_sum_result = self.points_sum(gauge_type, next_time);
+                            (points_sum_result).bias = old_bias
+                                .checked_sub(old_bias)
+                                .unwrap_or_revert_with(Error::GaugeControllerUnderFlow17);
+                            PointsSum::instance().set(&gauge_type, &next_time, points_sum_result);
+                        }
+
+                        if old_slope.end > next_time {
+                            let max_weight_slope = old_weight_slope
+                                .checked_add(new_slope.slope)
+                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow22);
+                            let max_sum_slope = old_sum_slope
+                                .checked_add(new_slope.slope)
+                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow23);
+
+                            if max_weight_slope > old_slope.slope {
                                 let mut points_weight_result =
                                     self.points_weight(_gauge_addr, next_time);
-                                (points_weight_result).bias = max_weight_bias
-                                    .checked_sub(old_bias)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow14);
+                                (points_weight_result).slope = max_weight_slope
+                                    .checked_sub(old_slope.slope)
+                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow18);
                                 PointsWeight::instance().set(
                                     &_gauge_addr,
                                     &next_time,
@@ -820,9 +1652,10 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                             } else {
                                 let mut points_weight_result =
                                     self.points_weight(_gauge_addr, next_time);
-                                (points_weight_result).bias = old_bias
-                                    .checked_sub(old_bias)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow15);
+                                (points_weight_result).slope = old_slope
+                                    .slope
+                                    .checked_sub(old_slope.slope)
+                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow19);
                                 PointsWeight::instance().set(
                                     &_gauge_addr,
                                     &next_time,
@@ -830,11 +1663,11 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                                 );
                             }
 
-                            if max_sum_bias > old_bias {
+                            if max_sum_slope > old_slope.slope {
                                 let mut points_sum_result = self.points_sum(gauge_type, next_time);
-                                (points_sum_result).bias = max_sum_bias
-                                    .checked_sub(old_bias)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow16);
+                                (points_sum_result).slope = max_sum_slope
+                                    .checked_sub(old_slope.slope)
+                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow20);
                                 PointsSum::instance().set(
                                     &gauge_type,
                                     &next_time,
@@ -842,131 +1675,45 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                                 );
                             } else {
                                 let mut points_sum_result = self.points_sum(gauge_type, next_time);
-                                (points_sum_result).bias = old_bias
-                                    .checked_sub(old_bias)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow17);
-                                PointsSum::instance().set(
-                                    &gauge_type,
-                                    &next_time,
-                                    points_sum_result,
-                                );
-                            }
-
-                            if old_slope.end > next_time {
-                                let max_weight_slope = old_weight_slope
-                                    .checked_add(new_slope.slope)
-                                    .unwrap_or_revert_with(Error::GaugeControllerOverFlow22);
-                                let max_sum_slope = old_sum_slope
-                                    .checked_add(new_slope.slope)
-                                    .unwrap_or_revert_with(Error::GaugeControllerOverFlow23);
-
-                                if max_weight_slope > old_slope.slope {
-                                    let mut points_weight_result =
-                                        self.points_weight(_gauge_addr, next_time);
-                                    (points_weight_result).slope = max_weight_slope
-                                        .checked_sub(old_slope.slope)
-                                        .unwrap_or_revert_with(Error::GaugeControllerUnderFlow18);
-                                    PointsWeight::instance().set(
-                                        &_gauge_addr,
-                                        &next_time,
-                                        points_weight_result,
-                                    );
-                                } else {
-                                    let mut points_weight_result =
-                                        self.points_weight(_gauge_addr, next_time);
-                                    (points_weight_result).slope = old_slope
-                                        .slope
-                                        .checked_sub(old_slope.slope)
-                                        .unwrap_or_revert_with(Error::GaugeControllerUnderFlow19);
-                                    PointsWeight::instance().set(
-                                        &_gauge_addr,
-                                        &next_time,
-                                        points_weight_result,
-                                    );
-                                }
-
-                                if max_sum_slope > old_slope.slope {
-                                    let mut points_sum_result =
-                                        self.points_sum(gauge_type, next_time);
-                                    (points_sum_result).slope = max_sum_slope
-                                        .checked_sub(old_slope.slope)
-                                        .unwrap_or_revert_with(Error::GaugeControllerUnderFlow20);
-                                    PointsSum::instance().set(
-                                        &gauge_type,
-                                        &next_time,
-                                        points_sum_result,
-                                    );
-                                } else {
-                                    let mut points_sum_result =
-                                        self.points_sum(gauge_type, next_time);
-                                    (points_sum_result).slope = old_slope
-                                        .slope
-                                        .checked_sub(old_slope.slope)
-                                        .unwrap_or_revert_with(Error::GaugeControllerUnderFlow21);
-                                    PointsSum::instance().set(
-                                        &gauge_type,
-                                        &next_time,
-                                        points_sum_result,
-                                    );
-                                }
-                            } else {
-                                let mut points_weight_result =
-                                    self.points_weight(_gauge_addr, next_time);
-                                (points_weight_result).slope = (points_weight_result)
-                                    .slope
-                                    .checked_add(new_slope.slope)
-                                    .unwrap_or_revert_with(Error::GaugeControllerOverFlow24);
-                                PointsWeight::instance().set(
-                                    &_gauge_addr,
-                                    &next_time,
-                                    points_weight_result,
-                                );
-
-                                let mut points_sum_result = self.points_sum(gauge_type, next_time);
-                                (points_sum_result).slope = (points_sum_result)
+                                (points_sum_result).slope = old_slope
                                     .slope
-                                    .checked_add(new_slope.slope)
-                                    .unwrap_or_revert_with(Error::GaugeControllerOverFlow25);
+                                    .checked_sub(old_slope.slope)
+                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow21);
                                 PointsSum::instance().set(
                                     &gauge_type,
                                     &next_time,
                                     points_sum_result,
                                 );
                             }
+                        } else {
+                            let mut points_weight_result =
+                                self.points_weight(_gauge_addr, next_time);
+                            (points_weight_result).slope = (points_weight_result)
+                                .slope
+                                .checked_add(new_slope.slope)
+                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow24);
+                            PointsWeight::instance().set(
+                                &_gauge_addr,
+                                &next_time,
+                                points_weight_result,
+                            );
 
-                            if old_slope.end > U256::from(u64::from(runtime::get_blocktime())) {
-                                // Cancel old slope changes if they still didn't happen
-
-                                let mut changes_weight_result =
-                                    self.changes_weight(_gauge_addr, old_slope.end);
-                                changes_weight_result = changes_weight_result
-                                    .checked_sub(old_slope.slope)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow22);
-                                ChangesWeight::instance().set(
-                                    &_gauge_addr,
-                                    &old_slope.end,
-                                    changes_weight_result,
-                                );
-
-                                let mut changes_sum_result =
-                                    self.changes_sum(gauge_type, old_slope.end);
-                                changes_sum_result = changes_sum_result
-                                    .checked_sub(old_slope.slope)
-                                    .unwrap_or_revert_with(Error::GaugeControllerUnderFlow23);
-                                ChangesSum::instance().set(
-                                    &gauge_type,
-                                    &old_slope.end,
-                                    changes_sum_result,
-                                );
-                            }
+                            let mut points_sum_result = self.points_sum(gauge_type, next_time);
+                            (points_sum_result).slope = (points_sum_result)
+                                .slope
+                                .checked_add(new_slope.slope)
+                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow25);
+                            PointsSum::instance().set(&gauge_type, &next_time, points_sum_result);
+                        }
 
-                            // Add slope changes for new slopes
+                        if old_slope.end > U256::from(u64::from(runtime::get_blocktime())) {
+                            // Cancel old slope changes if they still didn't happen
 
                             let mut changes_weight_result =
-                                self.changes_weight(_gauge_addr, new_slope.end);
+                                self.changes_weight(_gauge_addr, old_slope.end);
                             changes_weight_result = changes_weight_result
-                                .checked_add(new_slope.slope)
-                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow26);
+                                .checked_sub(old_slope.slope)
+                                .unwrap_or_revert_with(Error::GaugeControllerUnderFlow22);
                             ChangesWeight::instance().set(
                                 &_gauge_addr,
                                 &old_slope.end,
@@ -974,40 +1721,53 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                             );
 
                             let mut changes_sum_result =
-                                self.changes_sum(gauge_type, new_slope.end);
+                                self.changes_sum(gauge_type, old_slope.end);
                             changes_sum_result = changes_sum_result
-                                .checked_add(new_slope.slope)
-                                .unwrap_or_revert_with(Error::GaugeControllerOverFlow27);
+                                .checked_sub(old_slope.slope)
+                                .unwrap_or_revert_with(Error::GaugeControllerUnderFlow23);
                             ChangesSum::instance().set(
                                 &gauge_type,
                                 &old_slope.end,
                                 changes_sum_result,
                             );
+                        }
 
-                            self._get_total();
+                        // Add slope changes for new slopes
+
+                        let mut changes_weight_result =
+                            self.changes_weight(_gauge_addr, new_slope.end);
+                        changes_weight_result = changes_weight_result
+                            .checked_add(new_slope.slope)
+                            .unwrap_or_revert_with(Error::GaugeControllerOverFlow26);
+                        ChangesWeight::instance().set(
+                            &_gauge_addr,
+                            &new_slope.end,
+                            changes_weight_result,
+                        );
 
-                            VoteUserSlopes::instance().set(
-                                &self.get_caller(),
-                                &_gauge_addr,
-                                new_slope,
-                            );
+                        let mut changes_sum_result = self.changes_sum(gauge_type, new_slope.end);
+                        changes_sum_result = changes_sum_result
+                            .checked_add(new_slope.slope)
+                            .unwrap_or_revert_with(Error::GaugeControllerOverFlow27);
+                        ChangesSum::instance().set(&gauge_type, &new_slope.end, changes_sum_result);
 
-                            //Record last action time
-                            LastUserVote::instance().set(
-                                &self.get_caller(),
-                                &_gauge_addr,
-                                U256::from(u64::from(runtime::get_blocktime())),
-                            );
+                        self._get_total();
 
-                            self.emit(&GAUGECONLTROLLEREvent::VoteForGauge {
-                                time: U256::from(u64::from(runtime::get_blocktime())),
-                                user: self.get_caller(),
-                                gauge_addr: _gauge_addr,
-                                weight: _user_weight,
-                            });
-                        } else {
-                            runtime::revert(Error::GaugeControllerUsedTooMuchPower);
-                        }
+                        VoteUserSlopes::instance().set(&user, &_gauge_addr, new_slope);
+
+                        //Record last action time
+                        LastUserVote::instance().set(
+                            &user,
+                            &_gauge_addr,
+                            U256::from(u64::from(runtime::get_blocktime())),
+                        );
+
+                        self.emit(&GAUGECONLTROLLEREvent::VoteForGauge {
+                            time: U256::from(u64::from(runtime::get_blocktime())),
+                            user,
+                            gauge_addr: _gauge_addr,
+                            weight: _user_weight,
+                        });
                     } else {
                         runtime::revert(Error::GaugeControllerGaugeNotAdded);
                     }
@@ -1124,6 +1884,76 @@ pub trait GAUGECONLTROLLER<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("weight", weight.to_string());
                 storage::new_uref(event);
             }
+            GAUGECONLTROLLEREvent::KillGauge {
+                addr,
+                time,
+                total_weight,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_controller_event.type_name());
+                event.insert("addr", addr.to_string());
+                event.insert("time", time.to_string());
+                event.insert("total_weight", total_weight.to_string());
+                storage::new_uref(event);
+            }
+            GAUGECONLTROLLEREvent::NewGaugeWeightCap {
+                gauge_address,
+                time,
+                cap,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_controller_event.type_name());
+                event.insert("gauge_address", gauge_address.to_string());
+                event.insert("time", time.to_string());
+                event.insert("cap", cap.to_string());
+                storage::new_uref(event);
+            }
+            GAUGECONLTROLLEREvent::SetWeightCapsEnabled { enabled } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_controller_event.type_name());
+                event.insert("enabled", enabled.to_string());
+                storage::new_uref(event);
+            }
+            GAUGECONLTROLLEREvent::SetVoteDelegate {
+                delegator,
+                delegate,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_controller_event.type_name());
+                event.insert("delegator", delegator.to_string());
+                event.insert("delegate", delegate.to_string());
+                storage::new_uref(event);
+            }
+            GAUGECONLTROLLEREvent::RevokeVoteDelegate {
+                delegator,
+                delegate,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_controller_event.type_name());
+                event.insert("delegator", delegator.to_string());
+                event.insert("delegate", delegate.to_string());
+                storage::new_uref(event);
+            }
+            GAUGECONLTROLLEREvent::SetGaugeMetadata {
+                addr,
+                name,
+                kind,
+                lp_token,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", gauge_controller_event.type_name());
+                event.insert("addr", addr.to_string());
+                event.insert("name", name.to_string());
+                event.insert("kind", kind.to_string());
+                event.insert("lp_token", lp_token.to_string());
+                storage::new_uref(event);
+            }
             GAUGECONLTROLLEREvent::VoteForGauge {
                 time,
                 user,
@@ -8,9 +8,25 @@ use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
     runtime_args, CLTyped, ContractPackageHash, Key, RuntimeArgs, U128, U256,
 };
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
 use casperlabs_test_env::{TestContract, TestEnv};
 use std::time::SystemTime;
 
+#[derive(Clone, Debug, PartialEq, CLTyped, ToBytes, FromBytes)]
+pub struct GaugeMetadata {
+    pub name: String,
+    pub kind: u8,
+    pub lp_token: Key,
+    pub reward_tokens: Vec<Key>,
+}
+
+#[derive(Clone, Debug, PartialEq, CLTyped, ToBytes, FromBytes)]
+pub struct GaugeInfo {
+    pub addr: Key,
+    pub gauge_type: (bool, U128),
+    pub metadata: GaugeMetadata,
+}
+
 pub struct GAUGECONLTROLLERInstance(TestContract);
 #[allow(clippy::too_many_arguments)]
 impl GAUGECONLTROLLERInstance {
@@ -290,6 +306,158 @@ impl GAUGECONLTROLLERInstance {
         );
     }
 
+    pub fn vote_for_many_gauge_weights(
+        &self,
+        sender: AccountHash,
+        votes: Vec<(Key, U256)>,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "vote_for_many_gauge_weights",
+            runtime_args! {
+                "votes" => votes,
+            },
+            block_time,
+        );
+    }
+    pub fn set_vote_delegate<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        delegate: T,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_vote_delegate",
+            runtime_args! {
+                "delegate" => delegate.into(),
+            },
+            block_time,
+        );
+    }
+    pub fn revoke_vote_delegate(&self, sender: AccountHash, block_time: u64) {
+        self.0
+            .call_contract(sender, "revoke_vote_delegate", runtime_args! {}, block_time);
+    }
+    pub fn vote_for_gauge_weights_for<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        user: T,
+        _gauge_addr: Key,
+        _user_weight: U256,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "vote_for_gauge_weights_for",
+            runtime_args! {
+                "user" => user.into(),
+                "gauge_addr" => _gauge_addr,
+                "user_weight" => _user_weight,
+            },
+            block_time,
+        );
+    }
+    pub fn set_gauge_weight_cap<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        addr: T,
+        cap: U256,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_gauge_weight_cap",
+            runtime_args! {
+                "addr" => addr.into(),
+                "cap" => cap,
+            },
+            block_time,
+        );
+    }
+    pub fn set_weight_caps_enabled(&self, sender: AccountHash, enabled: bool, block_time: u64) {
+        self.0.call_contract(
+            sender,
+            "set_weight_caps_enabled",
+            runtime_args! {
+                "enabled" => enabled,
+            },
+            block_time,
+        );
+    }
+    pub fn points_capped_weight(&self, owner: Key, spender: U256) -> U256 {
+        self.0
+            .query_dictionary(
+                "points_capped_weight",
+                key_and_value_to_str(&owner, &spender),
+            )
+            .unwrap_or_default()
+    }
+    pub fn add_gauge_with_metadata<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        addr: T,
+        gauge_type: (bool, U128),
+        weight: Option<U256>,
+        metadata: GaugeMetadata,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "add_gauge_with_metadata",
+            runtime_args! {
+                "addr" => addr.into(),
+                "gauge_type" => gauge_type,
+                "weight" => weight,
+                "metadata" => metadata
+            },
+            block_time,
+        );
+    }
+    pub fn set_gauge_metadata<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        addr: T,
+        metadata: GaugeMetadata,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_gauge_metadata",
+            runtime_args! {
+                "addr" => addr.into(),
+                "metadata" => metadata
+            },
+            block_time,
+        );
+    }
+    pub fn kill_gauge<T: Into<Key>>(&self, sender: AccountHash, addr: T, block_time: u64) {
+        self.0.call_contract(
+            sender,
+            "kill_gauge",
+            runtime_args! {
+                "addr" => addr.into(),
+            },
+            block_time,
+        );
+    }
+    pub fn reclaim_gauge_power<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        _gauge_addr: T,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "reclaim_gauge_power",
+            runtime_args! {
+                "gauge_addr" => _gauge_addr.into(),
+            },
+            block_time,
+        );
+    }
+
     pub fn gauge_type_names<T: Into<Key>>(&self, owner: U128) -> String {
         self.0
             .query_dictionary("gauge_type_names", owner.to_string())
@@ -332,6 +500,21 @@ impl GAUGECONLTROLLERInstance {
             .unwrap_or_default()
     }
 
+    pub fn vote_delegates<T: Into<Key>>(&self, owner: T) -> Option<Key> {
+        self.0
+            .query_dictionary("vote_delegates", key_to_str(&owner.into()))
+    }
+    pub fn gauge_is_killed<T: Into<Key>>(&self, addr: T) -> bool {
+        self.0
+            .query_dictionary("gauge_is_killed", key_to_str(&addr.into()))
+            .unwrap_or_default()
+    }
+
+    pub fn gauge_metadata<T: Into<Key>>(&self, addr: T) -> Option<GaugeMetadata> {
+        self.0
+            .query_dictionary("gauge_metadata", key_to_str(&addr.into()))
+    }
+
     pub fn change_sum(&self, owner: U128, spender: U256) -> U256 {
         self.0
             .query_dictionary(
@@ -371,6 +554,11 @@ impl GAUGECONLTROLLERInstance {
             .query_dictionary("points_weight", key_and_value_to_str(&owner, &spender))
             .unwrap_or_default()
     }
+    pub fn points_weight_cap(&self, owner: Key, spender: U256) -> U256 {
+        self.0
+            .query_dictionary("points_weight_cap", key_and_value_to_str(&owner, &spender))
+            .unwrap_or_default()
+    }
     pub fn vote_user_slopes(&self, owner: Key, spender: Key) -> U256 {
         self.0
             .query_dictionary("vote_user_slopes", keys_to_str(&owner, &spender))
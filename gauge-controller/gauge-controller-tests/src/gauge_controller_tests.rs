@@ -9,6 +9,7 @@ const TOKEN_SYMBOL: &str = "ERC";
 const DECIMALS: u8 = 8;
 const INIT_TOTAL_SUPPLY: u64 = 0;
 pub const VOTING_ESCROW_WEEK: U256 = U256([604800000, 0, 0, 0]); // all future times are rounded by week
+const VOTE_LOCK_VALUE: u128 = 1000000000000000000; // lock slopes are value / MAXTIME, keep them non-zero
 
 fn deploy() -> (
     TestEnv,
@@ -144,6 +145,61 @@ fn deploy_fail() -> (
         block_time,
     )
 }
+
+fn lock_and_add_gauges(
+    gauge_controller: &GAUGECONLTROLLERInstance,
+    owner: AccountHash,
+    token: &TestContract,
+    voting_escrow: &TestContract,
+    gauges: &[Key],
+    blocktime: u64,
+) {
+    let value: U256 = VOTE_LOCK_VALUE.into();
+    let unlock_time: U256 = VOTING_ESCROW_WEEK
+        + VOTING_ESCROW_WEEK
+        + VOTING_ESCROW_WEEK
+        + VOTING_ESCROW_WEEK
+        + blocktime;
+    token.call_contract(
+        owner,
+        "mint",
+        runtime_args! {
+            "to" => Key::Account(owner),
+            "amount" => value
+        },
+        blocktime,
+    );
+    token.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(voting_escrow.package_hash()),
+            "amount" => value
+        },
+        blocktime,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "create_lock",
+        runtime_args! {
+            "value" => value,
+            "unlock_time" => unlock_time
+        },
+        blocktime,
+    );
+    let name: String = "type".to_string();
+    gauge_controller.add_type(owner, name, None, blocktime);
+    let gauge_type: (bool, U128) = (false, 0.into());
+    for gauge in gauges {
+        gauge_controller.add_gauge(owner, *gauge, gauge_type, None, blocktime);
+    }
+}
+
+fn vote_lock_end(blocktime: u64) -> U256 {
+    let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+    U256::from((blocktime + week * 4) / week * week)
+}
+
 mod ownership_and_deploy_test_cases {
     use crate::gauge_controller_tests::*;
     #[test]
@@ -1809,223 +1865,878 @@ mod add_gauge_function_test_cases {
         assert_eq!(ret, liquidity_gauge, "Invalid result");
     }
 }
-mod panic_test_cases_1 {
+mod vote_for_many_gauge_weights_test_cases {
     use crate::gauge_controller_tests::*;
     #[test]
-    #[should_panic]
-    fn test_gauge_controller_add_gauge_by_user() {
-        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
-            deploy();
-        let _user = env.next_user();
-        let _user1 = env.next_user();
-        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+    fn test_gauge_controller_vote_for_many_gauge_weights() {
+        let (
+            _env,
+            gauge_controller,
+            owner,
+            token,
+            voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        lock_and_add_gauges(
+            &gauge_controller,
+            owner,
+            &token,
+            &voting_escrow,
+            &[liquidity_gauge, liquidity_gauge_1],
+            blocktime,
+        );
+        gauge_controller.vote_for_many_gauge_weights(
+            owner,
+            vec![
+                (liquidity_gauge, 6000.into()),
+                (liquidity_gauge_1, 4000.into()),
+            ],
+            blocktime,
+        );
         assert_eq!(
-            gauge_controller.voting_escrow(),
-            Key::Hash(_voting_escrow.package_hash())
+            gauge_controller.vote_user_power(Key::Account(owner)),
+            10000.into()
+        );
+        let lock_end: U256 = vote_lock_end(blocktime);
+        let slope: U256 = gauge_controller.changes_weight(liquidity_gauge, lock_end);
+        let slope_1: U256 = gauge_controller.changes_weight(liquidity_gauge_1, lock_end);
+        assert!(slope_1 > 0.into());
+        assert!(slope > slope_1);
+        // Raising a vote before lowering the other one is fine, only the
+        // final total is checked
+        let blocktime = blocktime + 86400000;
+        gauge_controller.vote_for_many_gauge_weights(
+            owner,
+            vec![
+                (liquidity_gauge_1, 8000.into()),
+                (liquidity_gauge, 2000.into()),
+            ],
+            blocktime,
         );
-        assert_eq!(gauge_controller.admin(), Key::from(_owner));
-        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
         assert_eq!(
-            gauge_controller.time_total(),
-            U256::from(blocktime / week * week)
+            gauge_controller.vote_user_power(Key::Account(owner)),
+            10000.into()
         );
-        let name: String = "type".to_string();
-        gauge_controller.add_type(_owner, name, None, blocktime);
-        let gauge_type: (bool, U128) = (false, 0.into());
-        gauge_controller.add_gauge(_user, liquidity_gauge, gauge_type, None, blocktime);
+        // The old slopes are replaced, not added to
+        let new_slope: U256 = gauge_controller.changes_weight(liquidity_gauge, lock_end);
+        let new_slope_1: U256 = gauge_controller.changes_weight(liquidity_gauge_1, lock_end);
+        assert!(new_slope > 0.into());
+        assert!(new_slope_1 >= new_slope * 4);
+        assert!(new_slope + new_slope_1 <= slope + slope_1 + 1);
     }
 
     #[test]
     #[should_panic]
-    fn test_gauge_controller_add_gauge_multiple_time_by_user() {
+    fn test_gauge_controller_vote_for_many_gauge_weights_too_much_power() {
         let (
-            env,
+            _env,
             gauge_controller,
-            _owner,
-            _token,
-            _voting_escrow,
+            owner,
+            token,
+            voting_escrow,
             blocktime,
             liquidity_gauge,
             liquidity_gauge_1,
         ) = deploy();
-        let _user = env.next_user();
-        let _user1 = env.next_user();
-        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
-        assert_eq!(
-            gauge_controller.voting_escrow(),
-            Key::Hash(_voting_escrow.package_hash())
-        );
-        assert_eq!(gauge_controller.admin(), Key::from(_owner));
-        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
-        assert_eq!(
-            gauge_controller.time_total(),
-            U256::from(blocktime / week * week)
+        lock_and_add_gauges(
+            &gauge_controller,
+            owner,
+            &token,
+            &voting_escrow,
+            &[liquidity_gauge, liquidity_gauge_1],
+            blocktime,
         );
-        let name: String = "type".to_string();
-        gauge_controller.add_type(_owner, name, None, blocktime);
-        let gauge_type: (bool, U128) = (false, 0.into());
-        gauge_controller.add_gauge(_owner, liquidity_gauge, gauge_type, None, blocktime);
-        let name: String = "type2".to_string();
-        gauge_controller.add_type(_owner, name, None, blocktime);
-        let gauge_type: (bool, U128) = (false, 1.into());
-        gauge_controller.add_gauge(_user, liquidity_gauge_1, gauge_type, None, blocktime);
-        let val: U256 = 0.into();
-        TestContract::new(
-            &env,
-            TEST_SESSION_CODE_WASM,
-            TEST_SESSION_CODE_NAME,
-            _owner,
-            runtime_args! {
-                "entrypoint" => String::from(GAUGES),
-                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
-                "owner" => val
-            },
+        gauge_controller.vote_for_many_gauge_weights(
+            owner,
+            vec![
+                (liquidity_gauge, 6000.into()),
+                (liquidity_gauge_1, 5000.into()),
+            ],
             blocktime,
         );
-        let ret: Key = env.query_account_named_key(_owner, &[GAUGES.into()]);
-        assert_eq!(ret, liquidity_gauge, "Invalid result");
     }
+}
+mod vote_delegate_test_cases {
+    use crate::gauge_controller_tests::*;
     #[test]
-    #[should_panic]
-    fn test_gauge_controller_change_gauge_weight_without_adding_gauge() {
-        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
+    fn test_gauge_controller_vote_for_gauge_weights_for() {
+        let (env, gauge_controller, owner, token, voting_escrow, blocktime, liquidity_gauge, _) =
             deploy();
-        let _user = env.next_user();
-        let _user1 = env.next_user();
-        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+        let delegate = env.next_user();
+        lock_and_add_gauges(
+            &gauge_controller,
+            owner,
+            &token,
+            &voting_escrow,
+            &[liquidity_gauge],
+            blocktime,
+        );
+        gauge_controller.set_vote_delegate(owner, Key::Account(delegate), blocktime);
         assert_eq!(
-            gauge_controller.voting_escrow(),
-            Key::Hash(_voting_escrow.package_hash())
+            gauge_controller.vote_delegates(Key::Account(owner)),
+            Some(Key::Account(delegate))
+        );
+        gauge_controller.vote_for_gauge_weights_for(
+            delegate,
+            Key::Account(owner),
+            liquidity_gauge,
+            1000.into(),
+            blocktime,
         );
-        assert_eq!(gauge_controller.admin(), Key::from(_owner));
-        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
         assert_eq!(
-            gauge_controller.time_total(),
-            U256::from(blocktime / week * week)
+            gauge_controller.vote_user_power(Key::Account(owner)),
+            1000.into()
+        );
+        assert_eq!(
+            gauge_controller.vote_user_power(Key::Account(delegate)),
+            0.into()
+        );
+        // The vote is backed by the slope of the owner's lock
+        assert!(
+            gauge_controller.changes_weight(liquidity_gauge, vote_lock_end(blocktime)) > 0.into()
+        );
+        assert_eq!(
+            gauge_controller.last_user_vote(Key::Account(owner), liquidity_gauge),
+            blocktime.into()
         );
-        let name: String = "type".to_string();
-        gauge_controller.add_type(_owner, name, None, blocktime);
-        let weight: U256 = 2.into();
-        gauge_controller.change_gauge_weight(_owner, liquidity_gauge, weight, blocktime);
     }
     #[test]
     #[should_panic]
-    fn test_gauge_controller_vote_for_gauge_weights_by_user() {
+    fn test_gauge_controller_vote_for_gauge_weights_for_after_revoke() {
         let (env, gauge_controller, owner, token, voting_escrow, blocktime, liquidity_gauge, _) =
             deploy();
-
-        let value: U256 = 1000.into();
-        let unlock_time: U256 =
-            VOTING_ESCROW_WEEK + VOTING_ESCROW_WEEK + VOTING_ESCROW_WEEK + VOTING_ESCROW_WEEK;
-        token.call_contract(
+        let delegate = env.next_user();
+        lock_and_add_gauges(
+            &gauge_controller,
             owner,
-            "mint",
-            runtime_args! {
-                "to" => Key::Account(owner),
-                "amount" => value + value
-            },
-            0,
+            &token,
+            &voting_escrow,
+            &[liquidity_gauge],
+            blocktime,
         );
-        token.call_contract(
-            owner,
-            "approve",
-            runtime_args! {
-                "spender" => Key::Hash(voting_escrow.package_hash()),
-                "amount" => value + value
-            },
-            0,
+        gauge_controller.set_vote_delegate(owner, Key::Account(delegate), blocktime);
+        gauge_controller.revoke_vote_delegate(owner, blocktime);
+        gauge_controller.vote_for_gauge_weights_for(
+            delegate,
+            Key::Account(owner),
+            liquidity_gauge,
+            1000.into(),
+            blocktime,
         );
-        voting_escrow.call_contract(
-            owner,
-            "create_lock",
+    }
+}
+mod gauge_weight_cap_test_cases {
+    use crate::gauge_controller_tests::*;
+
+    fn relative_weight(
+        env: &TestEnv,
+        gauge_controller: &GAUGECONLTROLLERInstance,
+        sender: AccountHash,
+        addr: Key,
+        blocktime: u64,
+    ) -> U256 {
+        TestContract::new(
+            env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            sender,
             runtime_args! {
-                "value" => value,
-                "unlock_time" => unlock_time
+                "entrypoint" => String::from(GAUGE_RELATIVE_WEIGHT),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+                "addr" => addr,
+                "time" => None::<U256>
             },
-            0,
-        );
-        let _user = env.next_user();
-        let _user1 = env.next_user();
-        assert_eq!(gauge_controller.token(), Key::Hash(token.package_hash()));
-        assert_eq!(
-            gauge_controller.voting_escrow(),
-            Key::Hash(voting_escrow.package_hash())
-        );
-        assert_eq!(gauge_controller.admin(), Key::from(owner));
-        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
-        assert_eq!(
-            gauge_controller.time_total(),
-            U256::from(blocktime / week * week)
+            blocktime,
         );
-        let name: String = "type".to_string();
-        gauge_controller.add_type(owner, name, None, blocktime);
-        let gauge_type: (bool, U128) = (false, 0.into());
-        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
-        let weight: U256 = 0.into();
-        gauge_controller.vote_for_gauge_weights(_user, liquidity_gauge, weight, blocktime);
+        env.query_account_named_key(sender, &[GAUGE_RELATIVE_WEIGHT.into()])
     }
+
     #[test]
-    #[should_panic]
-    fn test_gauge_controller_gauge_types_without_adding_gauge_types() {
+    fn test_gauge_controller_set_gauge_weight_cap() {
         let (
             env,
             gauge_controller,
-            _owner,
+            owner,
             _token,
             _voting_escrow,
             blocktime,
             liquidity_gauge,
-            _liquidity_gauge_1,
+            liquidity_gauge_1,
         ) = deploy();
-        let _user = env.next_user();
-        let _user1 = env.next_user();
-        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
-        assert_eq!(
-            gauge_controller.voting_escrow(),
-            Key::Hash(_voting_escrow.package_hash())
+        let liquidity_gauge_2 = Key::Hash([7u8; 32]);
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(1.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            gauge_type,
+            Some(1000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(6000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_2,
+            gauge_type,
+            Some(3000.into()),
+            blocktime,
+        );
+        gauge_controller.set_gauge_weight_cap(
+            owner,
+            liquidity_gauge_1,
+            400000000.into(),
+            blocktime,
         );
-        assert_eq!(gauge_controller.admin(), Key::from(_owner));
         let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let next_time: u64 = (blocktime + week) / week * week;
         assert_eq!(
-            gauge_controller.time_total(),
-            U256::from(blocktime / week * week)
+            gauge_controller.points_weight_cap(liquidity_gauge_1, next_time.into()),
+            400000000.into()
         );
-        TestContract::new(
-            &env,
-            TEST_SESSION_CODE_WASM,
-            TEST_SESSION_CODE_NAME,
-            _user,
-            runtime_args! {
-                "entrypoint" => String::from(GAUGE_TYPES),
-                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
-                "addr" => liquidity_gauge
-            },
-            blocktime,
+        // 10% / 60% / 30% becomes 15% / 40% / 45%
+        assert_eq!(
+            relative_weight(&env, &gauge_controller, owner, liquidity_gauge, next_time),
+            150000000.into()
         );
-
-        let ret: (bool, U128) = env.query_account_named_key(_user, &[GAUGE_TYPES.into()]);
-        assert_eq!(ret, (false, 0.into()));
-    }
-
-    #[test]
-    #[should_panic]
-    fn test_gauge_controller_apply_transfer_ownership_by_user() {
-        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, _, _) = deploy();
-        let _user = env.next_user();
-        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
         assert_eq!(
-            gauge_controller.voting_escrow(),
-            Key::Hash(_voting_escrow.package_hash())
+            relative_weight(&env, &gauge_controller, owner, liquidity_gauge_1, next_time),
+            400000000.into()
         );
-        assert_eq!(gauge_controller.admin(), Key::from(_owner));
-        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
         assert_eq!(
-            gauge_controller.time_total(),
-            U256::from(blocktime / week * week)
+            relative_weight(&env, &gauge_controller, owner, liquidity_gauge_2, next_time),
+            450000000.into()
         );
-        gauge_controller.commit_transfer_ownership(_owner, _user, blocktime);
-        assert_eq!(gauge_controller.future_admin(), Key::from(_user));
-        gauge_controller.apply_transfer_ownership(_user, blocktime);
+    }
+
+    #[test]
+    fn test_gauge_controller_set_gauge_weight_cap_with_votes() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            token,
+            voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        lock_and_add_gauges(
+            &gauge_controller,
+            owner,
+            &token,
+            &voting_escrow,
+            &[liquidity_gauge, liquidity_gauge_1],
+            blocktime,
+        );
+        gauge_controller.change_type_weight(owner, (false, 0.into()), 1.into(), blocktime);
+        gauge_controller.set_gauge_weight_cap(owner, liquidity_gauge, 500000000.into(), blocktime);
+        gauge_controller.vote_for_many_gauge_weights(
+            owner,
+            vec![
+                (liquidity_gauge, 8000.into()),
+                (liquidity_gauge_1, 2000.into()),
+            ],
+            blocktime,
+        );
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let next_time: u64 = (blocktime + week) / week * week;
+        // 80% / 20% of the vote biases becomes 50% / 50%, up to rounding
+        assert_eq!(
+            relative_weight(&env, &gauge_controller, owner, liquidity_gauge, next_time),
+            500000000.into()
+        );
+        let relative_weight_1: U256 =
+            relative_weight(&env, &gauge_controller, owner, liquidity_gauge_1, next_time);
+        assert!(relative_weight_1 <= 500000000.into());
+        assert!(relative_weight_1 >= 499999999.into());
+    }
+
+    #[test]
+    fn test_gauge_controller_weight_caps_checkpointed() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(1.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            gauge_type,
+            Some(4000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(6000.into()),
+            blocktime,
+        );
+        gauge_controller.set_gauge_weight_cap(
+            owner,
+            liquidity_gauge_1,
+            500000000.into(),
+            blocktime,
+        );
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let next_time: u64 = (blocktime + week) / week * week;
+        gauge_controller.checkpoint(owner, next_time);
+        assert_eq!(
+            gauge_controller.points_capped_weight(liquidity_gauge, next_time.into()),
+            500000000.into()
+        );
+        assert_eq!(
+            gauge_controller.points_capped_weight(liquidity_gauge_1, next_time.into()),
+            500000000.into()
+        );
+        // The checkpointed week keeps its capped weights, the following ones do not
+        gauge_controller.set_weight_caps_enabled(owner, false, next_time);
+        assert!(!gauge_controller.key_value::<bool>(WEIGHT_CAPS_ENABLED.to_string()));
+        assert_eq!(
+            relative_weight(&env, &gauge_controller, owner, liquidity_gauge_1, next_time),
+            500000000.into()
+        );
+        assert_eq!(
+            relative_weight(
+                &env,
+                &gauge_controller,
+                owner,
+                liquidity_gauge_1,
+                next_time + week
+            ),
+            600000000.into()
+        );
+    }
+
+    #[test]
+    fn test_gauge_controller_weight_caps_kept_for_past_weeks() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(1.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            gauge_type,
+            Some(4000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(6000.into()),
+            blocktime,
+        );
+        gauge_controller.set_gauge_weight_cap(
+            owner,
+            liquidity_gauge_1,
+            500000000.into(),
+            blocktime,
+        );
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let next_time: u64 = (blocktime + week) / week * week;
+        // Nobody checkpoints during the first capped week, turning caps off
+        // afterwards must not uncap it, nor the week that already started
+        gauge_controller.set_weight_caps_enabled(owner, false, next_time + week);
+        assert_eq!(
+            gauge_controller.points_capped_weight(liquidity_gauge_1, next_time.into()),
+            500000000.into()
+        );
+        assert_eq!(
+            gauge_controller.points_capped_weight(liquidity_gauge_1, (next_time + week).into()),
+            500000000.into()
+        );
+        assert_eq!(
+            relative_weight(
+                &env,
+                &gauge_controller,
+                owner,
+                liquidity_gauge_1,
+                next_time + 2 * week
+            ),
+            600000000.into()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_set_weight_caps_enabled_by_user() {
+        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, _, _) = deploy();
+        let user = env.next_user();
+        gauge_controller.set_weight_caps_enabled(user, false, blocktime);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_set_gauge_weight_cap_by_user() {
+        let (env, gauge_controller, owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+        let user = env.next_user();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
+        gauge_controller.set_gauge_weight_cap(user, liquidity_gauge, 400000000.into(), blocktime);
+    }
+}
+mod kill_gauge_test_cases {
+    use crate::gauge_controller_tests::*;
+    #[test]
+    fn test_gauge_controller_kill_gauge() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(2.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            gauge_type,
+            Some(500.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(1000000.into()),
+            blocktime,
+        );
+        gauge_controller.kill_gauge(owner, liquidity_gauge, blocktime);
+        assert!(gauge_controller.gauge_is_killed(liquidity_gauge));
+        assert!(!gauge_controller.gauge_is_killed(liquidity_gauge_1));
+        TestContract::new(
+            &env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            owner,
+            runtime_args! {
+                "entrypoint" => String::from(GET_TOTAL_WEIGHT),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+            },
+            blocktime,
+        );
+        let ret: U256 = env.query_account_named_key(owner, &[GET_TOTAL_WEIGHT.into()]);
+        assert_eq!(ret, 2000000.into());
+        TestContract::new(
+            &env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            owner,
+            runtime_args! {
+                "entrypoint" => String::from(GET_GAUGE_WEIGHT),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+                "addr" => liquidity_gauge
+            },
+            blocktime,
+        );
+        let ret: U256 = env.query_account_named_key(owner, &[GET_GAUGE_WEIGHT.into()]);
+        assert_eq!(ret, 0.into());
+    }
+    #[test]
+    fn test_gauge_controller_reclaim_gauge_power() {
+        let (_env, gauge_controller, owner, token, voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+        let value: U256 = VOTE_LOCK_VALUE.into();
+        let unlock_time: U256 = VOTING_ESCROW_WEEK
+            + VOTING_ESCROW_WEEK
+            + VOTING_ESCROW_WEEK
+            + VOTING_ESCROW_WEEK
+            + blocktime;
+        token.call_contract(
+            owner,
+            "mint",
+            runtime_args! {
+                "to" => Key::Account(owner),
+                "amount" => value
+            },
+            blocktime,
+        );
+        token.call_contract(
+            owner,
+            "approve",
+            runtime_args! {
+                "spender" => Key::Hash(voting_escrow.package_hash()),
+                "amount" => value
+            },
+            blocktime,
+        );
+        voting_escrow.call_contract(
+            owner,
+            "create_lock",
+            runtime_args! {
+                "value" => value,
+                "unlock_time" => unlock_time
+            },
+            blocktime,
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
+        let weight: U256 = 1000.into();
+        gauge_controller.vote_for_gauge_weights(owner, liquidity_gauge, weight, blocktime);
+        assert_eq!(
+            gauge_controller.vote_user_power(Key::Account(owner)),
+            1000.into()
+        );
+        gauge_controller.kill_gauge(owner, liquidity_gauge, blocktime);
+        gauge_controller.reclaim_gauge_power(owner, liquidity_gauge, blocktime);
+        assert_eq!(
+            gauge_controller.vote_user_power(Key::Account(owner)),
+            0.into()
+        );
+    }
+    #[test]
+    fn test_gauge_controller_kill_gauge_with_live_votes() {
+        let (
+            _env,
+            gauge_controller,
+            owner,
+            token,
+            voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let value: U256 = VOTE_LOCK_VALUE.into();
+        let unlock_time: U256 = VOTING_ESCROW_WEEK * U256::from(4) + blocktime;
+        token.call_contract(
+            owner,
+            "mint",
+            runtime_args! {
+                "to" => Key::Account(owner),
+                "amount" => value
+            },
+            blocktime,
+        );
+        token.call_contract(
+            owner,
+            "approve",
+            runtime_args! {
+                "spender" => Key::Hash(voting_escrow.package_hash()),
+                "amount" => value
+            },
+            blocktime,
+        );
+        voting_escrow.call_contract(
+            owner,
+            "create_lock",
+            runtime_args! {
+                "value" => value,
+                "unlock_time" => unlock_time
+            },
+            blocktime,
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(1.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
+        // Keeps the type sum above the vote biases once the votes run out
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(1000000000000000000_u128.into()),
+            blocktime,
+        );
+        gauge_controller.vote_for_gauge_weights(owner, liquidity_gauge, 5000.into(), blocktime);
+        gauge_controller.vote_for_gauge_weights(owner, liquidity_gauge_1, 5000.into(), blocktime);
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let lock_end: U256 = unlock_time / week * week;
+        assert!(gauge_controller.changes_weight(liquidity_gauge, lock_end) > 0.into());
+        gauge_controller.kill_gauge(owner, liquidity_gauge, blocktime);
+        assert_eq!(
+            gauge_controller.changes_weight(liquidity_gauge, lock_end),
+            0.into()
+        );
+        gauge_controller.reclaim_gauge_power(owner, liquidity_gauge, blocktime);
+        assert_eq!(
+            gauge_controller.vote_user_power(Key::Account(owner)),
+            5000.into()
+        );
+        // Walking the type sum past the end of the votes must not subtract the killed slope again
+        let after_end: u64 = lock_end.as_u64() + week * 2;
+        gauge_controller.checkpoint_gauge(owner, liquidity_gauge_1, after_end);
+        gauge_controller.checkpoint(owner, after_end);
+    }
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_kill_gauge_by_user() {
+        let (env, gauge_controller, owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+        let user = env.next_user();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
+        gauge_controller.kill_gauge(user, liquidity_gauge, blocktime);
+    }
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_vote_for_killed_gauge() {
+        let (_env, gauge_controller, owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
+        gauge_controller.kill_gauge(owner, liquidity_gauge, blocktime);
+        gauge_controller.vote_for_gauge_weights(owner, liquidity_gauge, 0.into(), blocktime);
+    }
+}
+mod panic_test_cases_1 {
+    use crate::gauge_controller_tests::*;
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_add_gauge_by_user() {
+        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+        let _user = env.next_user();
+        let _user1 = env.next_user();
+        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+        assert_eq!(
+            gauge_controller.voting_escrow(),
+            Key::Hash(_voting_escrow.package_hash())
+        );
+        assert_eq!(gauge_controller.admin(), Key::from(_owner));
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from(blocktime / week * week)
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(_owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(_user, liquidity_gauge, gauge_type, None, blocktime);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_add_gauge_multiple_time_by_user() {
+        let (
+            env,
+            gauge_controller,
+            _owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let _user = env.next_user();
+        let _user1 = env.next_user();
+        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+        assert_eq!(
+            gauge_controller.voting_escrow(),
+            Key::Hash(_voting_escrow.package_hash())
+        );
+        assert_eq!(gauge_controller.admin(), Key::from(_owner));
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from(blocktime / week * week)
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(_owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(_owner, liquidity_gauge, gauge_type, None, blocktime);
+        let name: String = "type2".to_string();
+        gauge_controller.add_type(_owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 1.into());
+        gauge_controller.add_gauge(_user, liquidity_gauge_1, gauge_type, None, blocktime);
+        let val: U256 = 0.into();
+        TestContract::new(
+            &env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            _owner,
+            runtime_args! {
+                "entrypoint" => String::from(GAUGES),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+                "owner" => val
+            },
+            blocktime,
+        );
+        let ret: Key = env.query_account_named_key(_owner, &[GAUGES.into()]);
+        assert_eq!(ret, liquidity_gauge, "Invalid result");
+    }
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_change_gauge_weight_without_adding_gauge() {
+        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+        let _user = env.next_user();
+        let _user1 = env.next_user();
+        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+        assert_eq!(
+            gauge_controller.voting_escrow(),
+            Key::Hash(_voting_escrow.package_hash())
+        );
+        assert_eq!(gauge_controller.admin(), Key::from(_owner));
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from(blocktime / week * week)
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(_owner, name, None, blocktime);
+        let weight: U256 = 2.into();
+        gauge_controller.change_gauge_weight(_owner, liquidity_gauge, weight, blocktime);
+    }
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_vote_for_gauge_weights_by_user() {
+        let (env, gauge_controller, owner, token, voting_escrow, blocktime, liquidity_gauge, _) =
+            deploy();
+
+        let value: U256 = 1000.into();
+        let unlock_time: U256 =
+            VOTING_ESCROW_WEEK + VOTING_ESCROW_WEEK + VOTING_ESCROW_WEEK + VOTING_ESCROW_WEEK;
+        token.call_contract(
+            owner,
+            "mint",
+            runtime_args! {
+                "to" => Key::Account(owner),
+                "amount" => value + value
+            },
+            0,
+        );
+        token.call_contract(
+            owner,
+            "approve",
+            runtime_args! {
+                "spender" => Key::Hash(voting_escrow.package_hash()),
+                "amount" => value + value
+            },
+            0,
+        );
+        voting_escrow.call_contract(
+            owner,
+            "create_lock",
+            runtime_args! {
+                "value" => value,
+                "unlock_time" => unlock_time
+            },
+            0,
+        );
+        let _user = env.next_user();
+        let _user1 = env.next_user();
+        assert_eq!(gauge_controller.token(), Key::Hash(token.package_hash()));
+        assert_eq!(
+            gauge_controller.voting_escrow(),
+            Key::Hash(voting_escrow.package_hash())
+        );
+        assert_eq!(gauge_controller.admin(), Key::from(owner));
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from(blocktime / week * week)
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, None, blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(owner, liquidity_gauge, gauge_type, None, blocktime);
+        let weight: U256 = 0.into();
+        gauge_controller.vote_for_gauge_weights(_user, liquidity_gauge, weight, blocktime);
+    }
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_gauge_types_without_adding_gauge_types() {
+        let (
+            env,
+            gauge_controller,
+            _owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            _liquidity_gauge_1,
+        ) = deploy();
+        let _user = env.next_user();
+        let _user1 = env.next_user();
+        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+        assert_eq!(
+            gauge_controller.voting_escrow(),
+            Key::Hash(_voting_escrow.package_hash())
+        );
+        assert_eq!(gauge_controller.admin(), Key::from(_owner));
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from(blocktime / week * week)
+        );
+        TestContract::new(
+            &env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            _user,
+            runtime_args! {
+                "entrypoint" => String::from(GAUGE_TYPES),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+                "addr" => liquidity_gauge
+            },
+            blocktime,
+        );
+
+        let ret: (bool, U128) = env.query_account_named_key(_user, &[GAUGE_TYPES.into()]);
+        assert_eq!(ret, (false, 0.into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_apply_transfer_ownership_by_user() {
+        let (env, gauge_controller, _owner, _token, _voting_escrow, blocktime, _, _) = deploy();
+        let _user = env.next_user();
+        assert_eq!(gauge_controller.token(), Key::Hash(_token.package_hash()));
+        assert_eq!(
+            gauge_controller.voting_escrow(),
+            Key::Hash(_voting_escrow.package_hash())
+        );
+        assert_eq!(gauge_controller.admin(), Key::from(_owner));
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from(blocktime / week * week)
+        );
+        gauge_controller.commit_transfer_ownership(_owner, _user, blocktime);
+        assert_eq!(gauge_controller.future_admin(), Key::from(_user));
+        gauge_controller.apply_transfer_ownership(_user, blocktime);
         assert_eq!(gauge_controller.admin(), Key::from(_user));
     }
 }
@@ -2152,3 +2863,462 @@ mod panic_test_cases_2 {
         gauge_controller.change_type_weight(_user, type_id, weight, blocktime);
     }
 }
+mod projected_gauge_weights_test_cases {
+    use crate::gauge_controller_tests::*;
+
+    fn projected_gauge_weights(
+        env: &TestEnv,
+        gauge_controller: &GAUGECONLTROLLERInstance,
+        sender: AccountHash,
+        addr: Key,
+        weeks: U256,
+        blocktime: u64,
+    ) -> Vec<(U256, U256, U256)> {
+        TestContract::new(
+            env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            sender,
+            runtime_args! {
+                "entrypoint" => String::from(PROJECTED_GAUGE_WEIGHTS),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+                "addr" => addr,
+                "weeks" => weeks
+            },
+            blocktime,
+        );
+        env.query_account_named_key(sender, &[PROJECTED_GAUGE_WEIGHTS.into()])
+    }
+
+    #[test]
+    fn test_gauge_controller_projected_gauge_weights() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            token,
+            voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let value: U256 = 1000000000000000_u128.into();
+        let unlock_time: U256 = VOTING_ESCROW_WEEK * 52 + blocktime;
+        token.call_contract(
+            owner,
+            "mint",
+            runtime_args! {
+                "to" => Key::Account(owner),
+                "amount" => value
+            },
+            blocktime,
+        );
+        token.call_contract(
+            owner,
+            "approve",
+            runtime_args! {
+                "spender" => Key::Hash(voting_escrow.package_hash()),
+                "amount" => value
+            },
+            blocktime,
+        );
+        voting_escrow.call_contract(
+            owner,
+            "create_lock",
+            runtime_args! {
+                "value" => value,
+                "unlock_time" => unlock_time
+            },
+            blocktime,
+        );
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(1.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            gauge_type,
+            Some(1000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(3000.into()),
+            blocktime,
+        );
+        gauge_controller.vote_for_gauge_weights(owner, liquidity_gauge, 10000.into(), blocktime);
+
+        let weeks: U256 = 4.into();
+        let voted = projected_gauge_weights(
+            &env,
+            &gauge_controller,
+            owner,
+            liquidity_gauge,
+            weeks,
+            blocktime,
+        );
+        let fixed = projected_gauge_weights(
+            &env,
+            &gauge_controller,
+            owner,
+            liquidity_gauge_1,
+            weeks,
+            blocktime,
+        );
+        assert_eq!(voted.len(), 4);
+        assert_eq!(fixed.len(), 4);
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        assert_eq!(voted[0].0, U256::from(blocktime / week * week));
+        // Nothing is in force before the next week starts
+        assert_eq!(voted[0].1, 0.into());
+        assert_eq!(fixed[0].1, 0.into());
+        for (projected, flat) in voted.iter().zip(fixed.iter()).skip(1) {
+            assert_eq!(flat.1, 3000.into());
+            assert!(projected.1 > 1000.into());
+            let relative_sum: U256 = projected.2 + flat.2;
+            assert!(relative_sum <= 1000000000.into());
+            assert!(relative_sum >= 999999998.into());
+        }
+        // The vote decays every week while the admin weight stays flat
+        for pair in voted.windows(2).skip(1) {
+            assert_eq!(pair[1].0, pair[0].0 + VOTING_ESCROW_WEEK);
+            assert!(pair[1].1 < pair[0].1);
+        }
+        // State is left untouched
+        assert_eq!(
+            gauge_controller.time_total(),
+            U256::from((blocktime + week) / week * week)
+        );
+    }
+
+    #[test]
+    fn test_gauge_controller_projected_gauge_weights_capped() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        let name: String = "type".to_string();
+        gauge_controller.add_type(owner, name, Some(1.into()), blocktime);
+        let gauge_type: (bool, U128) = (false, 0.into());
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            gauge_type,
+            Some(4000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            gauge_type,
+            Some(6000.into()),
+            blocktime,
+        );
+        gauge_controller.set_gauge_weight_cap(
+            owner,
+            liquidity_gauge_1,
+            500000000.into(),
+            blocktime,
+        );
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let next_time: U256 = ((blocktime + week) / week * week).into();
+        // 40% / 60% becomes 50% / 50% once the gauges have weight
+        let projection = projected_gauge_weights(
+            &env,
+            &gauge_controller,
+            owner,
+            liquidity_gauge_1,
+            3.into(),
+            blocktime,
+        );
+        assert_eq!(projection[0].2, 0.into());
+        assert_eq!(projection[1], (next_time, 6000.into(), 500000000.into()));
+        assert_eq!(projection[2].2, 500000000.into());
+        let projection = projected_gauge_weights(
+            &env,
+            &gauge_controller,
+            owner,
+            liquidity_gauge,
+            3.into(),
+            blocktime,
+        );
+        assert_eq!(projection[1], (next_time, 4000.into(), 500000000.into()));
+    }
+
+    #[test]
+    fn test_gauge_controller_projected_gauge_weights_pending_type_weight() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            _token,
+            _voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            liquidity_gauge_1,
+        ) = deploy();
+        gauge_controller.add_type(owner, "type".to_string(), Some(1.into()), blocktime);
+        gauge_controller.add_type(owner, "type_1".to_string(), Some(1.into()), blocktime);
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge,
+            (false, 0.into()),
+            Some(1000.into()),
+            blocktime,
+        );
+        gauge_controller.add_gauge(
+            owner,
+            liquidity_gauge_1,
+            (false, 1.into()),
+            Some(1000.into()),
+            blocktime,
+        );
+        let week: u64 = VOTING_ESCROW_WEEK.as_u64();
+        let next_time: u64 = (blocktime + week) / week * week;
+        // Only in force from the week after `next_time` on
+        gauge_controller.change_type_weight(owner, (false, 1.into()), 3.into(), next_time);
+        let projection = projected_gauge_weights(
+            &env,
+            &gauge_controller,
+            owner,
+            liquidity_gauge,
+            3.into(),
+            next_time,
+        );
+        assert_eq!(
+            projection[0],
+            (next_time.into(), 1000.into(), 500000000.into())
+        );
+        assert_eq!(
+            projection[1],
+            ((next_time + week).into(), 1000.into(), 250000000.into())
+        );
+        assert_eq!(projection[2].2, 250000000.into());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_projected_gauge_weights_too_many_weeks() {
+        let (
+            env,
+            gauge_controller,
+            owner,
+            token,
+            voting_escrow,
+            blocktime,
+            liquidity_gauge,
+            _liquidity_gauge_1,
+        ) = deploy();
+        lock_and_add_gauges(
+            &gauge_controller,
+            owner,
+            &token,
+            &voting_escrow,
+            &[liquidity_gauge],
+            blocktime,
+        );
+        projected_gauge_weights(
+            &env,
+            &gauge_controller,
+            owner,
+            liquidity_gauge,
+            53.into(),
+            blocktime,
+        );
+    }
+}
+
+mod gauge_metadata_test_cases {
+    use crate::gauge_controller_instance::{GaugeInfo, GaugeMetadata};
+    use crate::gauge_controller_tests::*;
+
+    fn gauges_page(
+        env: &TestEnv,
+        gauge_controller: &GAUGECONLTROLLERInstance,
+        sender: AccountHash,
+        start: U256,
+        count: U256,
+        blocktime: u64,
+    ) -> Vec<GaugeInfo> {
+        TestContract::new(
+            env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            sender,
+            runtime_args! {
+                "entrypoint" => String::from(GAUGES_PAGE),
+                "package_hash" => Key::from(gauge_controller.contract_package_hash()),
+                "start" => start,
+                "count" => count
+            },
+            blocktime,
+        );
+        env.query_account_named_key(sender, &[GAUGES_PAGE.into()])
+    }
+
+    fn metadata(name: &str, kind: u8) -> GaugeMetadata {
+        GaugeMetadata {
+            name: name.to_string(),
+            kind,
+            lp_token: Key::Hash([1u8; 32]),
+            reward_tokens: vec![Key::Hash([2u8; 32]), Key::Hash([3u8; 32])],
+        }
+    }
+
+    #[test]
+    fn test_gauge_controller_gauges_page() {
+        let (env, gauge_controller, owner, _, _, blocktime, _, _) = deploy();
+        gauge_controller.add_type(owner, "type_0".to_string(), None, blocktime);
+        gauge_controller.add_type(owner, "type_1".to_string(), None, blocktime);
+        let gauges: Vec<Key> = (0..3u8).map(|i| Key::Hash([10 + i; 32])).collect();
+        gauge_controller.add_gauge_with_metadata(
+            owner,
+            gauges[0],
+            (false, 0.into()),
+            None,
+            metadata("v3", 1),
+            blocktime,
+        );
+        // Gauges added without metadata are listed with an empty record
+        gauge_controller.add_gauge(owner, gauges[1], (false, 1.into()), None, blocktime);
+        gauge_controller.add_gauge_with_metadata(
+            owner,
+            gauges[2],
+            (false, 1.into()),
+            None,
+            metadata("wrapper", 3),
+            blocktime,
+        );
+        assert_eq!(
+            gauge_controller.gauge_metadata(gauges[0]),
+            Some(metadata("v3", 1))
+        );
+        assert_eq!(
+            gauge_controller.gauge_metadata(gauges[1]),
+            Some(GaugeMetadata {
+                name: String::new(),
+                kind: 0,
+                lp_token: Key::Hash([0u8; 32]),
+                reward_tokens: Vec::new(),
+            })
+        );
+
+        let page = gauges_page(
+            &env,
+            &gauge_controller,
+            owner,
+            0.into(),
+            2.into(),
+            blocktime,
+        );
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].addr, gauges[0]);
+        assert_eq!(page[0].gauge_type, (false, 0.into()));
+        assert_eq!(page[0].metadata, metadata("v3", 1));
+        assert_eq!(page[1].addr, gauges[1]);
+        assert_eq!(page[1].gauge_type, (false, 1.into()));
+        assert_eq!(page[1].metadata.name, String::new());
+
+        let page = gauges_page(
+            &env,
+            &gauge_controller,
+            owner,
+            2.into(),
+            2.into(),
+            blocktime,
+        );
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].addr, gauges[2]);
+        assert_eq!(page[0].metadata, metadata("wrapper", 3));
+
+        let page = gauges_page(
+            &env,
+            &gauge_controller,
+            owner,
+            3.into(),
+            2.into(),
+            blocktime,
+        );
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn test_gauge_controller_set_gauge_metadata() {
+        let (_, gauge_controller, owner, _, _, blocktime, _, _) = deploy();
+        let gauge = Key::Hash([10u8; 32]);
+        gauge_controller.add_type(owner, "type".to_string(), None, blocktime);
+        gauge_controller.add_gauge(owner, gauge, (false, 0.into()), None, blocktime);
+        gauge_controller.set_gauge_metadata(owner, gauge, metadata("reward only", 2), blocktime);
+        assert_eq!(
+            gauge_controller.gauge_metadata(gauge),
+            Some(metadata("reward only", 2))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_set_gauge_metadata_by_user() {
+        let (env, gauge_controller, owner, _, _, blocktime, _, _) = deploy();
+        let gauge = Key::Hash([10u8; 32]);
+        gauge_controller.add_type(owner, "type".to_string(), None, blocktime);
+        gauge_controller.add_gauge(owner, gauge, (false, 0.into()), None, blocktime);
+        gauge_controller.set_gauge_metadata(
+            env.next_user(),
+            gauge,
+            metadata("reward only", 2),
+            blocktime,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_set_gauge_metadata_invalid_kind() {
+        let (_, gauge_controller, owner, _, _, blocktime, _, _) = deploy();
+        gauge_controller.add_type(owner, "type".to_string(), None, blocktime);
+        gauge_controller.add_gauge_with_metadata(
+            owner,
+            Key::Hash([10u8; 32]),
+            (false, 0.into()),
+            None,
+            metadata("unknown", 4),
+            blocktime,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_set_gauge_metadata_name_too_long() {
+        let (_, gauge_controller, owner, _, _, blocktime, _, _) = deploy();
+        gauge_controller.add_type(owner, "type".to_string(), None, blocktime);
+        gauge_controller.add_gauge_with_metadata(
+            owner,
+            Key::Hash([10u8; 32]),
+            (false, 0.into()),
+            None,
+            metadata(&"a".repeat(65), 1),
+            blocktime,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gauge_controller_gauges_page_too_large() {
+        let (env, gauge_controller, owner, _, _, blocktime, _, _) = deploy();
+        gauges_page(
+            &env,
+            &gauge_controller,
+            owner,
+            0.into(),
+            51.into(),
+            blocktime,
+        );
+    }
+}
@@ -4,7 +4,7 @@
 #[macro_use]
 extern crate alloc;
 
-use alloc::{boxed::Box, collections::BTreeSet, format, string::String};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
@@ -16,7 +16,7 @@ use casper_types::{
 };
 use casperlabs_contract_utils::{ContractContext, OnChainContractStorage};
 use gauge_controller_crate::{
-    data::{Point, VotedSlope},
+    data::{GaugeInfo, GaugeMetadata, Point, VotedSlope},
     utils::*,
     GAUGECONLTROLLER,
 };
@@ -136,6 +136,20 @@ fn gauge_relative_weight_write() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// @notice Project the weight of gauge `addr` over the next `weeks` weeks,
+///         assuming nobody changes their votes
+/// @param addr Gauge address
+/// @param weeks Number of weeks to project, starting with the current week
+/// @return List of (week start, absolute weight, relative weight normalized to 1e9)
+
+#[no_mangle]
+fn projected_gauge_weights() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let weeks: U256 = runtime::get_named_arg("weeks");
+    let ret: Vec<(U256, U256, U256)> = Token::default().projected_gauge_weights(addr, weeks);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// @notice Change gauge type `type_id` weight to `weight`
 /// @param type_id Gauge type id
 /// @param weight New Gauge weight
@@ -273,6 +287,13 @@ fn points_weight() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 #[no_mangle]
+fn points_weight_cap() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let spender: U256 = runtime::get_named_arg("spender");
+    let ret: U256 = Token::default().points_weight_cap(owner, spender);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
 fn time_sum() {
     let owner: U256 = runtime::get_named_arg("owner");
     let ret: U256 = Token::default().time_sum(owner);
@@ -364,6 +385,48 @@ fn add_gauge() {
     Token::default().add_gauge(addr, tuple_to_i128(gauge_type), weight);
 }
 
+/// @notice Add gauge `addr` of type `gauge_type` together with its metadata record
+/// @param addr Gauge address
+/// @param gauge_type Gauge type
+/// @param weight Gauge weight
+/// @param metadata Name (at most 64 bytes), kind, LP token and reward tokens of the gauge
+#[no_mangle]
+fn add_gauge_with_metadata() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let gauge_type: (bool, U128) = runtime::get_named_arg("gauge_type");
+    let weight: Option<U256> = runtime::get_named_arg("weight");
+    let metadata: GaugeMetadata = runtime::get_named_arg("metadata");
+    Token::default().add_gauge_with_metadata(addr, tuple_to_i128(gauge_type), weight, metadata);
+}
+
+/// @notice Set the metadata record of gauge `addr`
+/// @param addr Gauge address
+/// @param metadata Name (at most 64 bytes), kind, LP token and reward tokens of the gauge
+#[no_mangle]
+fn set_gauge_metadata() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let metadata: GaugeMetadata = runtime::get_named_arg("metadata");
+    Token::default().set_gauge_metadata(addr, metadata);
+}
+
+#[no_mangle]
+fn gauge_metadata() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ret: GaugeMetadata = Token::default().gauge_metadata(addr);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Get a page of gauges with their type and metadata
+/// @param start Index of the first gauge
+/// @param count Maximum number of gauges to return
+#[no_mangle]
+fn gauges_page() {
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    let ret: Vec<GaugeInfo> = Token::default().gauges_page(start, count);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// @notice Allocate voting power for changing pool weights
 /// @param _gauge_addr Gauge which `msg.sender` votes for
 /// @param _user_weight Weight for a gauge in bps (units of 0.01%). Minimal is 0.01%. Ignored if 0
@@ -374,6 +437,88 @@ fn vote_for_gauge_weights() {
     Token::default().vote_for_gauge_weights(gauge_addr, user_weight);
 }
 
+/// @notice Allocate voting power for several gauges in one call
+/// @param votes List of (gauge address, weight in bps) pairs
+#[no_mangle]
+fn vote_for_many_gauge_weights() {
+    let votes: Vec<(Key, U256)> = runtime::get_named_arg("votes");
+    Token::default().vote_for_many_gauge_weights(votes);
+}
+
+/// @notice Allow `delegate` to vote for gauge weights on behalf of the caller
+/// @param delegate Address voting with the caller's power
+#[no_mangle]
+fn set_vote_delegate() {
+    let delegate: Key = runtime::get_named_arg("delegate");
+    Token::default().set_vote_delegate(delegate);
+}
+
+/// @notice Revoke the vote delegate of the caller
+#[no_mangle]
+fn revoke_vote_delegate() {
+    Token::default().revoke_vote_delegate();
+}
+
+/// @notice Allocate voting power of `user` as its vote delegate
+/// @param user Delegator whose voting power is used
+/// @param gauge_addr Gauge which `user` votes for
+/// @param user_weight Weight for a gauge in bps (units of 0.01%)
+#[no_mangle]
+fn vote_for_gauge_weights_for() {
+    let user: Key = runtime::get_named_arg("user");
+    let gauge_addr: Key = runtime::get_named_arg("gauge_addr");
+    let user_weight: U256 = runtime::get_named_arg("user_weight");
+    Token::default().vote_for_gauge_weights_for(user, gauge_addr, user_weight);
+}
+
+#[no_mangle]
+fn vote_delegates() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let ret: Key = Token::default().vote_delegates(owner);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Set the maximum relative weight of gauge `addr` from next week on
+/// @param addr Gauge address
+/// @param cap Maximum relative weight normalized to 1e9, 0 removes the cap
+#[no_mangle]
+fn set_gauge_weight_cap() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let cap: U256 = runtime::get_named_arg("cap");
+    Token::default().set_gauge_weight_cap(addr, cap);
+}
+
+/// @notice Turn per-gauge weight caps on or off from the current week on
+/// @param enabled Whether relative weights apply the caps
+#[no_mangle]
+fn set_weight_caps_enabled() {
+    let enabled: bool = runtime::get_named_arg("enabled");
+    Token::default().set_weight_caps_enabled(enabled);
+}
+
+/// @notice Kill gauge `addr`, removing its weight and stopping new votes
+/// @param addr Gauge address
+#[no_mangle]
+fn kill_gauge() {
+    let addr: Key = runtime::get_named_arg("addr");
+    Token::default().kill_gauge(addr);
+}
+
+/// @notice Return the voting power allocated to a killed gauge
+/// @param gauge_addr Killed gauge address
+#[no_mangle]
+fn reclaim_gauge_power() {
+    let gauge_addr: Key = runtime::get_named_arg("gauge_addr");
+    Token::default().reclaim_gauge_power(gauge_addr);
+}
+
+#[no_mangle]
+fn gauge_is_killed() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ret: bool = Token::default().gauge_is_killed(addr);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn call() {
     // Contract name must be same for all new versions of the contracts
@@ -733,6 +878,16 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "projected_gauge_weights",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("weeks", U256::cl_type()),
+        ],
+        Vec::<(U256, U256, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "change_type_weight",
         vec![
@@ -824,5 +979,134 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "vote_for_many_gauge_weights",
+        vec![Parameter::new("votes", Vec::<(Key, U256)>::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_vote_delegate",
+        vec![Parameter::new("delegate", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "revoke_vote_delegate",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "vote_for_gauge_weights_for",
+        vec![
+            Parameter::new("user", Key::cl_type()),
+            Parameter::new("gauge_addr", Key::cl_type()),
+            Parameter::new("user_weight", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "vote_delegates",
+        vec![Parameter::new("owner", Key::cl_type())],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_gauge_weight_cap",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("cap", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_weight_caps_enabled",
+        vec![Parameter::new("enabled", bool::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "points_weight_cap",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("spender", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "kill_gauge",
+        vec![Parameter::new("addr", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reclaim_gauge_power",
+        vec![Parameter::new("gauge_addr", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "gauge_is_killed",
+        vec![Parameter::new("addr", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_gauge_with_metadata",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new(
+                "gauge_type",
+                CLType::Tuple2([Box::new(CLType::Bool), Box::new(CLType::U128)]),
+            ),
+            Parameter::new("weight", CLType::Option(Box::new(U256::cl_type()))),
+            Parameter::new("metadata", GaugeMetadata::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_gauge_metadata",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("metadata", GaugeMetadata::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "gauge_metadata",
+        vec![Parameter::new("addr", Key::cl_type())],
+        GaugeMetadata::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "gauges_page",
+        vec![
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
+        Vec::<GaugeInfo>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
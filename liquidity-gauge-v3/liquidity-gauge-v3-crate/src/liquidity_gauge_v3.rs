@@ -132,13 +132,13 @@ pub trait LIQUIDITYTGAUGEV3<Storage: ContractStorage>:
 
     fn _update_liquidity_limit(&self, addr: Key, l: U256, _supply: U256) {
         let voting_escrow: Key = data::get_voting_escrow();
+        // Boosts delegated through the VotingEscrow are taken into account
         let voting_balance: U256 = runtime::call_versioned_contract(
             voting_escrow.into_hash().unwrap_or_revert().into(),
             None,
-            "balance_of",
+            "adjusted_balance_of",
             runtime_args! {
-                "addr" => addr,
-                "t" => None::<U256>
+                "addr" => addr
             },
         );
         let voting_total: U256 = runtime::call_versioned_contract(
@@ -1,7 +1,10 @@
+use alloc::{format, string::String};
 use casper_contract::{contract_api::runtime::get_call_stack, unwrap_or_revert::UnwrapOrRevert};
 use casper_types::{system::CallStackElement, ContractPackageHash, Key, U256};
 use casperlabs_contract_utils::{get_key, set_key, Dict};
-use common::keys::*;
+use common::{keys::*, utils::*};
+
+pub const MAX_CLAIMABLE_PAGE: U256 = U256([50, 0, 0, 0]);
 
 pub struct Minted {
     dict: Dict,
@@ -51,6 +54,131 @@ impl AllowedToMintFor {
     }
 }
 
+fn minted_for_token_key(owner: &Key, gauge: &Key, token: &Key) -> String {
+    hash(format!(
+        "{}_{}_{}_{}",
+        MINTED_FOR_TOKEN_DICT,
+        owner.to_formatted_string(),
+        gauge.to_formatted_string(),
+        token.to_formatted_string()
+    ))
+}
+
+pub struct MintedForToken {
+    dict: Dict,
+}
+
+impl MintedForToken {
+    pub fn instance() -> MintedForToken {
+        MintedForToken {
+            dict: Dict::instance(MINTED_FOR_TOKEN_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(MINTED_FOR_TOKEN_DICT)
+    }
+
+    pub fn get(&self, owner: &Key, gauge: &Key, token: &Key) -> U256 {
+        self.dict
+            .get(&minted_for_token_key(owner, gauge, token))
+            .unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, gauge: &Key, token: &Key, value: U256) {
+        self.dict
+            .set(&minted_for_token_key(owner, gauge, token), value);
+    }
+}
+
+pub struct EmissionTokens {
+    dict: Dict,
+}
+
+impl EmissionTokens {
+    pub fn instance() -> EmissionTokens {
+        EmissionTokens {
+            dict: Dict::instance(EMISSION_TOKENS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(EMISSION_TOKENS_DICT)
+    }
+
+    pub fn get(&self, token: &Key) -> bool {
+        self.dict.get(&key_to_str(token)).unwrap_or_default()
+    }
+
+    pub fn set(&self, token: &Key, value: bool) {
+        self.dict.set(&key_to_str(token), value);
+    }
+}
+
+pub struct GaugeTokens {
+    dict: Dict,
+}
+
+impl GaugeTokens {
+    pub fn instance() -> GaugeTokens {
+        GaugeTokens {
+            dict: Dict::instance(GAUGE_TOKENS_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(GAUGE_TOKENS_DICT)
+    }
+
+    pub fn get(&self, gauge: &Key) -> Option<Key> {
+        self.dict.get(&key_to_str(gauge))
+    }
+
+    pub fn set(&self, gauge: &Key, value: Key) {
+        self.dict.set(&key_to_str(gauge), value);
+    }
+}
+
+pub struct Nonces {
+    dict: Dict,
+}
+
+impl Nonces {
+    pub fn instance() -> Nonces {
+        Nonces {
+            dict: Dict::instance(NONCES_DICT),
+        }
+    }
+
+    pub fn init() {
+        Dict::init(NONCES_DICT)
+    }
+
+    pub fn get(&self, owner: &Key) -> U256 {
+        self.dict.get(&key_to_str(owner)).unwrap_or_default()
+    }
+
+    pub fn set(&self, owner: &Key, value: U256) {
+        self.dict.set(&key_to_str(owner), value);
+    }
+}
+
+pub fn admin() -> Key {
+    get_key(ADMIN).unwrap_or_else(zero_address)
+}
+
+pub fn set_admin(admin: Key) {
+    set_key(ADMIN, admin);
+}
+
+pub fn future_admin() -> Key {
+    get_key(FUTURE_ADMIN).unwrap_or_else(zero_address)
+}
+
+pub fn set_future_admin(future_admin: Key) {
+    set_key(FUTURE_ADMIN, future_admin);
+}
+
 pub fn token() -> Key {
     get_key(TOKEN).unwrap_or_revert()
 }
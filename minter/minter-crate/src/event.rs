@@ -4,6 +4,20 @@ pub enum MINTEREvent {
     Minted {
         recipient: Key,
         gauge: Key,
+        token: Key,
         minted: U256,
     },
+    MintAndLock {
+        recipient: Key,
+        minted: U256,
+        lock_end: U256,
+        created: bool,
+    },
+    AddEmissionToken {
+        token: Key,
+    },
+    SetGaugeToken {
+        gauge: Key,
+        token: Key,
+    },
 }
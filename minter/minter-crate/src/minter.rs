@@ -1,19 +1,50 @@
 use crate::alloc::string::ToString;
-use crate::data::{self, get_package_hash, AllowedToMintFor, Minted};
+use crate::data::{
+    self, get_package_hash, AllowedToMintFor, EmissionTokens, GaugeTokens, Minted, MintedForToken,
+    Nonces, MAX_CLAIMABLE_PAGE,
+};
 use alloc::collections::BTreeMap;
 use alloc::{string::String, vec::Vec};
-use casper_contract::contract_api::runtime;
 use casper_contract::contract_api::storage;
-use casper_types::{runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, U128, U256};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, runtime_args, ApiError, ContractPackageHash, Key,
+    PublicKey, RuntimeArgs, U128, U256,
+};
 use casperlabs_contract_utils::{ContractContext, ContractStorage};
 use common::{errors::*, utils::*};
+use core::convert::TryInto;
+use crv20::Address;
+use cryptoxide::ed25519;
 
 pub enum MINTEREvent {
+    /// `minted` is the total amount of `token` minted so far for (`recipient`, `gauge`),
+    /// not the amount of this mint
     Minted {
         recipient: Key,
         gauge: Key,
+        token: Key,
         minted: U256,
     },
+    MintAndLock {
+        recipient: Key,
+        minted: U256,
+        lock_end: U256,
+        created: bool,
+    },
+    AddEmissionToken {
+        token: Key,
+    },
+    SetGaugeToken {
+        gauge: Key,
+        token: Key,
+    },
+    CommitOwnership {
+        admin: Key,
+    },
+    ApplyOwnership {
+        admin: Key,
+    },
 }
 
 impl MINTEREvent {
@@ -22,8 +53,19 @@ impl MINTEREvent {
             MINTEREvent::Minted {
                 recipient: _,
                 gauge: _,
+                token: _,
                 minted: _,
             } => "minted",
+            MINTEREvent::MintAndLock {
+                recipient: _,
+                minted: _,
+                lock_end: _,
+                created: _,
+            } => "MintAndLock",
+            MINTEREvent::AddEmissionToken { token: _ } => "AddEmissionToken",
+            MINTEREvent::SetGaugeToken { gauge: _, token: _ } => "SetGaugeToken",
+            MINTEREvent::CommitOwnership { admin: _ } => "CommitOwnership",
+            MINTEREvent::ApplyOwnership { admin: _ } => "ApplyOwnership",
         }
         .to_string()
     }
@@ -42,11 +84,21 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
         data::set_hash(contract_hash);
         data::set_package_hash(package_hash);
         data::set_lock(0);
+        data::set_admin(self.get_caller());
         Minted::init();
         AllowedToMintFor::init();
+        MintedForToken::init();
+        EmissionTokens::init();
+        GaugeTokens::init();
+        Nonces::init();
     }
 
     fn _mint_for(&mut self, gauge_addr: Key, _for: Key) {
+        self._mint_to(gauge_addr, _for, _for);
+    }
+
+    /// @dev Mint what `gauge_addr` owes `_for` to `recipient` and return the minted amount
+    fn _mint_to(&mut self, gauge_addr: Key, _for: Key, recipient: Key) -> U256 {
         let controller: Key = self.controller();
         let controller_hash_add_array = match controller {
             Key::Hash(package) => package,
@@ -65,6 +117,16 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
             //dev: gauge is not added
             runtime::revert(Error::MinterIsNotAdded);
         }
+        let is_killed: bool = runtime::call_versioned_contract(
+            controller_package_hash,
+            None,
+            "gauge_is_killed",
+            runtime_args! {"addr" => gauge_addr},
+        );
+        if is_killed {
+            //dev: gauge is killed
+            runtime::revert(Error::MinterGaugeIsKilled);
+        }
 
         let gauge_addr_hash_add_array = match gauge_addr {
             Key::Hash(package) => package,
@@ -84,9 +146,11 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
             runtime_args! {"owner" => _for},
         );
         let minted = self.minted(_for, gauge_addr);
-        let to_mint: U256 = total_mint - minted;
+        let to_mint: U256 = total_mint
+            .checked_sub(minted)
+            .unwrap_or_revert_with(Error::MinterUnderFlow1);
         if to_mint != U256::from(0) {
-            let token = self.token();
+            let token = self.gauge_token(gauge_addr);
             let token_hash_add_array = match token {
                 Key::Hash(package) => package,
                 _ => runtime::revert(ApiError::UnexpectedKeyVariant),
@@ -96,15 +160,22 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
                 token_package_hash,
                 None,
                 "mint",
-                runtime_args! {"to" => _for,"amount" => to_mint},
+                runtime_args! {"to" => recipient,"amount" => to_mint},
             );
             Minted::instance().set(&_for, &gauge_addr, total_mint);
+            let minted_for_token: U256 = self
+                .minted_for_token(_for, gauge_addr, token)
+                .checked_add(to_mint)
+                .unwrap_or_revert_with(Error::MinterOverFlow1);
+            MintedForToken::instance().set(&_for, &gauge_addr, &token, minted_for_token);
             self.emit(&MINTEREvent::Minted {
                 recipient: _for,
                 gauge: gauge_addr,
-                minted: total_mint,
+                token,
+                minted: minted_for_token,
             });
         }
+        to_mint
     }
     fn mint(&mut self, gauge_addr: Key) {
         let lock = data::get_lock();
@@ -131,6 +202,134 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
         }
         data::set_lock(0);
     }
+    /// @notice Mint everything which belongs to the caller across multiple gauges
+    ///         and lock it in `VotingEscrow`
+    /// @dev The minted amount is added to the caller's lock with `deposit_for`, which
+    ///      needs the smart wallet checker of `VotingEscrow` to approve the minter.
+    ///      Without a lock, or once it was withdrawn, a new one ending at `unlock_time` is
+    ///      created with `create_lock_for`, which needs the minter to be an approved depositor.
+    ///      Both only touch the primary lock (id 0) of the caller. An expired lock has to be
+    ///      withdrawn first, and nothing can be locked after `unlock_all`
+    /// @param gauge_addrs List of `LiquidityGauge` addresses minting `token`
+    /// @param unlock_time Epoch time when a new lock ends, ignored for an existing lock
+    fn mint_and_lock(&mut self, gauge_addrs: Vec<Key>, unlock_time: Option<U256>) {
+        let lock = data::get_lock();
+        if lock != 0 {
+            //Minter: Locked
+            runtime::revert(Error::MinterLocked5);
+        }
+        data::set_lock(1);
+        let caller: Key = self.get_caller();
+        let voting_escrow: Key = runtime::call_versioned_contract(
+            self.controller().into_hash().unwrap_or_revert().into(),
+            None,
+            "voting_escrow",
+            runtime_args! {},
+        );
+        let voting_escrow_package_hash: ContractPackageHash =
+            voting_escrow.into_hash().unwrap_or_revert().into();
+        let unlocked: bool = runtime::call_versioned_contract(
+            voting_escrow_package_hash,
+            None,
+            "unlocked",
+            runtime_args! {},
+        );
+        if unlocked {
+            //Minter: Voting Escrow Unlocked
+            runtime::revert(Error::MinterVotingEscrowUnlocked);
+        }
+        // `LockedBalance` of the caller: signed amount and end
+        let (amount, _): ((bool, U128), U256) = runtime::call_versioned_contract(
+            voting_escrow_package_hash,
+            None,
+            "locked",
+            runtime_args! {"addr" => caller},
+        );
+        // A new lock is funded by the minter, an existing one by the caller
+        let created: bool = amount.1 == U128::from(0);
+        if !created {
+            // The end of a perpetual lock is only known to `locked_end`
+            let locked_end: U256 = runtime::call_versioned_contract(
+                voting_escrow_package_hash,
+                None,
+                "locked_end",
+                runtime_args! {"addr" => caller},
+            );
+            if locked_end <= U256::from(u64::from(runtime::get_blocktime())) {
+                //Minter: Lock Expired
+                runtime::revert(Error::MinterLockExpired);
+            }
+        }
+        let recipient: Key = if created {
+            Key::from(data::get_package_hash())
+        } else {
+            caller
+        };
+        let token: Key = self.token();
+        let mut minted: U256 = U256::from(0);
+        for item in &gauge_addrs {
+            if *item == zero_address() || *item == account_zero_address() {
+                break;
+            }
+            if self.gauge_token(*item) != token {
+                //Minter: Gauge Token Can Not Be Locked
+                runtime::revert(Error::MinterCannotLockGaugeToken);
+            }
+            minted = minted
+                .checked_add(self._mint_to(*item, caller, recipient))
+                .unwrap_or_revert_with(Error::MinterOverFlow4);
+        }
+        if minted == U256::from(0) {
+            //Minter: Nothing To Lock
+            runtime::revert(Error::MinterNothingToLock);
+        }
+        if created {
+            let unlock_time: U256 =
+                unlock_time.unwrap_or_revert_with(Error::MinterUnlockTimeRequired);
+            let () = runtime::call_versioned_contract(
+                token.into_hash().unwrap_or_revert().into(),
+                None,
+                "approve",
+                runtime_args! {
+                    "spender" => Address::from(voting_escrow),
+                    "amount" => minted
+                },
+            );
+            let () = runtime::call_versioned_contract(
+                voting_escrow_package_hash,
+                None,
+                "create_lock_for",
+                runtime_args! {
+                    "addr" => caller,
+                    "value" => minted,
+                    "unlock_time" => unlock_time
+                },
+            );
+        } else {
+            let () = runtime::call_versioned_contract(
+                voting_escrow_package_hash,
+                None,
+                "deposit_for",
+                runtime_args! {
+                    "addr" => caller,
+                    "value" => minted
+                },
+            );
+        }
+        let lock_end: U256 = runtime::call_versioned_contract(
+            voting_escrow_package_hash,
+            None,
+            "locked_end",
+            runtime_args! {"addr" => caller},
+        );
+        self.emit(&MINTEREvent::MintAndLock {
+            recipient: caller,
+            minted,
+            lock_end,
+            created,
+        });
+        data::set_lock(0);
+    }
     fn mint_for(&mut self, gauge_addr: Key, _for: Key) {
         let lock = data::get_lock();
         if lock != 0 {
@@ -139,23 +338,251 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
         }
         data::set_lock(1);
         let is_allowed: bool = self.allowed_to_mint_for(self.get_caller(), _for);
-        if is_allowed {
-            self._mint_for(gauge_addr, _for)
+        if !is_allowed {
+            //Minter: Not Allowed To Mint For
+            runtime::revert(Error::MinterNotAllowedToMintFor);
         }
+        self._mint_for(gauge_addr, _for);
         data::set_lock(0);
     }
 
+    /// @notice Mint tokens for `_for` on behalf of a relayer
+    /// @dev `_for` approves the mint by signing the digest returned by `mint_for_digest`
+    ///      with its ed25519 account key. Each signature can be used once.
+    ///      Only ed25519 account keys are supported, accounts with secp256k1 keys
+    ///      have to use `toggle_approve_mint` and `mint_for` instead
+    /// @param gauge_addr `LiquidityGauge` address to get mintable amount from
+    /// @param _for Address to mint to, must be the account of `public_key`
+    /// @param public_key Ed25519 account key of `_for`
+    /// @param deadline Time in milliseconds after which the signature is rejected
+    /// @param signature ed25519 signature of the digest
+    fn mint_for_signed(
+        &mut self,
+        gauge_addr: Key,
+        _for: Key,
+        public_key: PublicKey,
+        deadline: U256,
+        signature: Vec<u8>,
+    ) {
+        let lock = data::get_lock();
+        if lock != 0 {
+            //Minter: Locked
+            runtime::revert(Error::MinterLocked4);
+        }
+        data::set_lock(1);
+        if U256::from(u64::from(runtime::get_blocktime())) > deadline {
+            //Minter: Signature Expired
+            runtime::revert(Error::MinterSignatureExpired);
+        }
+        if Key::Account(AccountHash::from(&public_key)) != _for {
+            //Minter: Invalid Signer
+            runtime::revert(Error::MinterInvalidSigner);
+        }
+        let public_key: [u8; 32] = match public_key {
+            PublicKey::Ed25519(_) => Vec::<u8>::from(&public_key)
+                .try_into()
+                .unwrap_or_revert_with(Error::MinterUnsupportedPublicKey),
+            _ => runtime::revert(Error::MinterUnsupportedPublicKey),
+        };
+        let signature: [u8; 64] = signature
+            .try_into()
+            .unwrap_or_revert_with(Error::MinterInvalidSignature);
+        let nonce: U256 = self.nonces(_for);
+        let digest: [u8; 32] = self.mint_for_digest(gauge_addr, _for, nonce, deadline);
+        if !ed25519::verify(&digest, &public_key, &signature) {
+            //Minter: Invalid Signature
+            runtime::revert(Error::MinterInvalidSignature);
+        }
+        Nonces::instance().set(
+            &_for,
+            nonce
+                .checked_add(U256::from(1))
+                .unwrap_or_revert_with(Error::MinterOverFlow3),
+        );
+        self._mint_for(gauge_addr, _for);
+        data::set_lock(0);
+    }
+
+    /// @notice Get the digest `_for` signs to approve `mint_for_signed`
+    /// @return blake2b hash of (minter, gauge_addr, _for, nonce, deadline)
+    fn mint_for_digest(
+        &mut self,
+        gauge_addr: Key,
+        _for: Key,
+        nonce: U256,
+        deadline: U256,
+    ) -> [u8; 32] {
+        let mut message: Vec<u8> = Vec::new();
+        message.extend(
+            Key::from(data::get_package_hash())
+                .to_bytes()
+                .unwrap_or_revert(),
+        );
+        message.extend(gauge_addr.to_bytes().unwrap_or_revert());
+        message.extend(_for.to_bytes().unwrap_or_revert());
+        message.extend(nonce.to_bytes().unwrap_or_revert());
+        message.extend(deadline.to_bytes().unwrap_or_revert());
+        runtime::blake2b(message)
+    }
+
+    /// @notice Get the amount `addr` can mint from the gauges `start` to `start + count`
+    ///         in `GaugeController`
+    /// @dev Checkpoints `addr` in each gauge first, so the amounts are what `mint`
+    ///      would mint now. Killed gauges are skipped
+    /// @param addr User address
+    /// @param start Index of the first gauge
+    /// @param count Number of gauges, at most `MAX_CLAIMABLE_PAGE`
+    /// @return List of (gauge address, claimable amount) pairs
+    fn claimable_by_gauge(&mut self, addr: Key, start: U256, count: U256) -> Vec<(Key, U256)> {
+        if count > MAX_CLAIMABLE_PAGE {
+            runtime::revert(Error::MinterClaimablePageTooLarge);
+        }
+        let controller: Key = self.controller();
+        let controller_hash_add_array = match controller {
+            Key::Hash(package) => package,
+            _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+        };
+        let controller_package_hash = ContractPackageHash::new(controller_hash_add_array);
+        let n_gauges: (bool, U128) = runtime::call_versioned_contract(
+            controller_package_hash,
+            None,
+            "n_gauges",
+            runtime_args! {},
+        );
+        let n_gauges: U256 = U256::from(n_gauges.1.as_u128());
+        let mut end: U256 = start
+            .checked_add(count)
+            .unwrap_or_revert_with(Error::MinterOverFlow5);
+        if end > n_gauges {
+            end = n_gauges;
+        }
+        let mut claimable: Vec<(Key, U256)> = Vec::new();
+        let mut i: U256 = start;
+        while i < end {
+            let gauge_addr: Key = runtime::call_versioned_contract(
+                controller_package_hash,
+                None,
+                "gauges",
+                runtime_args! {"owner" => i},
+            );
+            i = i
+                .checked_add(U256::from(1))
+                .unwrap_or_revert_with(Error::MinterOverFlow2);
+            let is_killed: bool = runtime::call_versioned_contract(
+                controller_package_hash,
+                None,
+                "gauge_is_killed",
+                runtime_args! {"addr" => gauge_addr},
+            );
+            if is_killed {
+                continue;
+            }
+            let gauge_package_hash: ContractPackageHash =
+                gauge_addr.into_hash().unwrap_or_revert().into();
+            let _ret: bool = runtime::call_versioned_contract(
+                gauge_package_hash,
+                None,
+                "user_checkpoint",
+                runtime_args! {"addr" => addr},
+            );
+            let total_mint: U256 = runtime::call_versioned_contract(
+                gauge_package_hash,
+                None,
+                "integrate_fraction",
+                runtime_args! {"owner" => addr},
+            );
+            claimable.push((
+                gauge_addr,
+                total_mint
+                    .checked_sub(self.minted(addr, gauge_addr))
+                    .unwrap_or_revert_with(Error::MinterUnderFlow2),
+            ));
+        }
+        claimable
+    }
+
     fn toggle_approve_mint(&mut self, minting_user: Key) {
         let is_allowed = self.allowed_to_mint_for(minting_user, self.get_caller());
         AllowedToMintFor::instance().set(&minting_user, &self.get_caller(), !is_allowed);
     }
 
+    /// @notice Register `token` as an emission token that gauges can be routed to
+    /// @dev The minter has to be allowed to mint `token`
+    /// @param token Emission token address
+    fn add_emission_token(&mut self, token: Key) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::MinterNotAdmin1);
+        }
+        if token == zero_address() || token == account_zero_address() {
+            runtime::revert(Error::MinterTokenAddressZero);
+        }
+        if self.is_emission_token(token) {
+            runtime::revert(Error::MinterEmissionTokenAlreadyAdded);
+        }
+        EmissionTokens::instance().set(&token, true);
+        self.emit(&MINTEREvent::AddEmissionToken { token });
+    }
+
+    /// @notice Route everything minted for `gauge` to emission token `token`
+    /// @param gauge `LiquidityGauge` address
+    /// @param token Registered emission token address
+    fn set_gauge_token(&mut self, gauge: Key, token: Key) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::MinterNotAdmin2);
+        }
+        if !self.is_emission_token(token) {
+            runtime::revert(Error::MinterUnknownEmissionToken);
+        }
+        GaugeTokens::instance().set(&gauge, token);
+        self.emit(&MINTEREvent::SetGaugeToken { gauge, token });
+    }
+
+    /// @notice Transfer ownership of the Minter to `addr`
+    /// @param addr Address to have ownership transferred to
+    fn commit_transfer_ownership(&mut self, addr: Key) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::MinterNotAdmin3);
+        }
+        data::set_future_admin(addr);
+        self.emit(&MINTEREvent::CommitOwnership { admin: addr });
+    }
+
+    /// @notice Apply pending ownership transfer
+    fn apply_transfer_ownership(&mut self) {
+        if self.get_caller() != self.admin() {
+            runtime::revert(Error::MinterNotAdmin4);
+        }
+        let admin: Key = data::future_admin();
+        if admin == zero_address() || admin == account_zero_address() {
+            runtime::revert(Error::MinterAdminNotSet);
+        }
+        data::set_admin(admin);
+        self.emit(&MINTEREvent::ApplyOwnership { admin });
+    }
+
+    /// @notice Get the emission token minted for `gauge`, `token` unless routed elsewhere
+    fn gauge_token(&mut self, gauge: Key) -> Key {
+        GaugeTokens::instance()
+            .get(&gauge)
+            .unwrap_or_else(data::token)
+    }
+
+    fn is_emission_token(&mut self, token: Key) -> bool {
+        token == self.token() || EmissionTokens::instance().get(&token)
+    }
+
     fn allowed_to_mint_for(&mut self, owner: Key, spender: Key) -> bool {
         AllowedToMintFor::instance().get(&owner, &spender)
     }
     fn minted(&mut self, owner: Key, spender: Key) -> U256 {
         Minted::instance().get(&owner, &spender)
     }
+    fn nonces(&mut self, owner: Key) -> U256 {
+        Nonces::instance().get(&owner)
+    }
+    fn minted_for_token(&mut self, owner: Key, gauge: Key, token: Key) -> U256 {
+        MintedForToken::instance().get(&owner, &gauge, &token)
+    }
 
     fn token(&mut self) -> Key {
         data::token()
@@ -163,12 +590,19 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
     fn controller(&mut self) -> Key {
         data::controller()
     }
+    fn admin(&mut self) -> Key {
+        data::admin()
+    }
+    fn future_admin(&mut self) -> Key {
+        data::future_admin()
+    }
 
     fn emit(&mut self, minter_event: &MINTEREvent) {
         match minter_event {
             MINTEREvent::Minted {
                 recipient,
                 gauge,
+                token,
                 minted,
             } => {
                 let mut event = BTreeMap::new();
@@ -176,7 +610,45 @@ pub trait MINTER<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("event_type", minter_event.type_name());
                 event.insert("recipient", recipient.to_string());
                 event.insert("gauge", gauge.to_string());
+                event.insert("token", token.to_string());
+                event.insert("minted", minted.to_string());
+                storage::new_uref(event);
+            }
+            MINTEREvent::MintAndLock {
+                recipient,
+                minted,
+                lock_end,
+                created,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", minter_event.type_name());
+                event.insert("recipient", recipient.to_string());
                 event.insert("minted", minted.to_string());
+                event.insert("lock_end", lock_end.to_string());
+                event.insert("created", created.to_string());
+                storage::new_uref(event);
+            }
+            MINTEREvent::AddEmissionToken { token } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", minter_event.type_name());
+                event.insert("token", token.to_string());
+                storage::new_uref(event);
+            }
+            MINTEREvent::SetGaugeToken { gauge, token } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", minter_event.type_name());
+                event.insert("gauge", gauge.to_string());
+                event.insert("token", token.to_string());
+                storage::new_uref(event);
+            }
+            MINTEREvent::CommitOwnership { admin } | MINTEREvent::ApplyOwnership { admin } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", minter_event.type_name());
+                event.insert("admin", admin.to_string());
                 storage::new_uref(event);
             }
         };
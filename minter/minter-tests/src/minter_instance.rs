@@ -4,9 +4,10 @@ use blake2::{
 };
 use casper_types::{
     account::AccountHash, bytesrepr::ToBytes, runtime_args, CLTyped, ContractPackageHash, Key,
-    RuntimeArgs, U128, U256,
+    PublicKey, RuntimeArgs, U128, U256,
 };
 use casperlabs_test_env::{TestContract, TestEnv};
+use common::utils::hash;
 use std::collections::BTreeMap;
 use std::time::SystemTime;
 
@@ -184,6 +185,23 @@ impl MINTERInstance {
             block_time,
         );
     }
+    pub fn mint_and_lock(
+        &self,
+        sender: AccountHash,
+        gauge_addrs: Vec<Key>,
+        unlock_time: Option<U256>,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint_and_lock",
+            runtime_args! {
+                "gauge_addrs" => gauge_addrs,
+                "unlock_time" => unlock_time,
+            },
+            block_time,
+        );
+    }
     pub fn mint_for<T: Into<Key>>(
         &self,
         sender: AccountHash,
@@ -201,6 +219,29 @@ impl MINTERInstance {
             block_time,
         );
     }
+    pub fn mint_for_signed<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        gauge_addr: T,
+        _for: T,
+        public_key: PublicKey,
+        deadline: U256,
+        signature: Vec<u8>,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "mint_for_signed",
+            runtime_args! {
+                "gauge_addr" => gauge_addr.into(),
+                "for" => _for.into(),
+                "public_key" => public_key,
+                "deadline" => deadline,
+                "signature" => signature,
+            },
+            block_time,
+        );
+    }
     pub fn toggle_approve_mint<T: Into<Key>>(
         &self,
         sender: AccountHash,
@@ -216,7 +257,71 @@ impl MINTERInstance {
             block_time,
         );
     }
+    pub fn add_emission_token<T: Into<Key>>(&self, sender: AccountHash, token: T, block_time: u64) {
+        self.0.call_contract(
+            sender,
+            "add_emission_token",
+            runtime_args! {
+                "token" => token.into(),
+            },
+            block_time,
+        );
+    }
+    pub fn set_gauge_token<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        gauge: T,
+        token: T,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "set_gauge_token",
+            runtime_args! {
+                "gauge" => gauge.into(),
+                "token" => token.into(),
+            },
+            block_time,
+        );
+    }
 
+    pub fn commit_transfer_ownership<T: Into<Key>>(
+        &self,
+        sender: AccountHash,
+        addr: T,
+        block_time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "commit_transfer_ownership",
+            runtime_args! {
+                "addr" => addr.into(),
+            },
+            block_time,
+        );
+    }
+    pub fn apply_transfer_ownership(&self, sender: AccountHash, block_time: u64) {
+        self.0.call_contract(
+            sender,
+            "apply_transfer_ownership",
+            runtime_args! {},
+            block_time,
+        );
+    }
+    pub fn minted_for_token<T: Into<Key>>(&self, owner: T, gauge: T, token: T) -> U256 {
+        self.0
+            .query_dictionary(
+                "minted_for_token",
+                hash(format!(
+                    "{}_{}_{}_{}",
+                    "minted_for_token",
+                    owner.into().to_formatted_string(),
+                    gauge.into().to_formatted_string(),
+                    token.into().to_formatted_string()
+                )),
+            )
+            .unwrap_or_default()
+    }
     pub fn minted<T: Into<Key>>(&self, owner: T, spender: T) -> U256 {
         let owner: Key = owner.into();
         let spender: Key = spender.into();
@@ -231,6 +336,20 @@ impl MINTERInstance {
             .query_dictionary("allowed_to_mint_for", keys_to_str(&owner, &spender))
             .unwrap_or_default()
     }
+    pub fn nonces<T: Into<Key>>(&self, owner: T) -> U256 {
+        self.0
+            .query_dictionary("nonces", key_to_str(&owner.into()))
+            .unwrap_or_default()
+    }
+    pub fn gauge_tokens<T: Into<Key>>(&self, gauge: T) -> Option<Key> {
+        self.0
+            .query_dictionary("gauge_tokens", key_to_str(&gauge.into()))
+    }
+    pub fn emission_tokens<T: Into<Key>>(&self, token: T) -> bool {
+        self.0
+            .query_dictionary("emission_tokens", key_to_str(&token.into()))
+            .unwrap_or_default()
+    }
 
     pub fn token(&self) -> Key {
         self.0.query_named_key(String::from("token"))
@@ -238,6 +357,12 @@ impl MINTERInstance {
     pub fn controller(&self) -> Key {
         self.0.query_named_key(String::from("controller"))
     }
+    pub fn admin(&self) -> Key {
+        self.0.query_named_key(String::from("admin"))
+    }
+    pub fn future_admin(&self) -> Key {
+        self.0.query_named_key(String::from("future_admin"))
+    }
     pub fn reward_count(&self) -> U256 {
         self.0.query_named_key(String::from("reward_count"))
     }
@@ -283,6 +408,25 @@ pub fn add_gauge<T: Into<Key>>(
         block_time,
     );
 }
+/// Digest signed by `_for` to approve `mint_for_signed`
+pub fn mint_for_digest(
+    minter: Key,
+    gauge_addr: Key,
+    _for: Key,
+    nonce: U256,
+    deadline: U256,
+) -> [u8; 32] {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.update(minter.to_bytes().unwrap());
+    hasher.update(gauge_addr.to_bytes().unwrap());
+    hasher.update(_for.to_bytes().unwrap());
+    hasher.update(nonce.to_bytes().unwrap());
+    hasher.update(deadline.to_bytes().unwrap());
+    let mut ret = [0u8; 32];
+    hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
+    ret
+}
+
 pub fn keys_to_str(key_a: &Key, key_b: &Key) -> String {
     let mut hasher = VarBlake2b::new(32).unwrap();
     hasher.update(key_a.to_bytes().unwrap());
@@ -1,13 +1,17 @@
-use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U128, U256};
+use casper_types::{account::AccountHash, runtime_args, Key, PublicKey, RuntimeArgs, U128, U256};
 use casperlabs_test_env::{TestContract, TestEnv};
+use common::{keys::*, utils::hash};
+use cryptoxide::ed25519;
 
-use crate::minter_instance::{add_gauge, MINTERInstance};
+use crate::minter_instance::{add_gauge, mint_for_digest, MINTERInstance};
 
 const NAME: &str = "MINTER";
 const TOKEN_NAME: &str = "ERC20";
 const TOKEN_SYMBOL: &str = "ERC";
 const DECIMALS: u8 = 8;
 const INIT_TOTAL_SUPPLY: u64 = 0;
+const WEEK: u64 = 604800000;
+const LP_DEPOSIT: u64 = 100000000000;
 
 fn deploy_erc20(env: &TestEnv, sender: AccountHash) -> TestContract {
     TestContract::new(
@@ -446,3 +450,1378 @@ fn test_minter_mint_with_deposit() {
         block_time,
     );
 }
+
+#[test]
+#[should_panic]
+fn test_minter_mint_killed_gauge() {
+    let (
+        _env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let name: String = "type".to_string();
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => name,
+            "weight" => None::<U256>
+        },
+        block_time,
+    );
+    let gauge_type: (bool, U128) = (false, 0.into());
+    add_gauge(
+        &gauge_controller,
+        owner,
+        Key::Hash(liquidity_gauge.package_hash()),
+        gauge_type,
+        Some(U256::from(1000000)),
+        block_time,
+    );
+    gauge_controller.call_contract(
+        owner,
+        "kill_gauge",
+        runtime_args! {
+            "addr" => Key::Hash(liquidity_gauge.package_hash())
+        },
+        block_time,
+    );
+    minter.mint(owner, Key::Hash(liquidity_gauge.package_hash()), block_time);
+}
+
+#[test]
+fn test_minter_set_gauge_token() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    assert_eq!(minter.admin(), Key::Account(owner));
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    // Gauges mint the token set at deploy until they are routed elsewhere
+    assert_eq!(minter.gauge_tokens(gauge), None);
+    let emission_token = deploy_erc20(&env, owner);
+    let emission_token = Key::Hash(emission_token.package_hash());
+    minter.add_emission_token(owner, emission_token, block_time);
+    assert!(minter.emission_tokens(emission_token));
+    minter.set_gauge_token(owner, gauge, emission_token, block_time);
+    assert_eq!(minter.gauge_tokens(gauge), Some(emission_token));
+    minter.set_gauge_token(
+        owner,
+        gauge,
+        Key::Hash(erc20_crv.package_hash()),
+        block_time,
+    );
+    assert_eq!(
+        minter.gauge_tokens(gauge),
+        Some(Key::Hash(erc20_crv.package_hash()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_add_emission_token_by_user() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        _liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let emission_token = deploy_erc20(&env, owner);
+    minter.add_emission_token(
+        env.next_user(),
+        Key::Hash(emission_token.package_hash()),
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_set_gauge_token_unknown_token() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let emission_token = deploy_erc20(&env, owner);
+    minter.set_gauge_token(
+        owner,
+        Key::Hash(liquidity_gauge.package_hash()),
+        Key::Hash(emission_token.package_hash()),
+        block_time,
+    );
+}
+
+#[test]
+fn test_minter_mint_rerouted_gauge() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    let emission_token = deploy_erc20(&env, owner);
+    let emission = Key::Hash(emission_token.package_hash());
+    let crv = Key::Hash(erc20_crv.package_hash());
+    minter.add_emission_token(owner, emission, block_time);
+    minter.set_gauge_token(owner, gauge, emission, block_time);
+    let crv_balance = balance_of(&env, owner, &erc20_crv, Key::Account(owner), block_time);
+    minter.mint(owner, gauge, block_time + 2 * WEEK);
+    let minted = minter.minted_for_token(Key::Account(owner), gauge, emission);
+    assert!(minted > U256::zero());
+    assert_eq!(
+        balance_of(
+            &env,
+            owner,
+            &emission_token,
+            Key::Account(owner),
+            block_time + 2 * WEEK
+        ),
+        minted
+    );
+    assert_eq!(
+        minter.minted_for_token(Key::Account(owner), gauge, crv),
+        U256::zero()
+    );
+    assert_eq!(
+        balance_of(
+            &env,
+            owner,
+            &erc20_crv,
+            Key::Account(owner),
+            block_time + 2 * WEEK
+        ),
+        crv_balance
+    );
+}
+
+#[test]
+fn test_minter_mint_next_to_share_minter() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    // A fund holding 10% of the emission, the gauge minter is left with the rest
+    let fund = env.next_user();
+    erc20_crv.call_contract(
+        owner,
+        "add_minter",
+        runtime_args! {
+            "minter" => Key::Account(fund),
+            "share" => U256::from(100000000),
+            "cap" => U256::zero()
+        },
+        block_time,
+    );
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    erc20_crv.call_contract(
+        fund,
+        "mint",
+        runtime_args! {
+            "to" => Key::Account(fund),
+            "amount" => U256::from(LP_DEPOSIT)
+        },
+        block_time + 2 * WEEK,
+    );
+    minter.mint(owner, gauge, block_time + 2 * WEEK);
+    assert!(minter.minted(Key::Account(owner), gauge) > U256::zero());
+}
+
+#[test]
+fn test_minter_mint_past_epoch_rewards() {
+    let (
+        _env,
+        minter,
+        owner,
+        token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    // The whole first epoch emission went to the gauge, claimed once the second
+    // epoch with its lower rate has started
+    minter.mint(owner, gauge, block_time + 53 * WEEK);
+    assert!(minter.minted(Key::Account(owner), gauge) > U256::zero());
+}
+
+#[test]
+fn test_minter_transfer_ownership() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        _liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let new_admin = env.next_user();
+    minter.commit_transfer_ownership(owner, Key::Account(new_admin), block_time);
+    assert_eq!(minter.admin(), Key::Account(owner));
+    assert_eq!(minter.future_admin(), Key::Account(new_admin));
+    minter.apply_transfer_ownership(owner, block_time);
+    assert_eq!(minter.admin(), Key::Account(new_admin));
+}
+
+#[test]
+#[should_panic]
+fn test_minter_commit_transfer_ownership_by_user() {
+    let (
+        env,
+        minter,
+        _owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        _liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let user = env.next_user();
+    minter.commit_transfer_ownership(user, Key::Account(user), block_time);
+}
+
+#[test]
+#[should_panic]
+fn test_minter_apply_transfer_ownership_without_commit() {
+    let (
+        _env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        _liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    minter.apply_transfer_ownership(owner, block_time);
+}
+
+fn claimable_by_gauge(
+    env: &TestEnv,
+    minter: &MINTERInstance,
+    sender: AccountHash,
+    addr: Key,
+    start: U256,
+    count: U256,
+    block_time: u64,
+) -> Vec<(Key, U256)> {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        sender,
+        runtime_args! {
+            "entrypoint" => String::from(CLAIMABLE_BY_GAUGE),
+            "package_hash" => Key::from(minter.contract_package_hash()),
+            "addr" => addr,
+            "start" => start,
+            "count" => count
+        },
+        block_time,
+    );
+    env.query_account_named_key(sender, &[CLAIMABLE_BY_GAUGE.into()])
+}
+
+#[test]
+fn test_minter_claimable_by_gauge() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let name: String = "type".to_string();
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => name,
+            "weight" => None::<U256>
+        },
+        block_time,
+    );
+    let liquidity_gauge_1 = MINTERInstance::deploy_liquidity_gauge(
+        &env,
+        "Liquidity Gauge 1",
+        owner,
+        Key::Hash(token.package_hash()),
+        Key::from(minter.contract_package_hash()),
+        Key::Account(owner),
+        block_time,
+    );
+    let gauges = vec![
+        Key::Hash(liquidity_gauge.package_hash()),
+        Key::Hash(liquidity_gauge_1.package_hash()),
+    ];
+    for gauge in &gauges {
+        add_gauge(
+            &gauge_controller,
+            owner,
+            *gauge,
+            (false, 0.into()),
+            Some(U256::from(1000000)),
+            block_time,
+        );
+    }
+    let claimable = claimable_by_gauge(
+        &env,
+        &minter,
+        owner,
+        Key::Account(owner),
+        0.into(),
+        10.into(),
+        block_time,
+    );
+    assert_eq!(
+        claimable,
+        vec![(gauges[0], U256::zero()), (gauges[1], U256::zero())]
+    );
+    let claimable = claimable_by_gauge(
+        &env,
+        &minter,
+        owner,
+        Key::Account(owner),
+        1.into(),
+        1.into(),
+        block_time,
+    );
+    assert_eq!(claimable, vec![(gauges[1], U256::zero())]);
+    // Killed gauges can not be minted from and are left out
+    gauge_controller.call_contract(
+        owner,
+        "kill_gauge",
+        runtime_args! {
+            "addr" => gauges[0]
+        },
+        block_time,
+    );
+    let claimable = claimable_by_gauge(
+        &env,
+        &minter,
+        owner,
+        Key::Account(owner),
+        0.into(),
+        10.into(),
+        block_time,
+    );
+    assert_eq!(claimable, vec![(gauges[1], U256::zero())]);
+}
+
+#[test]
+fn test_minter_claimable_by_gauge_matches_mint() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    let claimable = claimable_by_gauge(
+        &env,
+        &minter,
+        owner,
+        Key::Account(owner),
+        0.into(),
+        1.into(),
+        block_time + 2 * WEEK,
+    );
+    assert_eq!(claimable.len(), 1);
+    assert_eq!(claimable[0].0, gauge);
+    assert!(claimable[0].1 > U256::zero());
+    minter.mint(owner, gauge, block_time + 2 * WEEK);
+    assert_eq!(minter.minted(Key::Account(owner), gauge), claimable[0].1);
+}
+
+#[test]
+#[should_panic]
+fn test_minter_claimable_by_gauge_page_too_large() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        _gauge_controller,
+        _liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    claimable_by_gauge(
+        &env,
+        &minter,
+        owner,
+        Key::Account(owner),
+        0.into(),
+        51.into(),
+        block_time,
+    );
+}
+
+/// Adds `liquidity_gauge` to the controller and returns the account of a fresh
+/// ed25519 key along with its secret key
+fn signed_mint_setup(
+    owner: AccountHash,
+    gauge_controller: &TestContract,
+    liquidity_gauge: &TestContract,
+    block_time: u64,
+) -> (Key, PublicKey, [u8; 64]) {
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => String::from("type"),
+            "weight" => None::<U256>
+        },
+        block_time,
+    );
+    add_gauge(
+        gauge_controller,
+        owner,
+        Key::Hash(liquidity_gauge.package_hash()),
+        (false, 0.into()),
+        Some(U256::from(1000000)),
+        block_time,
+    );
+    let (secret_key, public_key) = ed25519::keypair(&[5u8; 32]);
+    let public_key = PublicKey::ed25519_from_bytes(public_key).unwrap();
+    (
+        Key::Account(AccountHash::from(&public_key)),
+        public_key,
+        secret_key,
+    )
+}
+
+fn sign_mint_for(
+    minter: &MINTERInstance,
+    gauge_addr: Key,
+    user: Key,
+    nonce: U256,
+    deadline: U256,
+    secret_key: &[u8; 64],
+) -> Vec<u8> {
+    let digest = mint_for_digest(
+        Key::from(minter.contract_package_hash()),
+        gauge_addr,
+        user,
+        nonce,
+        deadline,
+    );
+    ed25519::signature(&digest, secret_key).to_vec()
+}
+
+#[test]
+fn test_minter_mint_for_signed() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (user, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    let relayer = env.next_user();
+    for nonce in 0..2u64 {
+        let signature = sign_mint_for(&minter, gauge, user, nonce.into(), deadline, &secret_key);
+        minter.mint_for_signed(
+            relayer,
+            gauge,
+            user,
+            public_key.clone(),
+            deadline,
+            signature,
+            block_time,
+        );
+        assert_eq!(minter.nonces(user), U256::from(nonce + 1));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_signed_replay() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (user, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    let signature = sign_mint_for(&minter, gauge, user, 0.into(), deadline, &secret_key);
+    let relayer = env.next_user();
+    for _ in 0..2 {
+        minter.mint_for_signed(
+            relayer,
+            gauge,
+            user,
+            public_key.clone(),
+            deadline,
+            signature.clone(),
+            block_time,
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_signed_expired() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (user, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    let signature = sign_mint_for(&minter, gauge, user, 0.into(), deadline, &secret_key);
+    minter.mint_for_signed(
+        env.next_user(),
+        gauge,
+        user,
+        public_key,
+        deadline,
+        signature,
+        block_time + 60001,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_signed_wrong_signer() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (_, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    let victim = Key::Account(env.next_user());
+    let signature = sign_mint_for(&minter, gauge, victim, 0.into(), deadline, &secret_key);
+    minter.mint_for_signed(
+        env.next_user(),
+        gauge,
+        victim,
+        public_key,
+        deadline,
+        signature,
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_signed_other_gauge() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (user, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    // Signed for another gauge
+    let signature = sign_mint_for(
+        &minter,
+        Key::Hash([1u8; 32]),
+        user,
+        0.into(),
+        deadline,
+        &secret_key,
+    );
+    minter.mint_for_signed(
+        env.next_user(),
+        gauge,
+        user,
+        public_key,
+        deadline,
+        signature,
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_signed_other_deadline() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (user, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    let signature = sign_mint_for(&minter, gauge, user, 0.into(), deadline, &secret_key);
+    // Relayed with a later deadline than the one signed
+    minter.mint_for_signed(
+        env.next_user(),
+        gauge,
+        user,
+        public_key,
+        deadline + 60000,
+        signature,
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_signed_other_minter() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    let (user, public_key, secret_key) =
+        signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    let deadline = U256::from(block_time + 60000);
+    // Signed for another minter, the signature can not be replayed here
+    let digest = mint_for_digest(Key::Hash([1u8; 32]), gauge, user, 0.into(), deadline);
+    let signature = ed25519::signature(&digest, &secret_key).to_vec();
+    minter.mint_for_signed(
+        env.next_user(),
+        gauge,
+        user,
+        public_key,
+        deadline,
+        signature,
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_for_not_approved() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    signed_mint_setup(owner, &gauge_controller, &liquidity_gauge, block_time);
+    minter.mint_for(
+        env.next_user(),
+        Key::Hash(liquidity_gauge.package_hash()),
+        Key::from(owner),
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_and_lock_nothing_minted() {
+    let (
+        _env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => String::from("type"),
+            "weight" => None::<U256>
+        },
+        block_time,
+    );
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    add_gauge(
+        &gauge_controller,
+        owner,
+        gauge,
+        (false, 0.into()),
+        Some(U256::from(1000000)),
+        block_time,
+    );
+    // Nothing was deposited in the gauge, so there is nothing to lock
+    minter.mint_and_lock(
+        owner,
+        vec![gauge],
+        Some(U256::from(block_time + 31536000000)),
+        block_time,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_and_lock_other_emission_token() {
+    let (
+        env,
+        minter,
+        owner,
+        _token,
+        _voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        _erc20_crv,
+        block_time,
+    ) = deploy();
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => String::from("type"),
+            "weight" => None::<U256>
+        },
+        block_time,
+    );
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    add_gauge(
+        &gauge_controller,
+        owner,
+        gauge,
+        (false, 0.into()),
+        Some(U256::from(1000000)),
+        block_time,
+    );
+    let emission_token = deploy_erc20(&env, owner);
+    let emission_token = Key::Hash(emission_token.package_hash());
+    minter.add_emission_token(owner, emission_token, block_time);
+    minter.set_gauge_token(owner, gauge, emission_token, block_time);
+    minter.mint_and_lock(
+        owner,
+        vec![gauge],
+        Some(U256::from(block_time + 31536000000)),
+        block_time,
+    );
+}
+
+/// Lets `minter` mint CRV and deposits LP tokens of `owner` into `liquidity_gauge`,
+/// added to a weighted type so that it emits CRV from the following week on
+fn emitting_gauge_setup(
+    minter: &MINTERInstance,
+    owner: AccountHash,
+    lp_token: &TestContract,
+    gauge_controller: &TestContract,
+    liquidity_gauge: &TestContract,
+    erc20_crv: &TestContract,
+    block_time: u64,
+) -> Key {
+    erc20_crv.call_contract(
+        owner,
+        "set_minter",
+        runtime_args! {
+            "minter" => Key::from(minter.contract_package_hash())
+        },
+        block_time,
+    );
+    gauge_controller.call_contract(
+        owner,
+        "add_type",
+        runtime_args! {
+            "name" => String::from("type"),
+            "weight" => Some(U256::from(1000000000))
+        },
+        block_time,
+    );
+    let gauge = Key::Hash(liquidity_gauge.package_hash());
+    add_gauge(
+        gauge_controller,
+        owner,
+        gauge,
+        (false, 0.into()),
+        Some(U256::from(1000000)),
+        block_time,
+    );
+    lp_token.call_contract(
+        owner,
+        "mint",
+        runtime_args! {
+            "to" => Key::Account(owner),
+            "amount" => U256::from(LP_DEPOSIT)
+        },
+        block_time,
+    );
+    lp_token.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => gauge,
+            "amount" => U256::from(LP_DEPOSIT)
+        },
+        block_time,
+    );
+    liquidity_gauge.call_contract(
+        owner,
+        "deposit",
+        runtime_args! {
+            "value" => U256::from(LP_DEPOSIT),
+            "addr" =>  None::<Key>,
+            "claim_rewards" => None::<bool>
+        },
+        block_time,
+    );
+    gauge
+}
+
+/// Locks CRV of `owner` for a year and allows `voting_escrow` to pull more of it
+fn create_crv_lock(
+    owner: AccountHash,
+    erc20_crv: &TestContract,
+    voting_escrow: &TestContract,
+    block_time: u64,
+) {
+    create_crv_lock_until(
+        owner,
+        erc20_crv,
+        voting_escrow,
+        block_time + 52 * WEEK,
+        block_time,
+    );
+}
+
+fn create_crv_lock_until(
+    owner: AccountHash,
+    erc20_crv: &TestContract,
+    voting_escrow: &TestContract,
+    unlock_time: u64,
+    block_time: u64,
+) {
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => Key::Hash(voting_escrow.package_hash()),
+            "amount" => U256::from(1000000000000000000_u64)
+        },
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "create_lock",
+        runtime_args! {
+            "value" => U256::from(1000000000000_u64),
+            "unlock_time" => U256::from(unlock_time)
+        },
+        block_time,
+    );
+}
+
+fn deploy_smart_wallet_checker(env: &TestEnv, owner: AccountHash, block_time: u64) -> TestContract {
+    TestContract::new(
+        env,
+        "smart-wallet-checker.wasm",
+        "smart_wallet_checker",
+        owner,
+        runtime_args! {},
+        block_time,
+    )
+}
+
+fn balance_of(
+    env: &TestEnv,
+    sender: AccountHash,
+    token: &TestContract,
+    owner: Key,
+    block_time: u64,
+) -> U256 {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        sender,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF),
+            "package_hash" => Key::Hash(token.package_hash()),
+            "owner" => owner
+        },
+        block_time,
+    );
+    env.query_account_named_key(sender, &[BALANCE_OF.into()])
+}
+
+fn locked_end(
+    env: &TestEnv,
+    sender: AccountHash,
+    voting_escrow: &TestContract,
+    addr: Key,
+    block_time: u64,
+) -> U256 {
+    TestContract::new(
+        env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        sender,
+        runtime_args! {
+            "entrypoint" => String::from(LOCKED_END),
+            "package_hash" => Key::Hash(voting_escrow.package_hash()),
+            "addr" => addr
+        },
+        block_time,
+    );
+    env.query_account_named_key(sender, &[LOCKED_END.into()])
+}
+
+/// Amount of the primary lock of `owner`
+fn locked_amount(voting_escrow: &TestContract, owner: Key) -> U256 {
+    let amount: (bool, U128) = voting_escrow
+        .query_dictionary(
+            "locked",
+            hash(format!("locked_amount_{}", owner.to_formatted_string())),
+        )
+        .unwrap_or_default();
+    U256::from(amount.1.as_u128())
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_and_lock_contract_depositor_not_allowed() {
+    let (
+        _env,
+        minter,
+        owner,
+        token,
+        voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    create_crv_lock(owner, &erc20_crv, &voting_escrow, block_time);
+    // The existing lock is topped up through `deposit_for`, which rejects the
+    // minter contract as long as no smart wallet checker approves it
+    minter.mint_and_lock(owner, vec![gauge], None, block_time + 2 * WEEK);
+}
+
+#[test]
+fn test_minter_mint_and_lock_approved_contract_depositor() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    create_crv_lock(owner, &erc20_crv, &voting_escrow, block_time);
+    let checker = deploy_smart_wallet_checker(&env, owner, block_time);
+    checker.call_contract(
+        owner,
+        "approve_wallet",
+        runtime_args! {
+            "addr" => Key::from(minter.contract_package_hash())
+        },
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "commit_smart_wallet_checker",
+        runtime_args! {
+            "addr" => Key::Hash(checker.package_hash())
+        },
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "apply_smart_wallet_checker",
+        runtime_args! {},
+        block_time,
+    );
+    let escrow = Key::Hash(voting_escrow.package_hash());
+    let escrowed = balance_of(&env, owner, &erc20_crv, escrow, block_time);
+    let locked = locked_amount(&voting_escrow, Key::Account(owner));
+    let lock_end = locked_end(&env, owner, &voting_escrow, Key::Account(owner), block_time);
+    minter.mint_and_lock(owner, vec![gauge], None, block_time + 2 * WEEK);
+    let minted = minter.minted(Key::Account(owner), gauge);
+    assert!(minted > U256::zero());
+    assert_eq!(
+        balance_of(&env, owner, &erc20_crv, escrow, block_time + 2 * WEEK),
+        escrowed + minted
+    );
+    // `deposit_for` adds to the existing lock without moving its end
+    assert_eq!(
+        locked_amount(&voting_escrow, Key::Account(owner)),
+        locked + minted
+    );
+    assert_eq!(
+        locked_end(
+            &env,
+            owner,
+            &voting_escrow,
+            Key::Account(owner),
+            block_time + 2 * WEEK
+        ),
+        lock_end
+    );
+}
+
+#[test]
+fn test_minter_mint_and_lock_creates_lock() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "set_approved_depositor",
+        runtime_args! {
+            "addr" => Key::from(minter.contract_package_hash()),
+            "approved" => true
+        },
+        block_time,
+    );
+    let unlock_time = block_time + 54 * WEEK;
+    minter.mint_and_lock(
+        owner,
+        vec![gauge],
+        Some(U256::from(unlock_time)),
+        block_time + 2 * WEEK,
+    );
+    let minted = minter.minted(Key::Account(owner), gauge);
+    assert!(minted > U256::zero());
+    // The minted tokens went into a new lock of `owner` created by `create_lock_for`
+    assert_eq!(locked_amount(&voting_escrow, Key::Account(owner)), minted);
+    assert_eq!(
+        locked_end(
+            &env,
+            owner,
+            &voting_escrow,
+            Key::Account(owner),
+            block_time + 2 * WEEK
+        ),
+        U256::from(unlock_time / WEEK * WEEK)
+    );
+    assert_eq!(
+        balance_of(
+            &env,
+            owner,
+            &erc20_crv,
+            Key::from(minter.contract_package_hash()),
+            block_time + 2 * WEEK
+        ),
+        U256::zero()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_and_lock_expired_lock() {
+    let (
+        _env,
+        minter,
+        owner,
+        token,
+        voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "set_approved_depositor",
+        runtime_args! {
+            "addr" => Key::from(minter.contract_package_hash()),
+            "approved" => true
+        },
+        block_time,
+    );
+    create_crv_lock_until(
+        owner,
+        &erc20_crv,
+        &voting_escrow,
+        block_time + 2 * WEEK,
+        block_time,
+    );
+    // The lock still holds its tokens, so it is neither topped up nor replaced
+    minter.mint_and_lock(
+        owner,
+        vec![gauge],
+        Some(U256::from(block_time + 54 * WEEK)),
+        block_time + 3 * WEEK,
+    );
+}
+
+#[test]
+fn test_minter_mint_and_lock_withdrawn_lock() {
+    let (
+        env,
+        minter,
+        owner,
+        token,
+        voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "set_approved_depositor",
+        runtime_args! {
+            "addr" => Key::from(minter.contract_package_hash()),
+            "approved" => true
+        },
+        block_time,
+    );
+    create_crv_lock_until(
+        owner,
+        &erc20_crv,
+        &voting_escrow,
+        block_time + 2 * WEEK,
+        block_time,
+    );
+    voting_escrow.call_contract(owner, "withdraw", runtime_args! {}, block_time + 3 * WEEK);
+    let unlock_time = block_time + 55 * WEEK;
+    minter.mint_and_lock(
+        owner,
+        vec![gauge],
+        Some(U256::from(unlock_time)),
+        block_time + 3 * WEEK,
+    );
+    // A withdrawn lock is replaced by a new one
+    let minted = minter.minted(Key::Account(owner), gauge);
+    assert!(minted > U256::zero());
+    assert_eq!(locked_amount(&voting_escrow, Key::Account(owner)), minted);
+    assert_eq!(
+        locked_end(
+            &env,
+            owner,
+            &voting_escrow,
+            Key::Account(owner),
+            block_time + 3 * WEEK
+        ),
+        U256::from(unlock_time / WEEK * WEEK)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_minter_mint_and_lock_after_unlock_all() {
+    let (
+        _env,
+        minter,
+        owner,
+        token,
+        voting_escrow,
+        gauge_controller,
+        liquidity_gauge,
+        erc20_crv,
+        block_time,
+    ) = deploy();
+    let gauge = emitting_gauge_setup(
+        &minter,
+        owner,
+        &token,
+        &gauge_controller,
+        &liquidity_gauge,
+        &erc20_crv,
+        block_time,
+    );
+    voting_escrow.call_contract(
+        owner,
+        "set_approved_depositor",
+        runtime_args! {
+            "addr" => Key::from(minter.contract_package_hash()),
+            "approved" => true
+        },
+        block_time,
+    );
+    create_crv_lock(owner, &erc20_crv, &voting_escrow, block_time);
+    voting_escrow.call_contract(owner, "unlock_all", runtime_args! {}, block_time + WEEK);
+    minter.mint_and_lock(
+        owner,
+        vec![gauge],
+        Some(U256::from(block_time + 54 * WEEK)),
+        block_time + 2 * WEEK,
+    );
+}
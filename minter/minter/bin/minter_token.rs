@@ -12,7 +12,8 @@ use casper_contract::{
 };
 use casper_types::{
     runtime_args, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
-    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, PublicKey, RuntimeArgs,
+    URef, U256,
 };
 use casperlabs_contract_utils::{ContractContext, OnChainContractStorage};
 use minter_crate::MINTER;
@@ -76,6 +77,19 @@ fn mint_many() {
     Token::default().mint_many(gauge_addrs);
 }
 
+///@notice Mint everything which belongs to `msg.sender` across multiple gauges and lock it
+///@dev Tops up the primary lock with `deposit_for` (needs the smart wallet checker to approve
+///     the minter) or creates it with `create_lock_for` (needs the minter to be an approved depositor)
+///@param gauge_addrs List of `LiquidityGauge` addresses
+///@param unlock_time Epoch time when a new lock ends, ignored when the caller has a lock
+
+#[no_mangle]
+fn mint_and_lock() {
+    let gauge_addrs: Vec<Key> = runtime::get_named_arg("gauge_addrs");
+    let unlock_time: Option<U256> = runtime::get_named_arg("unlock_time");
+    Token::default().mint_and_lock(gauge_addrs, unlock_time);
+}
+
 /// @notice Mint tokens for `_for`
 /// @dev Only possible when `msg.sender` has been approved via `toggle_approve_mint`
 /// @param gauge_addr `LiquidityGauge` address to get mintable amount from
@@ -88,6 +102,56 @@ fn mint_for() {
     Token::default().mint_for(gauge_addr, _for);
 }
 
+/// @notice Get the amount `addr` can mint from the gauges `start` to `start + count`
+/// @dev Checkpoints `addr` in each gauge, amounts are what `mint` would mint now
+/// @param addr User address
+/// @param start Index of the first gauge
+/// @param count Number of gauges (at most 50)
+
+#[no_mangle]
+fn claimable_by_gauge() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let start: U256 = runtime::get_named_arg("start");
+    let count: U256 = runtime::get_named_arg("count");
+    let ret: Vec<(Key, U256)> = Token::default().claimable_by_gauge(addr, start, count);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Mint tokens for `_for` with a signature of `_for` instead of an approval
+/// @dev Only ed25519 account keys are supported, secp256k1 keys are rejected
+/// @param gauge_addr `LiquidityGauge` address to get mintable amount from
+/// @param _for Address to mint to
+/// @param public_key Ed25519 account key of `_for`
+/// @param deadline Time after which the signature is rejected
+/// @param signature ed25519 signature of `mint_for_digest`
+
+#[no_mangle]
+fn mint_for_signed() {
+    let gauge_addr: Key = runtime::get_named_arg("gauge_addr");
+    let _for: Key = runtime::get_named_arg("for");
+    let public_key: PublicKey = runtime::get_named_arg("public_key");
+    let deadline: U256 = runtime::get_named_arg("deadline");
+    let signature: Vec<u8> = runtime::get_named_arg("signature");
+    Token::default().mint_for_signed(gauge_addr, _for, public_key, deadline, signature);
+}
+
+#[no_mangle]
+fn mint_for_digest() {
+    let gauge_addr: Key = runtime::get_named_arg("gauge_addr");
+    let _for: Key = runtime::get_named_arg("for");
+    let nonce: U256 = runtime::get_named_arg("nonce");
+    let deadline: U256 = runtime::get_named_arg("deadline");
+    let ret: [u8; 32] = Token::default().mint_for_digest(gauge_addr, _for, nonce, deadline);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn nonces() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let ret: U256 = Token::default().nonces(owner);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// @notice allow `minting_user` to mint for `msg.sender`
 /// @param minting_user Address to toggle permission for
 
@@ -97,6 +161,74 @@ fn toggle_approve_mint() {
     Token::default().toggle_approve_mint(minting_user);
 }
 
+/// @notice Register `token` as an emission token that gauges can be routed to
+/// @param token Emission token address
+
+#[no_mangle]
+fn add_emission_token() {
+    let token: Key = runtime::get_named_arg("token");
+    Token::default().add_emission_token(token);
+}
+
+/// @notice Route everything minted for `gauge` to emission token `token`
+/// @param gauge `LiquidityGauge` address
+/// @param token Registered emission token address
+
+#[no_mangle]
+fn set_gauge_token() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    Token::default().set_gauge_token(gauge, token);
+}
+
+#[no_mangle]
+fn gauge_token() {
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let ret: Key = Token::default().gauge_token(gauge);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
+fn is_emission_token() {
+    let token: Key = runtime::get_named_arg("token");
+    let ret: bool = Token::default().is_emission_token(token);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
+fn minted_for_token() {
+    let owner: Key = runtime::get_named_arg("owner");
+    let gauge: Key = runtime::get_named_arg("gauge");
+    let token: Key = runtime::get_named_arg("token");
+    let ret: U256 = Token::default().minted_for_token(owner, gauge, token);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+#[no_mangle]
+fn admin() {
+    let ret: Key = Token::default().admin();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn future_admin() {
+    let ret: Key = Token::default().future_admin();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Transfer ownership of the Minter to `addr`
+/// @param addr Address to have ownership transferred to
+
+#[no_mangle]
+fn commit_transfer_ownership() {
+    let addr: Key = runtime::get_named_arg("addr");
+    Token::default().commit_transfer_ownership(addr);
+}
+
+/// @notice Apply pending ownership transfer
+
+#[no_mangle]
+fn apply_transfer_ownership() {
+    Token::default().apply_transfer_ownership();
+}
+
 #[no_mangle]
 fn package_hash() {
     let ret: ContractPackageHash = Token::default().get_package_hash();
@@ -244,6 +376,16 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_and_lock",
+        vec![
+            Parameter::new("gauge_addrs", CLType::List(Box::new(Key::cl_type()))),
+            Parameter::new("unlock_time", CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "mint_for",
         vec![
@@ -254,6 +396,49 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "claimable_by_gauge",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("count", U256::cl_type()),
+        ],
+        Vec::<(Key, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_for_signed",
+        vec![
+            Parameter::new("gauge_addr", Key::cl_type()),
+            Parameter::new("for", Key::cl_type()),
+            Parameter::new("public_key", PublicKey::cl_type()),
+            Parameter::new("deadline", U256::cl_type()),
+            Parameter::new("signature", CLType::List(Box::new(u8::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint_for_digest",
+        vec![
+            Parameter::new("gauge_addr", Key::cl_type()),
+            Parameter::new("for", Key::cl_type()),
+            Parameter::new("nonce", U256::cl_type()),
+            Parameter::new("deadline", U256::cl_type()),
+        ],
+        <[u8; 32]>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "nonces",
+        vec![Parameter::new("owner", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "toggle_approve_mint",
         vec![Parameter::new("minting_user", Key::cl_type())],
@@ -281,6 +466,48 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "add_emission_token",
+        vec![Parameter::new("token", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_gauge_token",
+        vec![
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "gauge_token",
+        vec![Parameter::new("gauge", Key::cl_type())],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "is_emission_token",
+        vec![Parameter::new("token", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "minted_for_token",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("gauge", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
     entry_points.add_entry_point(EntryPoint::new(
         "token",
@@ -296,6 +523,34 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "admin",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "future_admin",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "commit_transfer_ownership",
+        vec![Parameter::new("addr", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "apply_transfer_ownership",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
     entry_points.add_entry_point(EntryPoint::new(
         "package_hash",
@@ -0,0 +1,21 @@
+use alloc::string::{String, ToString};
+use casper_types::Key;
+
+pub enum SmartWalletCheckerEvent {
+    CommitOwnership { admin: Key },
+    ApplyOwnership { admin: Key },
+    ApproveWallet { wallet: Key },
+    RevokeWallet { wallet: Key },
+}
+
+impl SmartWalletCheckerEvent {
+    pub fn type_name(&self) -> String {
+        match self {
+            SmartWalletCheckerEvent::CommitOwnership { admin: _ } => "commitOwnership",
+            SmartWalletCheckerEvent::ApplyOwnership { admin: _ } => "applyOwnership",
+            SmartWalletCheckerEvent::ApproveWallet { wallet: _ } => "approveWallet",
+            SmartWalletCheckerEvent::RevokeWallet { wallet: _ } => "revokeWallet",
+        }
+        .to_string()
+    }
+}
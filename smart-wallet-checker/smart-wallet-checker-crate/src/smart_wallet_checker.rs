@@ -0,0 +1,87 @@
+use crate::{data::*, event::SmartWalletCheckerEvent};
+use alloc::{collections::BTreeMap, string::ToString};
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{ApiError, ContractHash, ContractPackageHash, Key};
+use casperlabs_contract_utils::{ContractContext, ContractStorage};
+use common::{errors::*, utils::*};
+
+/// @notice Allowlist of smart contract wallets that are permitted to lock in the VotingEscrow
+/// @dev The VotingEscrow consults `check` whenever a contract package calls one of its deposit
+///      functions, so that tokenized ve wrappers cannot be created without DAO approval
+pub trait SMARTWALLETCHECKER<Storage: ContractStorage>: ContractContext<Storage> {
+    fn init(&self, contract_hash: ContractHash, package_hash: ContractPackageHash) {
+        Wallets::init();
+        set_admin(self.get_caller());
+        set_contract_hash(contract_hash);
+        set_package_hash(package_hash);
+    }
+
+    fn only_admin(&self) {
+        if self.get_caller() != get_admin() {
+            runtime::revert(ApiError::from(Error::SmartWalletCheckerAdminOnly));
+        }
+    }
+
+    /// @notice Transfer ownership of SmartWalletChecker contract to `addr`
+    /// @param addr Address to have ownership transferred to
+    fn commit_transfer_ownership(&mut self, addr: Key) {
+        self.only_admin();
+        set_future_admin(addr);
+        self.emit(&SmartWalletCheckerEvent::CommitOwnership { admin: addr });
+    }
+
+    /// @notice Apply ownership transfer
+    fn apply_transfer_ownership(&mut self) {
+        self.only_admin();
+        let admin: Key = get_future_admin();
+        if admin == zero_address() || admin == account_zero_address() {
+            runtime::revert(ApiError::from(Error::SmartWalletCheckerZeroAddress));
+        }
+        set_admin(admin);
+        self.emit(&SmartWalletCheckerEvent::ApplyOwnership { admin });
+    }
+
+    /// @notice Allow `addr` to lock tokens in the VotingEscrow
+    /// @param addr Contract package address of the smart wallet
+    fn approve_wallet(&mut self, addr: Key) {
+        self.only_admin();
+        Wallets::instance().set(&addr, true);
+        self.emit(&SmartWalletCheckerEvent::ApproveWallet { wallet: addr });
+    }
+
+    /// @notice Disallow `addr` from locking tokens in the VotingEscrow
+    /// @param addr Contract package address of the smart wallet
+    fn revoke_wallet(&mut self, addr: Key) {
+        self.only_admin();
+        Wallets::instance().set(&addr, false);
+        self.emit(&SmartWalletCheckerEvent::RevokeWallet { wallet: addr });
+    }
+
+    /// @notice Check if `addr` is an approved smart wallet
+    /// @param addr Address to be checked
+    /// @return bool success
+    fn check(&self, addr: Key) -> bool {
+        Wallets::instance().get(&addr)
+    }
+
+    fn emit(&self, smart_wallet_checker_event: &SmartWalletCheckerEvent) {
+        match smart_wallet_checker_event {
+            SmartWalletCheckerEvent::CommitOwnership { admin }
+            | SmartWalletCheckerEvent::ApplyOwnership { admin } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", smart_wallet_checker_event.type_name());
+                event.insert("admin", admin.to_string());
+                storage::new_uref(event);
+            }
+            SmartWalletCheckerEvent::ApproveWallet { wallet }
+            | SmartWalletCheckerEvent::RevokeWallet { wallet } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", smart_wallet_checker_event.type_name());
+                event.insert("wallet", wallet.to_string());
+                storage::new_uref(event);
+            }
+        };
+    }
+}
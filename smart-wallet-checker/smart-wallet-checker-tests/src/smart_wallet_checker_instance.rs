@@ -0,0 +1,94 @@
+use casper_types::{
+    account::AccountHash, bytesrepr::FromBytes, runtime_args, CLTyped, Key, RuntimeArgs,
+};
+use casperlabs_test_env::{TestContract, TestEnv};
+use hex::encode;
+use smart_wallet_checker_crate::data::WALLETS;
+use std::time::SystemTime;
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+pub struct SMARTWALLETCHECKERInstance(TestContract);
+
+impl SMARTWALLETCHECKERInstance {
+    pub fn new_deploy(
+        env: &TestEnv,
+        contract_name: &str,
+        sender: AccountHash,
+        time: u64,
+    ) -> SMARTWALLETCHECKERInstance {
+        SMARTWALLETCHECKERInstance(TestContract::new(
+            env,
+            "smart-wallet-checker.wasm",
+            contract_name,
+            sender,
+            runtime_args! {},
+            time,
+        ))
+    }
+
+    pub fn commit_transfer_ownership(&self, owner: AccountHash, addr: Key, time: u64) {
+        self.0.call_contract(
+            owner,
+            "commit_transfer_ownership",
+            runtime_args! {
+                "addr" => addr
+            },
+            time,
+        );
+    }
+
+    pub fn apply_transfer_ownership(&self, owner: AccountHash, time: u64) {
+        self.0
+            .call_contract(owner, "apply_transfer_ownership", runtime_args! {}, time);
+    }
+
+    pub fn approve_wallet(&self, owner: AccountHash, addr: Key, time: u64) {
+        self.0.call_contract(
+            owner,
+            "approve_wallet",
+            runtime_args! {
+                "addr" => addr
+            },
+            time,
+        );
+    }
+
+    pub fn revoke_wallet(&self, owner: AccountHash, addr: Key, time: u64) {
+        self.0.call_contract(
+            owner,
+            "revoke_wallet",
+            runtime_args! {
+                "addr" => addr
+            },
+            time,
+        );
+    }
+
+    pub fn package_hash(&self) -> [u8; 32] {
+        self.0.package_hash()
+    }
+
+    pub fn key_value<T: CLTyped + FromBytes>(&self, key: String) -> T {
+        self.0.query_named_key(key)
+    }
+
+    pub fn wallets(&self, addr: &Key) -> bool {
+        self.0
+            .query_dictionary(WALLETS, key_to_str(addr))
+            .unwrap_or_default()
+    }
+}
+
+pub fn key_to_str(key: &Key) -> String {
+    match key {
+        Key::Account(account) => account.to_string(),
+        Key::Hash(package) => encode(package),
+        _ => panic!("Unexpected key type"),
+    }
+}
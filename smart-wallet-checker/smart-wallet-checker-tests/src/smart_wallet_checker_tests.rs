@@ -0,0 +1,58 @@
+use crate::smart_wallet_checker_instance::{now, SMARTWALLETCHECKERInstance};
+use casper_types::{account::AccountHash, Key};
+use casperlabs_test_env::TestEnv;
+use common::keys::*;
+
+fn deploy() -> (TestEnv, AccountHash, SMARTWALLETCHECKERInstance, u64) {
+    let env = TestEnv::new();
+    let owner = env.next_user();
+    let time_now: u64 = now();
+    let instance =
+        SMARTWALLETCHECKERInstance::new_deploy(&env, "Smart Wallet Checker", owner, time_now);
+    (env, owner, instance, time_now)
+}
+
+#[test]
+fn test_deploy() {
+    let (_, owner, instance, _) = deploy();
+    let admin: Key = instance.key_value(ADMIN.to_string());
+    assert_eq!(admin, Key::from(owner), "Admin not set");
+}
+
+#[test]
+fn test_approve_wallet() {
+    let (_, owner, instance, time_now) = deploy();
+    let wallet: Key = Key::Hash([7u8; 32]);
+    assert!(!instance.wallets(&wallet), "Wallet approved by default");
+    instance.approve_wallet(owner, wallet, time_now);
+    assert!(instance.wallets(&wallet), "Wallet not approved");
+}
+
+#[test]
+fn test_revoke_wallet() {
+    let (_, owner, instance, time_now) = deploy();
+    let wallet: Key = Key::Hash([7u8; 32]);
+    instance.approve_wallet(owner, wallet, time_now);
+    instance.revoke_wallet(owner, wallet, time_now);
+    assert!(!instance.wallets(&wallet), "Wallet not revoked");
+}
+
+#[test]
+#[should_panic]
+fn test_approve_wallet_by_non_admin() {
+    let (env, _, instance, time_now) = deploy();
+    let user = env.next_user();
+    instance.approve_wallet(user, Key::Hash([7u8; 32]), time_now);
+}
+
+#[test]
+fn test_transfer_ownership() {
+    let (env, owner, instance, time_now) = deploy();
+    let new_admin: Key = Key::from(env.next_user());
+    instance.commit_transfer_ownership(owner, new_admin, time_now);
+    let future_admin: Key = instance.key_value(FUTURE_ADMIN.to_string());
+    assert_eq!(future_admin, new_admin, "Future admin not set");
+    instance.apply_transfer_ownership(owner, time_now);
+    let admin: Key = instance.key_value(ADMIN.to_string());
+    assert_eq!(admin, new_admin, "Admin not changed");
+}
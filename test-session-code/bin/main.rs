@@ -4,7 +4,7 @@
 // We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
 // `no_std` environment.
 extern crate alloc;
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
@@ -12,9 +12,26 @@ use casper_contract::{
 use casper_types::{
     bytesrepr::ToBytes, runtime_args, ApiError, CLTyped, Key, RuntimeArgs, URef, U128, U256,
 };
+use casper_types_derive::{CLTyped, FromBytes, ToBytes};
 use common::keys::*;
 use crv20::Address;
 
+// Mirrors of the GaugeController metadata records returned by `gauges_page`
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct GaugeMetadata {
+    pub name: String,
+    pub kind: u8,
+    pub lp_token: Key,
+    pub reward_tokens: Vec<Key>,
+}
+
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct GaugeInfo {
+    pub addr: Key,
+    pub gauge_type: (bool, U128),
+    pub metadata: GaugeMetadata,
+}
+
 // Key is the same a destination
 fn store<T: CLTyped + ToBytes>(key: &str, value: T) {
     // Store `value` under a new unforgeable reference.
@@ -112,6 +129,56 @@ pub extern "C" fn call() {
             );
             store(BALANCE_OF_AT, ret);
         }
+        BALANCE_OF_AT_TIME => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let t: U256 = runtime::get_named_arg("t");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                BALANCE_OF_AT_TIME,
+                runtime_args! {
+                    "addr" => addr,
+                    "t" => t
+                },
+            );
+            store(BALANCE_OF_AT_TIME, ret);
+        }
+        DELEGATED_BALANCE => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                DELEGATED_BALANCE,
+                runtime_args! {
+                    "addr" => addr
+                },
+            );
+            store(DELEGATED_BALANCE, ret);
+        }
+        RECEIVED_BALANCE => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                RECEIVED_BALANCE,
+                runtime_args! {
+                    "addr" => addr
+                },
+            );
+            store(RECEIVED_BALANCE, ret);
+        }
+        ADJUSTED_BALANCE_OF => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                ADJUSTED_BALANCE_OF,
+                runtime_args! {
+                    "addr" => addr
+                },
+            );
+            store(ADJUSTED_BALANCE_OF, ret);
+        }
         APPROVED_TO_DEPOSIT => {
             let owner: Key = runtime::get_named_arg("owner");
             let spender: Key = runtime::get_named_arg("spender");
@@ -195,6 +262,78 @@ pub extern "C" fn call() {
             );
             store(TOTAL_SUPPLY_AT, ret);
         }
+        TOTAL_SUPPLY_AT_TIME => {
+            let t: U256 = runtime::get_named_arg("t");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                TOTAL_SUPPLY_AT_TIME,
+                runtime_args! {
+                    "t" => t,
+                },
+            );
+            store(TOTAL_SUPPLY_AT_TIME, ret);
+        }
+        BALANCE_OF_AT_TIMES => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let ts: Vec<U256> = runtime::get_named_arg("ts");
+            let ret: Vec<U256> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                BALANCE_OF_AT_TIMES,
+                runtime_args! {
+                    "addr" => addr,
+                    "ts" => ts
+                },
+            );
+            store(BALANCE_OF_AT_TIMES, ret);
+        }
+        BALANCE_OF_RANGE => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let start: U256 = runtime::get_named_arg("start");
+            let end: U256 = runtime::get_named_arg("end");
+            let step: U256 = runtime::get_named_arg("step");
+            let ret: Vec<U256> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                BALANCE_OF_RANGE,
+                runtime_args! {
+                    "addr" => addr,
+                    "start" => start,
+                    "end" => end,
+                    "step" => step
+                },
+            );
+            store(BALANCE_OF_RANGE, ret);
+        }
+        TOTAL_SUPPLY_AT_TIMES => {
+            let ts: Vec<U256> = runtime::get_named_arg("ts");
+            let ret: Vec<U256> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                TOTAL_SUPPLY_AT_TIMES,
+                runtime_args! {
+                    "ts" => ts
+                },
+            );
+            store(TOTAL_SUPPLY_AT_TIMES, ret);
+        }
+        TOTAL_SUPPLY_RANGE => {
+            let start: U256 = runtime::get_named_arg("start");
+            let end: U256 = runtime::get_named_arg("end");
+            let step: U256 = runtime::get_named_arg("step");
+            let ret: Vec<U256> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                TOTAL_SUPPLY_RANGE,
+                runtime_args! {
+                    "start" => start,
+                    "end" => end,
+                    "step" => step
+                },
+            );
+            store(TOTAL_SUPPLY_RANGE, ret);
+        }
         LAST_TIME_REWARD_APPLICABLE => {
             let ret: U256 = runtime::call_versioned_contract(
                 package_hash.into_hash().unwrap_or_revert().into(),
@@ -257,6 +396,80 @@ pub extern "C" fn call() {
             );
             store(MINTABLE_IN_TIMEFRAME, ret);
         }
+        EMISSION_SCHEDULE => {
+            let count: U256 = runtime::get_named_arg("count");
+            let ret: Vec<(U256, U256, U256)> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                EMISSION_SCHEDULE,
+                runtime_args! {
+                    "count" => count
+                },
+            );
+            store(EMISSION_SCHEDULE, ret);
+        }
+        SUPPLY_REACHED_AT => {
+            let supply: U256 = runtime::get_named_arg("supply");
+            let ret: Option<U256> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                SUPPLY_REACHED_AT,
+                runtime_args! {
+                    "supply" => supply
+                },
+            );
+            store(SUPPLY_REACHED_AT, ret);
+        }
+        MINTER_ALLOWANCE => {
+            let minter: Key = runtime::get_named_arg("minter");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                MINTER_ALLOWANCE,
+                runtime_args! {
+                    "minter" => minter
+                },
+            );
+            store(MINTER_ALLOWANCE, ret);
+        }
+        MINTER_QUOTA => {
+            let minter: Key = runtime::get_named_arg("minter");
+            let ret: (U256, U256) = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                MINTER_QUOTA,
+                runtime_args! {
+                    "minter" => minter
+                },
+            );
+            store(MINTER_QUOTA, ret);
+        }
+        ERC20_CRV_BALANCE_OF_AT => {
+            let owner: Address = runtime::get_named_arg("owner");
+            let t: U256 = runtime::get_named_arg("t");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                BALANCE_OF_AT,
+                runtime_args! {
+                    "owner" => owner,
+                    "t" => t
+                },
+            );
+            store(ERC20_CRV_BALANCE_OF_AT, ret);
+        }
+        ERC20_CRV_TOTAL_SUPPLY_AT => {
+            let t: U256 = runtime::get_named_arg("t");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                TOTAL_SUPPLY_AT,
+                runtime_args! {
+                    "t" => t
+                },
+            );
+            store(ERC20_CRV_TOTAL_SUPPLY_AT, ret);
+        }
         VE_FOR_AT => {
             let user: Key = runtime::get_named_arg("user");
             let timestamp: U256 = runtime::get_named_arg("timestamp");
@@ -472,6 +685,62 @@ pub extern "C" fn call() {
             );
             store(GET_LAST_USER_SLOPE, ret);
         }
+        LOCKED_END => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let ret: U256 = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                LOCKED_END,
+                runtime_args! {
+                    "addr" => addr
+                },
+            );
+            store(LOCKED_END, ret);
+        }
+        PROJECTED_GAUGE_WEIGHTS => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let weeks: U256 = runtime::get_named_arg("weeks");
+            let ret: Vec<(U256, U256, U256)> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                PROJECTED_GAUGE_WEIGHTS,
+                runtime_args! {
+                    "addr" => addr,
+                    "weeks" => weeks
+                },
+            );
+            store(PROJECTED_GAUGE_WEIGHTS, ret);
+        }
+        GAUGES_PAGE => {
+            let start: U256 = runtime::get_named_arg("start");
+            let count: U256 = runtime::get_named_arg("count");
+            let ret: Vec<GaugeInfo> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                GAUGES_PAGE,
+                runtime_args! {
+                    "start" => start,
+                    "count" => count
+                },
+            );
+            store(GAUGES_PAGE, ret);
+        }
+        CLAIMABLE_BY_GAUGE => {
+            let addr: Key = runtime::get_named_arg("addr");
+            let start: U256 = runtime::get_named_arg("start");
+            let count: U256 = runtime::get_named_arg("count");
+            let ret: Vec<(Key, U256)> = runtime::call_versioned_contract(
+                package_hash.into_hash().unwrap_or_revert().into(),
+                None,
+                CLAIMABLE_BY_GAUGE,
+                runtime_args! {
+                    "addr" => addr,
+                    "start" => start,
+                    "count" => count
+                },
+            );
+            store(CLAIMABLE_BY_GAUGE, ret);
+        }
         _ => runtime::revert(ApiError::UnexpectedKeyVariant),
     };
 }
\ No newline at end of file
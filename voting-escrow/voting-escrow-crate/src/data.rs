@@ -3,7 +3,7 @@ use alloc::{
   string::{String, ToString},
   vec::Vec,
 };
-use casper_contract::contract_api::runtime::get_blocktime;
+use casper_contract::contract_api::runtime;
 use casper_types::{ContractHash, ContractPackageHash, Key, U128, U256};
 use casper_types_derive::{CLTyped, FromBytes, ToBytes};
 use casperlabs_contract_utils::{get_key, set_key, Dict};
@@ -11,15 +11,21 @@ use common::{keys::*, utils::*};
 
 pub const DEPOSIT_FOR_TYPE: i128 = 0;
 pub const CREATE_LOCK_TYPE: i128 = 1;
+pub const CREATE_LOCK_FOR_TYPE: i128 = 4;
 pub const INCREASE_LOCK_AMOUNT: i128 = 2;
 pub const INCREASE_UNLOCK_TIME: i128 = 3;
 pub const WEEK: U256 = U256([604800000, 0, 0, 0]); // all future times are rounded by week
 pub const MAXTIME: U256 = U256([126144000000, 0, 0, 0]); // 4 years
 pub const MULTIPLIER: U256 = U256([1000000000, 0, 0, 0]);
+pub const MAX_CHECKPOINT_WEEKS: U256 = U256([104, 0, 0, 0]); // weeks of global history a user operation may fill
+pub const MAX_HISTORY_POINTS: U256 = U256([256, 0, 0, 0]); // timestamps per historical batch query
+pub const MAX_BOOST_PERCENTAGE: U256 = U256([10000, 0, 0, 0]); // 100% in bps
+pub const MAX_LOCK_ID: U256 = U256([16, 0, 0, 0]); // bounds the locks summed up per account
 
 // We cannot really do block numbers per se b/c slope is per time, not per block
-// and per block could be fairly bad b/c Ethereum changes blocktimes.
-// What we can do is to extrapolate ***At functions
+// and a contract cannot read the Casper block height.
+// `blk` is the latest height recorded by the block height feeder at checkpoint time,
+// ***At functions map heights to timestamps through `BlockHeightHistory`
 #[derive(Clone, Copy, CLTyped, ToBytes, FromBytes, Default)]
 pub struct Point {
   pub bias: (bool, U128),
@@ -61,6 +67,39 @@ impl LockedBalance {
   }
 }
 
+#[derive(Clone, Copy, CLTyped, ToBytes, FromBytes, Default)]
+pub struct Boost {
+  pub bias: (bool, U128),
+  pub slope: (bool, U128),
+  pub ts: U256,
+  pub end: U256,
+  pub cancel_time: U256,
+}
+
+impl Boost {
+  pub fn set_bias(&mut self, value: i128) {
+      self.bias = i128_to_tuple(value);
+  }
+
+  pub fn bias(&self) -> i128 {
+      tuple_to_i128(self.bias)
+  }
+
+  pub fn set_slope(&mut self, value: i128) {
+      self.slope = i128_to_tuple(value);
+  }
+
+  pub fn slope(&self) -> i128 {
+      tuple_to_i128(self.slope)
+  }
+}
+
+#[derive(Clone, Copy, CLTyped, ToBytes, FromBytes, Default)]
+pub struct BlockHeight {
+  pub blk: U256,
+  pub ts: U256,
+}
+
 pub const LOCKED: &str = "locked";
 #[derive(CLTyped, ToBytes, FromBytes)]
 pub struct Locked {
@@ -383,6 +422,590 @@ impl PointHistory {
   }
 }
 
+pub const APPROVED_DEPOSITORS: &str = "approved_depositors";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct ApprovedDepositors {
+  dict: Dict,
+}
+
+impl ApprovedDepositors {
+  pub fn instance() -> ApprovedDepositors {
+      ApprovedDepositors {
+          dict: Dict::instance(APPROVED_DEPOSITORS),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(APPROVED_DEPOSITORS)
+  }
+
+  pub fn get(&self, depositor: &Key) -> bool {
+      self.dict.get_by_key(depositor).unwrap_or_default()
+  }
+
+  pub fn set(&self, depositor: &Key, value: bool) {
+      self.dict.set_by_key(depositor, value);
+  }
+}
+
+pub const LOCK_COUNT: &str = "lock_count";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct LockCount {
+  dict: Dict,
+}
+
+impl LockCount {
+  pub fn instance() -> LockCount {
+      LockCount {
+          dict: Dict::instance(LOCK_COUNT),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(LOCK_COUNT)
+  }
+
+  pub fn get(&self, owner: &Key) -> U256 {
+      self.dict.get_by_key(owner).unwrap_or_default()
+  }
+
+  pub fn set(&self, owner: &Key, value: U256) {
+      self.dict.set_by_key(owner, value);
+  }
+}
+
+pub const PERPETUAL_LOCKS: &str = "perpetual_locks";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct PerpetualLocks {
+  dict: Dict,
+}
+
+impl PerpetualLocks {
+  pub fn instance() -> PerpetualLocks {
+      PerpetualLocks {
+          dict: Dict::instance(PERPETUAL_LOCKS),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(PERPETUAL_LOCKS)
+  }
+
+  pub fn get(&self, owner: &Key) -> bool {
+      self.dict.get_by_key(owner).unwrap_or_default()
+  }
+
+  pub fn set(&self, owner: &Key, value: bool) {
+      self.dict.set_by_key(owner, value);
+  }
+}
+
+/// Key the lock `lock_id` of `owner` is stored under in `Locked`, `UserPointHistory` and `UserPointEpoch`.
+/// The primary lock (id 0) is stored under `owner` itself, additional locks under a derived hash
+pub fn lock_key(owner: &Key, lock_id: &U256) -> Key {
+  if *lock_id == 0.into() {
+      *owner
+  } else {
+      Key::Hash(runtime::blake2b(format!(
+          "{}{}{}",
+          LOCK_COUNT,
+          owner.to_formatted_string(),
+          lock_id
+      )))
+  }
+}
+
+pub const BLOCK_HEIGHT_HISTORY: &str = "block_height_history";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct BlockHeightHistory {
+  dict: Dict,
+}
+
+impl BlockHeightHistory {
+  pub fn instance() -> BlockHeightHistory {
+      BlockHeightHistory {
+          dict: Dict::instance(BLOCK_HEIGHT_HISTORY),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(BLOCK_HEIGHT_HISTORY)
+  }
+
+  pub fn get(&self, idx: &U256) -> BlockHeight {
+      BlockHeight {
+          blk: self
+              .dict
+              .get(hash(format!("{}{}{}", BLOCK_HEIGHT_HISTORY, "_blk_", idx)).as_str())
+              .unwrap_or_default(),
+          ts: self
+              .dict
+              .get(hash(format!("{}{}{}", BLOCK_HEIGHT_HISTORY, "_ts_", idx)).as_str())
+              .unwrap_or_default(),
+      }
+  }
+
+  pub fn set(&self, idx: &U256, value: BlockHeight) {
+      self.dict.set(
+          hash(format!("{}{}{}", BLOCK_HEIGHT_HISTORY, "_blk_", idx)).as_str(),
+          value.blk,
+      );
+
+      self.dict.set(
+          hash(format!("{}{}{}", BLOCK_HEIGHT_HISTORY, "_ts_", idx)).as_str(),
+          value.ts,
+      );
+  }
+}
+
+pub const BOOSTS: &str = "boosts";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct Boosts {
+  dict: Dict,
+}
+
+impl Boosts {
+  pub fn instance() -> Boosts {
+      Boosts {
+          dict: Dict::instance(BOOSTS),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(BOOSTS)
+  }
+
+  pub fn get(&self, delegator: &Key, receiver: &Key) -> Boost {
+      Boost {
+          bias: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}{}{}",
+                      BOOSTS,
+                      "_bias_",
+                      delegator.to_formatted_string(),
+                      "_",
+                      receiver.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          slope: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}{}{}",
+                      BOOSTS,
+                      "_slope_",
+                      delegator.to_formatted_string(),
+                      "_",
+                      receiver.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          ts: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}{}{}",
+                      BOOSTS,
+                      "_ts_",
+                      delegator.to_formatted_string(),
+                      "_",
+                      receiver.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          end: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}{}{}",
+                      BOOSTS,
+                      "_end_",
+                      delegator.to_formatted_string(),
+                      "_",
+                      receiver.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          cancel_time: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}{}{}",
+                      BOOSTS,
+                      "_cancel_time_",
+                      delegator.to_formatted_string(),
+                      "_",
+                      receiver.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+      }
+  }
+
+  pub fn set(&self, delegator: &Key, receiver: &Key, value: Boost) {
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}{}",
+              BOOSTS,
+              "_bias_",
+              delegator.to_formatted_string(),
+              "_",
+              receiver.to_formatted_string()
+          ))
+          .as_str(),
+          value.bias,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}{}",
+              BOOSTS,
+              "_slope_",
+              delegator.to_formatted_string(),
+              "_",
+              receiver.to_formatted_string()
+          ))
+          .as_str(),
+          value.slope,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}{}",
+              BOOSTS,
+              "_ts_",
+              delegator.to_formatted_string(),
+              "_",
+              receiver.to_formatted_string()
+          ))
+          .as_str(),
+          value.ts,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}{}",
+              BOOSTS,
+              "_end_",
+              delegator.to_formatted_string(),
+              "_",
+              receiver.to_formatted_string()
+          ))
+          .as_str(),
+          value.end,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}{}",
+              BOOSTS,
+              "_cancel_time_",
+              delegator.to_formatted_string(),
+              "_",
+              receiver.to_formatted_string()
+          ))
+          .as_str(),
+          value.cancel_time,
+      );
+  }
+}
+
+pub const DELEGATED_BOOST_POINTS: &str = "delegated_boost_points";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct DelegatedBoostPoints {
+  dict: Dict,
+}
+
+impl DelegatedBoostPoints {
+  pub fn instance() -> DelegatedBoostPoints {
+      DelegatedBoostPoints {
+          dict: Dict::instance(DELEGATED_BOOST_POINTS),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(DELEGATED_BOOST_POINTS)
+  }
+
+  pub fn get(&self, addr: &Key) -> Point {
+      Point {
+          bias: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}",
+                      DELEGATED_BOOST_POINTS,
+                      "_bias_",
+                      addr.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          slope: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}",
+                      DELEGATED_BOOST_POINTS,
+                      "_slope_",
+                      addr.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          ts: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}",
+                      DELEGATED_BOOST_POINTS,
+                      "_ts_",
+                      addr.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          blk: Default::default(),
+      }
+  }
+
+  pub fn set(&self, addr: &Key, value: Point) {
+      self.dict.set(
+          hash(format!(
+              "{}{}{}",
+              DELEGATED_BOOST_POINTS,
+              "_bias_",
+              addr.to_formatted_string()
+          ))
+          .as_str(),
+          value.bias,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}",
+              DELEGATED_BOOST_POINTS,
+              "_slope_",
+              addr.to_formatted_string()
+          ))
+          .as_str(),
+          value.slope,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}",
+              DELEGATED_BOOST_POINTS,
+              "_ts_",
+              addr.to_formatted_string()
+          ))
+          .as_str(),
+          value.ts,
+      );
+  }
+}
+
+pub const DELEGATED_SLOPE_CHANGES: &str = "delegated_slope_changes";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct DelegatedSlopeChanges {
+  dict: Dict,
+}
+
+impl DelegatedSlopeChanges {
+  pub fn instance() -> DelegatedSlopeChanges {
+      DelegatedSlopeChanges {
+          dict: Dict::instance(DELEGATED_SLOPE_CHANGES),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(DELEGATED_SLOPE_CHANGES)
+  }
+
+  pub fn get(&self, addr: &Key, time: &U256) -> i128 {
+      let ret: (bool, U128) = self
+          .dict
+          .get(
+              hash(format!(
+                  "{}{}{}{}",
+                  DELEGATED_SLOPE_CHANGES,
+                  addr.to_formatted_string(),
+                  "_",
+                  time
+              ))
+              .as_str(),
+          )
+          .unwrap_or_default();
+      tuple_to_i128(ret)
+  }
+
+  pub fn set(&self, addr: &Key, time: &U256, value: i128) {
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}",
+              DELEGATED_SLOPE_CHANGES,
+              addr.to_formatted_string(),
+              "_",
+              time
+          ))
+          .as_str(),
+          i128_to_tuple(value),
+      );
+  }
+}
+
+pub const RECEIVED_BOOST_POINTS: &str = "received_boost_points";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct ReceivedBoostPoints {
+  dict: Dict,
+}
+
+impl ReceivedBoostPoints {
+  pub fn instance() -> ReceivedBoostPoints {
+      ReceivedBoostPoints {
+          dict: Dict::instance(RECEIVED_BOOST_POINTS),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(RECEIVED_BOOST_POINTS)
+  }
+
+  pub fn get(&self, addr: &Key) -> Point {
+      Point {
+          bias: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}",
+                      RECEIVED_BOOST_POINTS,
+                      "_bias_",
+                      addr.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          slope: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}",
+                      RECEIVED_BOOST_POINTS,
+                      "_slope_",
+                      addr.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          ts: self
+              .dict
+              .get(
+                  hash(format!(
+                      "{}{}{}",
+                      RECEIVED_BOOST_POINTS,
+                      "_ts_",
+                      addr.to_formatted_string()
+                  ))
+                  .as_str(),
+              )
+              .unwrap_or_default(),
+          blk: Default::default(),
+      }
+  }
+
+  pub fn set(&self, addr: &Key, value: Point) {
+      self.dict.set(
+          hash(format!(
+              "{}{}{}",
+              RECEIVED_BOOST_POINTS,
+              "_bias_",
+              addr.to_formatted_string()
+          ))
+          .as_str(),
+          value.bias,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}",
+              RECEIVED_BOOST_POINTS,
+              "_slope_",
+              addr.to_formatted_string()
+          ))
+          .as_str(),
+          value.slope,
+      );
+
+      self.dict.set(
+          hash(format!(
+              "{}{}{}",
+              RECEIVED_BOOST_POINTS,
+              "_ts_",
+              addr.to_formatted_string()
+          ))
+          .as_str(),
+          value.ts,
+      );
+  }
+}
+
+pub const RECEIVED_SLOPE_CHANGES: &str = "received_slope_changes";
+#[derive(CLTyped, ToBytes, FromBytes)]
+pub struct ReceivedSlopeChanges {
+  dict: Dict,
+}
+
+impl ReceivedSlopeChanges {
+  pub fn instance() -> ReceivedSlopeChanges {
+      ReceivedSlopeChanges {
+          dict: Dict::instance(RECEIVED_SLOPE_CHANGES),
+      }
+  }
+
+  pub fn init() {
+      Dict::init(RECEIVED_SLOPE_CHANGES)
+  }
+
+  pub fn get(&self, addr: &Key, time: &U256) -> i128 {
+      let ret: (bool, U128) = self
+          .dict
+          .get(
+              hash(format!(
+                  "{}{}{}{}",
+                  RECEIVED_SLOPE_CHANGES,
+                  addr.to_formatted_string(),
+                  "_",
+                  time
+              ))
+              .as_str(),
+          )
+          .unwrap_or_default();
+      tuple_to_i128(ret)
+  }
+
+  pub fn set(&self, addr: &Key, time: &U256, value: i128) {
+      self.dict.set(
+          hash(format!(
+              "{}{}{}{}",
+              RECEIVED_SLOPE_CHANGES,
+              addr.to_formatted_string(),
+              "_",
+              time
+          ))
+          .as_str(),
+          i128_to_tuple(value),
+      );
+  }
+}
+
 pub fn get_token() -> Key {
   get_key(TOKEN).unwrap_or_else(zero_address)
 }
@@ -415,6 +1038,70 @@ pub fn set_future_admin(future_admin: Key) {
   set_key(FUTURE_ADMIN, future_admin);
 }
 
+pub fn get_smart_wallet_checker() -> Key {
+  get_key(SMART_WALLET_CHECKER).unwrap_or_else(zero_address)
+}
+
+pub fn set_smart_wallet_checker(smart_wallet_checker: Key) {
+  set_key(SMART_WALLET_CHECKER, smart_wallet_checker);
+}
+
+pub fn get_future_smart_wallet_checker() -> Key {
+  get_key(FUTURE_SMART_WALLET_CHECKER).unwrap_or_else(zero_address)
+}
+
+pub fn set_future_smart_wallet_checker(future_smart_wallet_checker: Key) {
+  set_key(FUTURE_SMART_WALLET_CHECKER, future_smart_wallet_checker);
+}
+
+pub fn get_block_height_feeder() -> Key {
+  get_key(BLOCK_HEIGHT_FEEDER).unwrap_or_else(zero_address)
+}
+
+pub fn set_block_height_feeder(block_height_feeder: Key) {
+  set_key(BLOCK_HEIGHT_FEEDER, block_height_feeder);
+}
+
+pub fn get_block_height_epoch() -> U256 {
+  get_key(BLOCK_HEIGHT_EPOCH).unwrap_or_default()
+}
+
+pub fn set_block_height_epoch(block_height_epoch: U256) {
+  set_key(BLOCK_HEIGHT_EPOCH, block_height_epoch);
+}
+
+pub fn get_early_withdraw_enabled() -> bool {
+  get_key(EARLY_WITHDRAW_ENABLED).unwrap_or_default()
+}
+
+pub fn set_early_withdraw_enabled(early_withdraw_enabled: bool) {
+  set_key(EARLY_WITHDRAW_ENABLED, early_withdraw_enabled);
+}
+
+pub fn get_penalty_receiver() -> Key {
+  get_key(PENALTY_RECEIVER).unwrap_or_else(zero_address)
+}
+
+pub fn set_penalty_receiver(penalty_receiver: Key) {
+  set_key(PENALTY_RECEIVER, penalty_receiver);
+}
+
+pub fn get_unlocked() -> bool {
+  get_key(UNLOCKED).unwrap_or_default()
+}
+
+pub fn set_unlocked(unlocked: bool) {
+  set_key(UNLOCKED, unlocked);
+}
+
+pub fn get_unlocked_at() -> U256 {
+  get_key(UNLOCKED_AT).unwrap_or_default()
+}
+
+pub fn set_unlocked_at(unlocked_at: U256) {
+  set_key(UNLOCKED_AT, unlocked_at);
+}
+
 pub fn get_controller() -> Key {
   get_key(CONTROLLER).unwrap_or_else(zero_address)
 }
@@ -503,7 +1190,9 @@ pub fn convert(a: U256, b: U256) -> i128 {
   }
 }
 
-pub fn block_number() -> u64 {
-  const AVG_BLOCK_TIME_IN_MS: u64 = 45000;
-  u64::from(get_blocktime()) / AVG_BLOCK_TIME_IN_MS
+/// Latest block height recorded by the block height feeder, 0 if none was recorded yet
+pub fn block_number() -> U256 {
+  BlockHeightHistory::instance()
+      .get(&get_block_height_epoch())
+      .blk
 }
\ No newline at end of file
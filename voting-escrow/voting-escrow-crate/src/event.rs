@@ -8,6 +8,16 @@ pub enum VotingEscrowEvent {
     ApplyOwnership {
         admin: Key,
     },
+    CommitSmartWalletChecker {
+        checker: Key,
+    },
+    ApplySmartWalletChecker {
+        checker: Key,
+    },
+    ApprovedDepositor {
+        depositor: Key,
+        approved: bool,
+    },
     Deposit {
         provider: Key,
         value: U256,
@@ -18,12 +28,57 @@ pub enum VotingEscrowEvent {
     Withdraw {
         provider: Key,
         value: U256,
+        penalty: U256,
         ts: U256,
     },
     Supply {
         prev_supply: U256,
         supply: U256,
     },
+    DelegateBoost {
+        delegator: Key,
+        receiver: Key,
+        bias: U256,
+        slope: U256,
+        expire_time: U256,
+    },
+    ExtendBoost {
+        delegator: Key,
+        receiver: Key,
+        bias: U256,
+        slope: U256,
+        expire_time: U256,
+    },
+    CancelBoost {
+        delegator: Key,
+        receiver: Key,
+    },
+    MergeLocks {
+        provider: Key,
+        from_id: U256,
+        to_id: U256,
+        value: U256,
+        locktime: U256,
+        ts: U256,
+    },
+    SplitLock {
+        provider: Key,
+        lock_id: U256,
+        new_lock_id: U256,
+        value: U256,
+        locktime: U256,
+        ts: U256,
+    },
+    PerpetualLock {
+        provider: Key,
+        enabled: bool,
+        locktime: U256,
+        ts: U256,
+    },
+    UnlockAll {
+        admin: Key,
+        ts: U256,
+    },
 }
 
 impl VotingEscrowEvent {
@@ -31,6 +86,14 @@ impl VotingEscrowEvent {
         match self {
             VotingEscrowEvent::CommitOwnership { admin: _ } => "commitOwnership",
             VotingEscrowEvent::ApplyOwnership { admin: _ } => "applyOwnership",
+            VotingEscrowEvent::CommitSmartWalletChecker { checker: _ } => {
+                "commitSmartWalletChecker"
+            }
+            VotingEscrowEvent::ApplySmartWalletChecker { checker: _ } => "applySmartWalletChecker",
+            VotingEscrowEvent::ApprovedDepositor {
+                depositor: _,
+                approved: _,
+            } => "approvedDepositor",
             VotingEscrowEvent::Deposit {
                 provider: _,
                 value: _,
@@ -41,12 +104,54 @@ impl VotingEscrowEvent {
             VotingEscrowEvent::Withdraw {
                 provider: _,
                 value: _,
+                penalty: _,
                 ts: _,
             } => "withdraw",
             VotingEscrowEvent::Supply {
                 prev_supply: _,
                 supply: _,
             } => "supply",
+            VotingEscrowEvent::DelegateBoost {
+                delegator: _,
+                receiver: _,
+                bias: _,
+                slope: _,
+                expire_time: _,
+            } => "delegateBoost",
+            VotingEscrowEvent::ExtendBoost {
+                delegator: _,
+                receiver: _,
+                bias: _,
+                slope: _,
+                expire_time: _,
+            } => "extendBoost",
+            VotingEscrowEvent::CancelBoost {
+                delegator: _,
+                receiver: _,
+            } => "cancelBoost",
+            VotingEscrowEvent::MergeLocks {
+                provider: _,
+                from_id: _,
+                to_id: _,
+                value: _,
+                locktime: _,
+                ts: _,
+            } => "mergeLocks",
+            VotingEscrowEvent::SplitLock {
+                provider: _,
+                lock_id: _,
+                new_lock_id: _,
+                value: _,
+                locktime: _,
+                ts: _,
+            } => "splitLock",
+            VotingEscrowEvent::PerpetualLock {
+                provider: _,
+                enabled: _,
+                locktime: _,
+                ts: _,
+            } => "perpetualLock",
+            VotingEscrowEvent::UnlockAll { admin: _, ts: _ } => "unlockAll",
         }
         .to_string()
     }
@@ -4,6 +4,7 @@ use crate::{data::*, event::VotingEscrowEvent};
 use alloc::{
     collections::BTreeMap,
     string::{String, ToString},
+    vec::Vec,
 };
 use casper_contract::{
     contract_api::{
@@ -50,12 +51,20 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         UserPointEpoch::init();
         SlopeChanges::init();
         PointHistory::init();
+        BlockHeightHistory::init();
+        ApprovedDepositors::init();
+        LockCount::init();
+        PerpetualLocks::init();
+        Boosts::init();
+        DelegatedBoostPoints::init();
+        DelegatedSlopeChanges::init();
+        ReceivedBoostPoints::init();
+        ReceivedSlopeChanges::init();
 
         set_admin(self.get_caller());
         set_token(token_addr);
         PointHistory::instance().set(&U256::from(0), {
             let mut point_history: Point = PointHistory::instance().get(&U256::from(0));
-            point_history.blk = block_number().into();
             point_history.ts = U256::from(u64::from(get_blocktime()));
             point_history
         });
@@ -80,6 +89,37 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         set_package_hash(package_hash);
     }
 
+    /// @notice Revert once `unlock_all` was called, no lock can be created or changed anymore
+    fn not_unlocked(&self) {
+        if get_unlocked() {
+            runtime::revert(ApiError::from(Error::VotingEscrowUnlocked));
+        }
+    }
+
+    /// @notice Release every lock: all tokens become withdrawable and voting power drops to zero
+    /// @dev Admin only, this cannot be undone. Voting power before the unlock stays queryable
+    fn unlock_all(&mut self) {
+        self.only_admin();
+        if get_unlocked() {
+            runtime::revert(ApiError::from(Error::VotingEscrowAlreadyUnlocked));
+        }
+        let ts: U256 = U256::from(u64::from(get_blocktime()));
+        set_unlocked(true);
+        set_unlocked_at(ts);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::UnlockAll {
+                admin: self.get_caller(),
+                ts,
+            },
+        );
+    }
+
+    /// @notice Whether `unlock_all` was called at or before time `t`
+    fn _is_unlocked_at(&self, t: U256) -> bool {
+        get_unlocked() && t >= get_unlocked_at()
+    }
+
     fn only_admin(&self) {
         if self.get_caller() != get_admin() {
             runtime::revert(ApiError::from(Error::VotingEscrowAdminOnly));
@@ -102,7 +142,62 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         VOTINGESCROW::emit(self, &VotingEscrowEvent::ApplyOwnership { admin });
     }
 
+    /// @notice Set an external contract to check for approved smart contract wallets
+    /// @param addr Address of Smart contract checker
+    fn commit_smart_wallet_checker(&mut self, addr: Key) {
+        self.only_admin();
+        set_future_smart_wallet_checker(addr);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::CommitSmartWalletChecker { checker: addr },
+        );
+    }
+
+    /// @notice Apply setting external contract to check approved smart contract wallets
+    fn apply_smart_wallet_checker(&mut self) {
+        self.only_admin();
+        let checker: Key = get_future_smart_wallet_checker();
+        set_smart_wallet_checker(checker);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::ApplySmartWalletChecker { checker },
+        );
+    }
+
+    /// @notice Check if the call is from a whitelisted smart contract, revert if not
+    /// @param addr Address to be checked
+    fn assert_not_contract(&self, addr: Key) {
+        if let Key::Hash(_) = addr {
+            let checker: Key = get_smart_wallet_checker();
+            if checker != zero_address() {
+                let approved: bool = runtime::call_versioned_contract(
+                    checker.into_hash().unwrap_or_revert().into(),
+                    None,
+                    "check",
+                    runtime_args! {
+                        "addr" => addr
+                    },
+                );
+                if approved {
+                    return;
+                }
+            }
+            runtime::revert(ApiError::from(
+                Error::VotingEscrowSmartContractDepositorsNotAllowed,
+            ));
+        }
+    }
+
+    /// @dev A perpetual lock reports the slope of a `MAXTIME` lock, so that it votes for gauges like one.
+    ///      Only the primary lock (id 0) votes, additional locks carry no voting power.
+    ///      Nothing votes anymore after `unlock_all`
     fn get_last_user_slope(&self, addr: Key) -> i128 {
+        if get_unlocked() {
+            return 0;
+        }
+        if PerpetualLocks::instance().get(&addr) {
+            return self._slope_of(Locked::instance().get(&addr));
+        }
         let uepoch: U256 = UserPointEpoch::instance().get(&addr);
         UserPointHistory::instance().get(&addr, &uepoch).slope()
     }
@@ -111,24 +206,116 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         UserPointHistory::instance().get(&addr, &idx).ts
     }
 
+    /// @dev A perpetual lock always ends `MAXTIME` from now, rounded down to weeks.
+    ///      This is the end of the primary lock (id 0), which backs gauge votes and boosts.
+    ///      Every lock has ended once `unlock_all` was called
     fn locked_end(&self, addr: Key) -> U256 {
+        if get_unlocked() {
+            return 0.into();
+        }
+        if PerpetualLocks::instance().get(&addr) {
+            return self._max_unlock_time();
+        }
         Locked::instance().get(&addr).end
     }
 
+    /// @notice End of a lock created now for `MAXTIME`, rounded down to weeks
+    fn _max_unlock_time(&self) -> U256 {
+        U256::from(u64::from(get_blocktime()))
+            .checked_add(MAXTIME)
+            .unwrap_or_revert_with(Error::VotingEscrowPerpetualArithmeticError1)
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowPerpetualArithmeticError2)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowPerpetualArithmeticError3)
+    }
+
+    /// @notice Slope of a lock, its amount spread over `MAXTIME`
+    fn _slope_of(&self, locked: LockedBalance) -> i128 {
+        locked
+            .amount()
+            .checked_div(MAXTIME.as_u128().try_into().unwrap())
+            .unwrap_or_revert_with(Error::VotingEscrowPerpetualArithmeticError4)
+    }
+
+    /// @notice Fixed voting power of a perpetual lock, the bias of a fresh `MAXTIME` lock
+    fn _perpetual_bias(&self, locked: LockedBalance) -> i128 {
+        self._slope_of(locked)
+            .checked_mul(MAXTIME.as_u128().try_into().unwrap())
+            .unwrap_or_revert_with(Error::VotingEscrowPerpetualArithmeticError5)
+    }
+
     /// @notice Record global and per-user data to checkpoint
     /// @param addr User's wallet address. No user checkpoint if 0x0
     /// @param old_locked Pevious locked amount / end lock time for the user
     /// @param new_locked New locked amount / end lock time for the user
     fn _checkpoint(&self, addr: Key, old_locked: LockedBalance, new_locked: LockedBalance) {
+        let perpetual: bool = PerpetualLocks::instance().get(&addr);
+        self._checkpoint_perpetual(addr, old_locked, perpetual, new_locked, perpetual)
+    }
+
+    /// @notice Record global and per-user data to checkpoint for a lock of `owner`
+    /// @dev Only the primary lock (id 0) carries voting power. Additional locks stay out of
+    ///      the user and global history, so the fee distributor and gauge votes, which only
+    ///      read the primary lock, see the same total supply as `balance_of`
+    /// @param owner User's wallet address
+    /// @param lock Storage key of the lock, see `lock_key`
+    /// @param old_locked Previous locked amount / end lock time of the lock
+    /// @param new_locked New locked amount / end lock time of the lock
+    fn _checkpoint_lock(
+        &self,
+        owner: Key,
+        lock: Key,
+        old_locked: LockedBalance,
+        new_locked: LockedBalance,
+    ) {
+        if lock == owner {
+            self._checkpoint(lock, old_locked, new_locked);
+        }
+    }
+
+    /// @notice Record global and per-user data to checkpoint, for locks that may be perpetual
+    /// @dev Perpetual locks are stored with a zero end: they have a zero slope and a fixed bias,
+    ///      and never schedule anything in `SlopeChanges`
+    /// @param addr User's wallet address. No user checkpoint if 0x0
+    /// @param old_locked Pevious locked amount / end lock time for the user
+    /// @param old_perpetual Was the previous lock perpetual?
+    /// @param new_locked New locked amount / end lock time for the user
+    /// @param new_perpetual Is the new lock perpetual?
+    fn _checkpoint_perpetual(
+        &self,
+        addr: Key,
+        old_locked: LockedBalance,
+        old_perpetual: bool,
+        new_locked: LockedBalance,
+        new_perpetual: bool,
+    ) {
         let mut u_old: Point = Point::default();
         let mut u_new: Point = Point::default();
         let mut old_dslope: i128 = 0.into();
         let mut new_dslope: i128 = 0.into();
-        let mut epoch: U256 = get_epoch();
         if addr != zero_address() && addr != account_zero_address() {
+            // User operations only fill a bounded number of weeks,
+            // older gaps have to be closed with `checkpoint_bounded` first.
+            // Withdrawals after `unlock_all` are never blocked
+            let epoch: U256 = get_epoch();
+            if epoch > 0.into() && !get_unlocked() {
+                let last_ts: U256 = PointHistory::instance().get(&epoch).ts;
+                if U256::from(u64::from(get_blocktime()))
+                    .checked_sub(last_ts)
+                    .unwrap_or_revert_with(Error::VotingEscrowCheckpointArithmeticError1)
+                    > WEEK
+                        .checked_mul(MAX_CHECKPOINT_WEEKS)
+                        .unwrap_or_revert_with(Error::VotingEscrowCheckpointArithmeticError2)
+                {
+                    runtime::revert(ApiError::from(Error::VotingEscrowCheckpointTooStale));
+                }
+            }
             //  Calculate slopes and biases
             //  Kept at zero when they have to
-            if (old_locked.end > U256::from(u64::from(get_blocktime())))
+            if old_perpetual {
+                u_old.set_bias(self._perpetual_bias(old_locked));
+            } else if (old_locked.end > U256::from(u64::from(get_blocktime())))
                 && (old_locked.amount() > 0.into())
             {
                 u_old.set_slope(
@@ -152,7 +339,9 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                         .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError1),
                 );
             }
-            if (new_locked.end > U256::from(u64::from(get_blocktime())))
+            if new_perpetual {
+                u_new.set_bias(self._perpetual_bias(new_locked));
+            } else if (new_locked.end > U256::from(u64::from(get_blocktime())))
                 && (new_locked.amount() > 0.into())
             {
                 u_new.set_slope(
@@ -188,113 +377,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 }
             }
         }
-        // let mut last_point: Point = Point::default();
-        // last_point.ts = U256::from(u64::from(get_blocktime()));
-        let mut last_point: Point = Point {
-            bias: Default::default(),
-            slope: Default::default(),
-            ts: U256::from(u64::from(get_blocktime())),
-            blk: block_number().into(),
-        };
-        if epoch > 0.into() {
-            last_point = PointHistory::instance().get(&epoch);
-        }
-        let mut last_checkpoint: U256 = last_point.ts;
-        // initial_last_point is used for extrapolation to calculate block number
-        // (approximately, for *At methods) and save them
-        // as we cannot figure that out exactly from inside the contract
-        let initial_last_point: Point = last_point;
-        let mut block_slope: U256 = 0.into();
-        if U256::from(u64::from(get_blocktime())) > last_point.ts {
-            block_slope = MULTIPLIER
-                .checked_mul(
-                    (U256::from(block_number())
-                        .checked_sub(last_point.blk)
-                        .unwrap_or_revert_with(Error::VotingEscrowSubtractionError29))
-                    .checked_div(
-                        U256::from(u64::from(get_blocktime()))
-                            .checked_sub(last_point.ts)
-                            .unwrap_or_revert_with(Error::VotingEscrowSubtractionError30),
-                    )
-                    .unwrap_or_revert_with(Error::VotingEscrowDivisionError10),
-                )
-                .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError15);
-        }
-        // If last point is already recorded in this block, slope=0
-        // But that's ok b/c we know the block in such case
-        let mut t_i: U256 = last_checkpoint
-            .checked_div(WEEK)
-            .unwrap_or_revert_with(Error::VotingEscrowDivisionError3)
-            .checked_mul(WEEK)
-            .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError3);
-        for _ in 0..255 {
-            // Hopefully it won't happen that this won't get used in 5 years!
-            // If it does, users will be able to withdraw but vote weight will be broken
-            t_i = t_i
-                .checked_add(WEEK)
-                .unwrap_or_revert_with(Error::VotingEscrowAdditionError2);
-            let mut d_slope: i128 = 0.into();
-            if t_i > U256::from(u64::from(get_blocktime())) {
-                t_i = U256::from(u64::from(get_blocktime()));
-            } else {
-                d_slope = SlopeChanges::instance().get(&t_i);
-            }
-            last_point.set_bias(
-                last_point
-                    .bias()
-                    .checked_sub(
-                        last_point
-                            .slope()
-                            .checked_mul(
-                                t_i.checked_sub(last_checkpoint)
-                                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError3)
-                                    .as_u128()
-                                    .try_into()
-                                    .unwrap(),
-                            )
-                            .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError4),
-                    )
-                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError4),
-            );
-            last_point.set_slope(
-                last_point
-                    .slope()
-                    .checked_add(d_slope)
-                    .unwrap_or_revert_with(Error::VotingEscrowAdditionError3),
-            );
-            if last_point.bias() < 0.into() {
-                // This can happen
-                last_point.set_bias(0);
-            }
-            if last_point.slope() < 0.into() {
-                // This cannot happen - just in case
-                last_point.set_slope(0);
-            }
-            last_checkpoint = t_i;
-            last_point.ts = t_i;
-            last_point.blk = initial_last_point
-                .blk
-                .checked_add(
-                    block_slope
-                        .checked_mul(
-                            (t_i.checked_sub(initial_last_point.ts)
-                                .unwrap_or_revert_with(Error::VotingEscrowSubtractionError31))
-                            .checked_div(MULTIPLIER)
-                            .unwrap_or_revert_with(Error::VotingEscrowDivisionError11),
-                        )
-                        .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError16),
-                )
-                .unwrap_or_revert_with(Error::VotingEscrowAdditionError27);
-            epoch = epoch
-                .checked_add(1.into())
-                .unwrap_or_revert_with(Error::VotingEscrowAdditionError5);
-            if t_i == U256::from(u64::from(get_blocktime())) {
-                last_point.blk = block_number().into();
-                break;
-            } else {
-                PointHistory::instance().set(&epoch, last_point);
-            }
-        }
+        let (mut last_point, epoch, _) = self._fill_point_history(255.into());
         set_epoch(epoch);
         // Now point_history is filled until t=now
         if addr != zero_address() && addr != account_zero_address() {
@@ -365,19 +448,108 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 .unwrap_or_revert_with(Error::VotingEscrowAdditionError9);
             UserPointEpoch::instance().set(&addr, user_epoch);
             u_new.ts = U256::from(u64::from(get_blocktime()));
-            u_new.blk = block_number().into();
+            u_new.blk = block_number();
             UserPointHistory::instance().set(&addr, &user_epoch, u_new);
         }
     }
 
+    /// @notice Walk the global point forward week by week, recording every full week into `PointHistory`
+    /// @param max_weeks Maximum number of weeks to walk
+    /// @return The last point, its epoch and whether it reached the current time.
+    ///         The last point is only recorded by the caller once it reached the current time
+    fn _fill_point_history(&self, max_weeks: U256) -> (Point, U256, bool) {
+        let mut epoch: U256 = get_epoch();
+        // let mut last_point: Point = Point::default();
+        // last_point.ts = U256::from(u64::from(get_blocktime()));
+        let mut last_point: Point = Point {
+            bias: Default::default(),
+            slope: Default::default(),
+            ts: U256::from(u64::from(get_blocktime())),
+            blk: block_number(),
+        };
+        if epoch > 0.into() {
+            last_point = PointHistory::instance().get(&epoch);
+        }
+        let mut last_checkpoint: U256 = last_point.ts;
+        let mut t_i: U256 = last_checkpoint
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowDivisionError3)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError3);
+        let mut reached_now: bool = false;
+        let mut week: U256 = 0.into();
+        while week < max_weeks {
+            // Hopefully it won't happen that this won't get used in 5 years!
+            // If it does, users will be able to withdraw but vote weight will be broken
+            week = week
+                .checked_add(1.into())
+                .unwrap_or_revert_with(Error::VotingEscrowCheckpointArithmeticError3);
+            t_i = t_i
+                .checked_add(WEEK)
+                .unwrap_or_revert_with(Error::VotingEscrowAdditionError2);
+            let mut d_slope: i128 = 0.into();
+            if t_i > U256::from(u64::from(get_blocktime())) {
+                t_i = U256::from(u64::from(get_blocktime()));
+            } else {
+                d_slope = SlopeChanges::instance().get(&t_i);
+            }
+            last_point.set_bias(
+                last_point
+                    .bias()
+                    .checked_sub(
+                        last_point
+                            .slope()
+                            .checked_mul(
+                                t_i.checked_sub(last_checkpoint)
+                                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError3)
+                                    .as_u128()
+                                    .try_into()
+                                    .unwrap(),
+                            )
+                            .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError4),
+                    )
+                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError4),
+            );
+            last_point.set_slope(
+                last_point
+                    .slope()
+                    .checked_add(d_slope)
+                    .unwrap_or_revert_with(Error::VotingEscrowAdditionError3),
+            );
+            if last_point.bias() < 0.into() {
+                // This can happen
+                last_point.set_bias(0);
+            }
+            if last_point.slope() < 0.into() {
+                // This cannot happen - just in case
+                last_point.set_slope(0);
+            }
+            last_checkpoint = t_i;
+            last_point.ts = t_i;
+            epoch = epoch
+                .checked_add(1.into())
+                .unwrap_or_revert_with(Error::VotingEscrowAdditionError5);
+            if t_i == U256::from(u64::from(get_blocktime())) {
+                last_point.blk = block_number();
+                reached_now = true;
+                break;
+            } else {
+                PointHistory::instance().set(&epoch, last_point);
+            }
+        }
+        (last_point, epoch, reached_now)
+    }
+
     /// @notice Deposit and lock tokens for a user
     /// @param _addr User's wallet address
+    /// @param funder Address the tokens are transferred from
     /// @param _value Amount to deposit
     /// @param unlock_time New time when to unlock the tokens, or 0 if unchanged
     /// @param locked_balance Previous locked amount / timestamp
     fn _deposit_for(
         &mut self,
         addr: Key,
+        funder: Key,
         value: U256,
         unlock_time: U256,
         locked_balance: LockedBalance,
@@ -413,7 +585,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 None,
                 "transfer_from",
                 runtime_args! {
-                    "owner" => Address::from(addr),
+                    "owner" => Address::from(funder),
                     "recipient" => Address::from(Key::from(get_package_hash())),
                     "amount" => value
                 },
@@ -448,11 +620,27 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         )
     }
 
+    /// @notice Record global data to checkpoint, walking at most `max_weeks` weeks
+    /// @dev Call repeatedly to catch up a stale global point before user operations
+    /// @param max_weeks Maximum number of weeks to fill in `PointHistory`
+    fn checkpoint_bounded(&self, max_weeks: U256) {
+        if max_weeks == 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNeedNonZeroWeeks));
+        }
+        let (last_point, epoch, reached_now) = self._fill_point_history(max_weeks);
+        set_epoch(epoch);
+        if reached_now {
+            PointHistory::instance().set(&epoch, last_point);
+        }
+    }
+
     fn deposit_for(&mut self, addr: Key, value: U256) {
         if get_lock() {
             runtime::revert(ApiError::from(Error::VotingEscrowIsLocked1));
         }
         set_lock(true);
+        self.not_unlocked();
+        self.assert_not_contract(self.get_caller());
         let locked: LockedBalance = Locked::instance().get(&addr);
         if value <= 0.into() {
             runtime::revert(ApiError::from(Error::VotingEscrowNeedNonZeroValue1));
@@ -460,12 +648,15 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         if locked.amount() <= 0.into() {
             runtime::revert(ApiError::from(Error::VotingEscrowNoExistingLockFound1));
         }
-        if locked.end <= U256::from(u64::from(get_blocktime())) {
+        if locked.end <= U256::from(u64::from(get_blocktime()))
+            && !PerpetualLocks::instance().get(&addr)
+        {
             runtime::revert(ApiError::from(
                 Error::VotingEscrowCannotAddToExpiredLockWithdraw1,
             ));
         }
         self._deposit_for(
+            addr,
             addr,
             value,
             0.into(),
@@ -480,6 +671,8 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
             runtime::revert(ApiError::from(Error::VotingEscrowIsLocked2));
         }
         set_lock(true);
+        self.not_unlocked();
+        self.assert_not_contract(self.get_caller());
         let unlock_time: U256 = unlock_time
             .checked_div(WEEK)
             .unwrap_or_revert_with(Error::VotingEscrowDivisionError4)
@@ -505,6 +698,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
             runtime::revert(ApiError::from(Error::VotingEscrowVotingLockCanBe4YearsMax1));
         }
         self._deposit_for(
+            self.get_caller(),
             self.get_caller(),
             value,
             unlock_time,
@@ -514,38 +708,112 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         set_lock(false);
     }
 
-    fn increase_amount(&mut self, value: U256) {
+    /// @notice Allow or disallow `addr` to create locks on behalf of other accounts
+    /// @param addr Address of the depositor
+    /// @param approved bool - can `addr` call `create_lock_for`?
+    fn set_approved_depositor(&mut self, addr: Key, approved: bool) {
+        self.only_admin();
+        ApprovedDepositors::instance().set(&addr, approved);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::ApprovedDepositor {
+                depositor: addr,
+                approved,
+            },
+        );
+    }
+
+    /// @notice Deposit `value` tokens of `self.get_caller()` for `addr` and lock until `unlock_time`
+    /// @dev Only callable by approved depositors, `addr` must not have an existing lock
+    /// @param addr User's wallet address
+    /// @param value Amount to deposit
+    /// @param unlock_time Epoch time when tokens unlock, rounded down to whole weeks
+    fn create_lock_for(&mut self, addr: Key, value: U256, unlock_time: U256) {
         if get_lock() {
-            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked3));
+            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked5));
         }
         set_lock(true);
-        let locked: LockedBalance = Locked::instance().get(&self.get_caller());
+        self.not_unlocked();
+        if !ApprovedDepositors::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::VotingEscrowNotApprovedDepositor));
+        }
+        self.assert_not_contract(addr);
+        let unlock_time: U256 = unlock_time
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowDivisionError13)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError17); // Locktime is rounded down to weeks
+        let locked: LockedBalance = Locked::instance().get(&addr);
         if value <= 0.into() {
-            runtime::revert(ApiError::from(Error::VotingEscrowNeedNonZeroValue3));
+            runtime::revert(ApiError::from(Error::VotingEscrowNeedNonZeroValue4));
         }
-        if locked.amount() <= 0.into() {
-            runtime::revert(ApiError::from(Error::VotingEscrowNoExistingLockFound2));
+        if locked.amount() != 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowWithdrawOldTokensFirst2));
         }
-        if locked.end <= U256::from(u64::from(get_blocktime())) {
+        if unlock_time <= U256::from(u64::from(get_blocktime())) {
             runtime::revert(ApiError::from(
-                Error::VotingEscrowCannotAddToExpiredLockWithdraw2,
+                Error::VotingEscrowCanOnlyLockUntilTimeInTheFuture2,
             ));
         }
+        if unlock_time
+            > U256::from(u64::from(get_blocktime()))
+                .checked_add(MAXTIME)
+                .unwrap_or_revert_with(Error::VotingEscrowAdditionError28)
+        {
+            runtime::revert(ApiError::from(Error::VotingEscrowVotingLockCanBe4YearsMax3));
+        }
         self._deposit_for(
+            addr,
             self.get_caller(),
             value,
-            0.into(),
+            unlock_time,
             locked,
-            INCREASE_LOCK_AMOUNT,
+            CREATE_LOCK_FOR_TYPE,
         );
         set_lock(false);
     }
 
-    fn increase_unlock_time(&mut self, unlock_time: U256) {
+    fn increase_amount(&mut self, value: U256) {
         if get_lock() {
-            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked4));
+            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked3));
         }
         set_lock(true);
+        self.not_unlocked();
+        self.assert_not_contract(self.get_caller());
+        let locked: LockedBalance = Locked::instance().get(&self.get_caller());
+        if value <= 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNeedNonZeroValue3));
+        }
+        if locked.amount() <= 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNoExistingLockFound2));
+        }
+        if locked.end <= U256::from(u64::from(get_blocktime()))
+            && !PerpetualLocks::instance().get(&self.get_caller())
+        {
+            runtime::revert(ApiError::from(
+                Error::VotingEscrowCannotAddToExpiredLockWithdraw2,
+            ));
+        }
+        self._deposit_for(
+            self.get_caller(),
+            self.get_caller(),
+            value,
+            0.into(),
+            locked,
+            INCREASE_LOCK_AMOUNT,
+        );
+        set_lock(false);
+    }
+
+    fn increase_unlock_time(&mut self, unlock_time: U256) {
+        if get_lock() {
+            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked4));
+        }
+        set_lock(true);
+        self.not_unlocked();
+        if PerpetualLocks::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLock1));
+        }
         let locked: LockedBalance = Locked::instance().get(&self.get_caller());
         let unlock_time: U256 = unlock_time
             .checked_div(WEEK)
@@ -571,6 +839,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
             runtime::revert(ApiError::from(Error::VotingEscrowVotingLockCanBe4YearsMax2));
         }
         self._deposit_for(
+            self.get_caller(),
             self.get_caller(),
             0.into(),
             unlock_time,
@@ -580,16 +849,82 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         set_lock(false);
     }
 
+    /// @notice Withdraw all tokens for `self.get_caller()`
+    /// @dev Only possible if the lock has expired or `unlock_all` was called
     fn withdraw(&mut self) {
-        let mut locked: LockedBalance = Locked::instance().get(&self.get_caller());
-        if U256::from(u64::from(get_blocktime())) < locked.end {
-            runtime::revert(ApiError::from(Error::VotingEscrowTheLockDidntExpire));
+        let locked: LockedBalance = Locked::instance().get(&self.get_caller());
+        if !get_unlocked() {
+            if PerpetualLocks::instance().get(&self.get_caller()) {
+                runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLock2));
+            }
+            if U256::from(u64::from(get_blocktime())) < locked.end {
+                runtime::revert(ApiError::from(Error::VotingEscrowTheLockDidntExpire));
+            }
+        }
+        self._withdraw(self.get_caller(), locked, 0.into());
+    }
+
+    /// @notice Withdraw all tokens for `self.get_caller()` before the lock expires
+    /// @dev A penalty proportional to the remaining lock time is sent to the penalty receiver.
+    ///      Outgoing boosts must be cancelled first
+    fn withdraw_early(&mut self) {
+        if !get_early_withdraw_enabled() {
+            runtime::revert(ApiError::from(Error::VotingEscrowEarlyWithdrawDisabled));
+        }
+        if PerpetualLocks::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLock3));
         }
+        let locked: LockedBalance = Locked::instance().get(&self.get_caller());
+        if locked.amount() <= 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNothingIsLocked2));
+        }
+        if self.delegated_balance(self.get_caller()) != 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowCancelBoostsFirst));
+        }
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        let mut penalty: U256 = 0.into();
+        // Nothing is locked anymore after `unlock_all`, so there is nothing to penalize
+        if locked.end > now && !get_unlocked() {
+            let value: U256 = locked.amount().try_into().unwrap();
+            penalty = value
+                .checked_mul(
+                    locked
+                        .end
+                        .checked_sub(now)
+                        .unwrap_or_revert_with(Error::VotingEscrowPenaltyArithmeticError1),
+                )
+                .unwrap_or_revert_with(Error::VotingEscrowPenaltyArithmeticError2)
+                .checked_div(MAXTIME)
+                .unwrap_or_revert_with(Error::VotingEscrowPenaltyArithmeticError3);
+        }
+        self._withdraw(self.get_caller(), locked, penalty);
+    }
+
+    /// @notice Allow or disallow withdrawing before the lock expires
+    /// @param enabled bool - is `withdraw_early` callable?
+    fn set_early_withdraw_enabled(&mut self, enabled: bool) {
+        self.only_admin();
+        set_early_withdraw_enabled(enabled);
+    }
+
+    /// @notice Set the address receiving early withdrawal penalties, e.g. the fee distributor
+    /// @param addr Address of the penalty receiver
+    fn set_penalty_receiver(&mut self, addr: Key) {
+        self.only_admin();
+        set_penalty_receiver(addr);
+    }
+
+    /// @notice Clear a lock of `self.get_caller()` and send back its tokens minus `penalty`
+    /// @param lock Storage key of the lock, see `lock_key`
+    /// @param locked Current lock of `self.get_caller()`
+    /// @param penalty Amount of tokens sent to the penalty receiver
+    fn _withdraw(&mut self, lock: Key, locked: LockedBalance, penalty: U256) {
+        let mut locked: LockedBalance = locked;
         let value: U256 = locked.amount().try_into().unwrap();
         let old_locked: LockedBalance = locked;
         locked.end = 0.into();
         locked.set_amount(0);
-        Locked::instance().set(&self.get_caller(), locked);
+        Locked::instance().set(&lock, locked);
         let supply_before: U256 = get_supply();
         set_supply(
             supply_before
@@ -599,7 +934,22 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         // old_locked can have either expired <= timestamp or zero end
         // _locked has only 0 end
         // Both can have >= 0 amount
-        self._checkpoint(self.get_caller(), old_locked, locked);
+        self._checkpoint_lock(self.get_caller(), lock, old_locked, locked);
+        if penalty != 0.into() {
+            let penalty_receiver: Key = get_penalty_receiver();
+            if penalty_receiver == zero_address() || penalty_receiver == account_zero_address() {
+                runtime::revert(ApiError::from(Error::VotingEscrowPenaltyReceiverNotSet));
+            }
+            let _ret: () = runtime::call_versioned_contract(
+                get_token().into_hash().unwrap_or_revert().into(),
+                None,
+                "transfer",
+                runtime_args! {
+                    "recipient" => Address::from(penalty_receiver),
+                    "amount" => penalty
+                },
+            );
+        }
         let _ret: () = runtime::call_versioned_contract(
             get_token().into_hash().unwrap_or_revert().into(),
             None,
@@ -607,6 +957,8 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
             runtime_args! {
                 "recipient" =>Address::from(self.get_caller()),
                 "amount" => value
+                    .checked_sub(penalty)
+                    .unwrap_or_revert_with(Error::VotingEscrowPenaltyArithmeticError4)
             },
         );
 
@@ -615,6 +967,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
             &VotingEscrowEvent::Withdraw {
                 provider: self.get_caller(),
                 value,
+                penalty,
                 ts: U256::from(u64::from(get_blocktime())),
             },
         );
@@ -629,16 +982,297 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         );
     }
 
-    /// The following ERC20/minime-compatible methods are not real balanceOf and supply!
-    /// They measure the weights for the purpose of voting, so they don't represent real coins.
-    /// @notice Binary search to estimate timestamp for block number
-    /// @param _block Block to find
-    /// @param max_epoch Don't go beyond this epoch
-    /// @return Approximate timestamp for block
-    fn _find_block_epoch(&self, block: U256, max_epoch: U256) -> U256 {
+    /// @notice Turn the perpetual max lock of `self.get_caller()` on or off
+    /// @dev While on, the lock is treated as always ending `MAXTIME` from now: its voting power
+    ///      is fixed and it cannot be withdrawn. Turning it off starts a normal `MAXTIME` lock
+    /// @param enabled bool - should the lock of `self.get_caller()` be perpetual?
+    fn set_perpetual_lock(&mut self, enabled: bool) {
+        if get_lock() {
+            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked8));
+        }
+        set_lock(true);
+        self.not_unlocked();
+        let owner: Key = self.get_caller();
+        let perpetual: bool = PerpetualLocks::instance().get(&owner);
+        if perpetual == enabled {
+            runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLockUnchanged));
+        }
+        let locked: LockedBalance = Locked::instance().get(&owner);
+        if locked.amount() <= 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNothingIsLocked5));
+        }
+        let mut new_locked: LockedBalance = locked;
+        if enabled {
+            if locked.end <= U256::from(u64::from(get_blocktime())) {
+                runtime::revert(ApiError::from(Error::VotingEscrowLockExpired4));
+            }
+            if self.delegated_balance(owner) != 0.into() {
+                runtime::revert(ApiError::from(Error::VotingEscrowCancelBoostsFirst4));
+            }
+            new_locked.end = 0.into();
+        } else {
+            new_locked.end = self._max_unlock_time();
+        }
+        PerpetualLocks::instance().set(&owner, enabled);
+        Locked::instance().set(&owner, new_locked);
+        self._checkpoint_perpetual(owner, locked, perpetual, new_locked, enabled);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::PerpetualLock {
+                provider: owner,
+                enabled,
+                locktime: new_locked.end,
+                ts: U256::from(u64::from(get_blocktime())),
+            },
+        );
+        set_lock(false);
+    }
+
+    /// @notice Number of locks of `addr`, the primary lock (id 0) included
+    /// @param addr User's wallet address
+    /// @return Number of lock ids
+    fn lock_count(&self, addr: Key) -> U256 {
+        LockCount::instance()
+            .get(&addr)
+            .checked_add(1.into())
+            .unwrap_or_revert_with(Error::VotingEscrowLockIdArithmeticError1)
+    }
+
+    /// @notice Get the lock `lock_id` of `addr`
+    /// @param addr User's wallet address
+    /// @param lock_id Id of the lock, 0 for the primary lock
+    /// @return Locked amount and end of the lock
+    fn locked_of(&self, addr: Key, lock_id: U256) -> LockedBalance {
+        Locked::instance().get(&lock_key(&addr, &lock_id))
+    }
+
+    /// @notice Withdraw all tokens of the lock `lock_id` of `self.get_caller()`
+    /// @dev Only possible if the lock has expired or `unlock_all` was called
+    /// @param lock_id Id of the lock, 0 for the primary lock
+    fn withdraw_lock(&mut self, lock_id: U256) {
+        if lock_id > LockCount::instance().get(&self.get_caller()) {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidLockId1));
+        }
+        let lock: Key = lock_key(&self.get_caller(), &lock_id);
+        let locked: LockedBalance = Locked::instance().get(&lock);
+        if !get_unlocked() {
+            if PerpetualLocks::instance().get(&lock) {
+                runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLock4));
+            }
+            if U256::from(u64::from(get_blocktime())) < locked.end {
+                runtime::revert(ApiError::from(Error::VotingEscrowTheLockDidntExpire2));
+            }
+        }
+        self._withdraw(lock, locked, 0.into());
+    }
+
+    /// @notice Merge the lock `from_id` of `self.get_caller()` into its lock `to_id`
+    /// @dev The merged lock ends at the later of both ends, `from_id` is left empty.
+    ///      Only the primary lock (id 0) has voting power, merging into it restores the power of `from_id`
+    /// @param from_id Id of the lock to empty
+    /// @param to_id Id of the lock receiving the tokens
+    fn merge_locks(&mut self, from_id: U256, to_id: U256) {
+        if get_lock() {
+            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked6));
+        }
+        set_lock(true);
+        self.not_unlocked();
+        let owner: Key = self.get_caller();
+        let lock_count: U256 = LockCount::instance().get(&owner);
+        if from_id > lock_count || to_id > lock_count {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidLockId2));
+        }
+        if from_id == to_id {
+            runtime::revert(ApiError::from(Error::VotingEscrowCannotMergeLockIntoItself));
+        }
+        let from_lock: Key = lock_key(&owner, &from_id);
+        let to_lock: Key = lock_key(&owner, &to_id);
+        if PerpetualLocks::instance().get(&from_lock) || PerpetualLocks::instance().get(&to_lock) {
+            runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLock5));
+        }
+        let from_locked: LockedBalance = Locked::instance().get(&from_lock);
+        let to_locked: LockedBalance = Locked::instance().get(&to_lock);
+        if from_locked.amount() <= 0.into() || to_locked.amount() <= 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNothingIsLocked3));
+        }
+        let end: U256 = core::cmp::max(from_locked.end, to_locked.end);
+        if end <= U256::from(u64::from(get_blocktime())) {
+            runtime::revert(ApiError::from(Error::VotingEscrowLockExpired2));
+        }
+        if from_id == 0.into() && self.delegated_balance(owner) != 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowCancelBoostsFirst2));
+        }
+        let mut merged: LockedBalance = to_locked;
+        merged.set_amount(
+            to_locked
+                .amount()
+                .checked_add(from_locked.amount())
+                .unwrap_or_revert_with(Error::VotingEscrowLockIdArithmeticError2),
+        );
+        merged.end = end;
+        Locked::instance().set(&from_lock, LockedBalance::default());
+        self._checkpoint_lock(owner, from_lock, from_locked, LockedBalance::default());
+        Locked::instance().set(&to_lock, merged);
+        self._checkpoint_lock(owner, to_lock, to_locked, merged);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::MergeLocks {
+                provider: owner,
+                from_id,
+                to_id,
+                value: from_locked.amount().try_into().unwrap(),
+                locktime: end,
+                ts: U256::from(u64::from(get_blocktime())),
+            },
+        );
+        set_lock(false);
+    }
+
+    /// @notice Move `value` tokens of the lock `lock_id` of `self.get_caller()` into a new lock with the same end
+    /// @dev Only the primary lock (id 0) has voting power, tokens split off it stop voting until merged back.
+    ///      Emptied locks are reused first, an account never holds lock ids above `MAX_LOCK_ID`
+    /// @param lock_id Id of the lock to split
+    /// @param value Amount of tokens to move into the new lock
+    /// @return Id of the new lock
+    fn split_lock(&mut self, lock_id: U256, value: U256) -> U256 {
+        if get_lock() {
+            runtime::revert(ApiError::from(Error::VotingEscrowIsLocked7));
+        }
+        set_lock(true);
+        self.not_unlocked();
+        let owner: Key = self.get_caller();
+        let lock_count: U256 = LockCount::instance().get(&owner);
+        if lock_id > lock_count {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidLockId3));
+        }
+        let lock: Key = lock_key(&owner, &lock_id);
+        if PerpetualLocks::instance().get(&lock) {
+            runtime::revert(ApiError::from(Error::VotingEscrowPerpetualLock6));
+        }
+        let locked: LockedBalance = Locked::instance().get(&lock);
+        if value == 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNeedNonZeroValue5));
+        }
+        if locked.amount() <= 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowNothingIsLocked4));
+        }
+        let amount: U256 = locked.amount().try_into().unwrap();
+        if value >= amount {
+            runtime::revert(ApiError::from(Error::VotingEscrowSplitExceedsLock));
+        }
+        if locked.end <= U256::from(u64::from(get_blocktime())) {
+            runtime::revert(ApiError::from(Error::VotingEscrowLockExpired3));
+        }
+        if lock_id == 0.into() && self.delegated_balance(owner) != 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowCancelBoostsFirst3));
+        }
+        let new_lock_id: U256 = match self._free_lock_id(owner, lock_count) {
+            Some(free_lock_id) => free_lock_id,
+            None => {
+                let new_lock_id: U256 = lock_count
+                    .checked_add(1.into())
+                    .unwrap_or_revert_with(Error::VotingEscrowLockIdArithmeticError3);
+                if new_lock_id > MAX_LOCK_ID {
+                    runtime::revert(ApiError::from(Error::VotingEscrowTooManyLocks));
+                }
+                LockCount::instance().set(&owner, new_lock_id);
+                new_lock_id
+            }
+        };
+        let mut remaining: LockedBalance = locked;
+        remaining.set_amount(
+            amount
+                .checked_sub(value)
+                .unwrap_or_revert_with(Error::VotingEscrowLockIdArithmeticError4)
+                .as_u128()
+                .try_into()
+                .unwrap(),
+        );
+        let mut split: LockedBalance = LockedBalance::default();
+        split.set_amount(value.as_u128().try_into().unwrap());
+        split.end = locked.end;
+        Locked::instance().set(&lock, remaining);
+        self._checkpoint_lock(owner, lock, locked, remaining);
+        let new_lock: Key = lock_key(&owner, &new_lock_id);
+        Locked::instance().set(&new_lock, split);
+        self._checkpoint_lock(owner, new_lock, LockedBalance::default(), split);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::SplitLock {
+                provider: owner,
+                lock_id,
+                new_lock_id,
+                value,
+                locktime: locked.end,
+                ts: U256::from(u64::from(get_blocktime())),
+            },
+        );
+        set_lock(false);
+        new_lock_id
+    }
+
+    /// @notice Lowest additional lock id of `owner` that was emptied by a withdrawal or merge
+    /// @param owner User's wallet address
+    /// @param lock_count Highest lock id of `owner`
+    fn _free_lock_id(&self, owner: Key, lock_count: U256) -> Option<U256> {
+        let mut lock_id: U256 = 1.into();
+        while lock_id <= lock_count {
+            if Locked::instance().get(&lock_key(&owner, &lock_id)).amount() == 0 {
+                return Some(lock_id);
+            }
+            lock_id = lock_id
+                .checked_add(1.into())
+                .unwrap_or_revert_with(Error::VotingEscrowLockIdArithmeticError9);
+        }
+        None
+    }
+
+    /// @notice Record the Casper block height the current deploy is executed in
+    /// @dev Only callable by the trusted block height feeder. Heights must be strictly increasing
+    /// @param block Block height of the current deploy
+    fn record_block_height(&mut self, block: U256) {
+        if self.get_caller() != get_block_height_feeder() {
+            runtime::revert(ApiError::from(Error::VotingEscrowBlockHeightFeederOnly));
+        }
+        let idx: U256 = get_block_height_epoch();
+        if block <= BlockHeightHistory::instance().get(&idx).blk {
+            runtime::revert(ApiError::from(Error::VotingEscrowBlockHeightMustIncrease));
+        }
+        let idx: U256 = idx
+            .checked_add(1.into())
+            .unwrap_or_revert_with(Error::VotingEscrowBlockHeightArithmeticError1);
+        BlockHeightHistory::instance().set(
+            &idx,
+            BlockHeight {
+                blk: block,
+                ts: U256::from(u64::from(get_blocktime())),
+            },
+        );
+        set_block_height_epoch(idx);
+    }
+
+    /// @notice Set the trusted account or contract allowed to record block heights
+    /// @param addr Address of the block height feeder
+    fn set_block_height_feeder(&mut self, addr: Key) {
+        self.only_admin();
+        set_block_height_feeder(addr);
+    }
+
+    /// @notice Binary search to find the timestamp of a block height
+    /// @dev Interpolates between the heights recorded by the block height feeder
+    /// @param block Block to find
+    /// @return Timestamp for block
+    fn _block_timestamp(&self, block: U256) -> U256 {
+        let max_idx: U256 = get_block_height_epoch();
+        if max_idx == 0.into() {
+            runtime::revert(ApiError::from(Error::VotingEscrowBlockHeightNotRecorded));
+        }
+        if block > BlockHeightHistory::instance().get(&max_idx).blk {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBlockNumber1));
+        }
         // Binary search
         let mut min: U256 = 0.into();
-        let mut max: U256 = max_epoch;
+        let mut max: U256 = max_idx;
         for _ in 0..128 {
             // Will be always enough for 128-bit numbers
             if min >= max {
@@ -651,7 +1285,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 .unwrap_or_revert_with(Error::VotingEscrowAdditionError16)
                 .checked_div(2.into())
                 .unwrap_or_revert_with(Error::VotingEscrowAdditionError17);
-            if PointHistory::instance().get(&mid).blk <= block {
+            if BlockHeightHistory::instance().get(&mid).blk <= block {
                 min = mid;
             } else {
                 max = mid
@@ -659,6 +1293,71 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                     .unwrap_or_revert_with(Error::VotingEscrowSubtractionError11);
             }
         }
+        if min == 0.into() {
+            // Block is older than the first recorded height
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBlockNumber2));
+        }
+        let point_0: BlockHeight = BlockHeightHistory::instance().get(&min);
+        if min == max_idx {
+            return point_0.ts;
+        }
+        let point_1: BlockHeight = BlockHeightHistory::instance().get(
+            &min.checked_add(1.into())
+                .unwrap_or_revert_with(Error::VotingEscrowAdditionError21),
+        );
+        let d_block: U256 = point_1
+            .blk
+            .checked_sub(point_0.blk)
+            .unwrap_or_revert_with(Error::VotingEscrowSubtractionError32);
+        let d_t: U256 = point_1
+            .ts
+            .checked_sub(point_0.ts)
+            .unwrap_or_revert_with(Error::VotingEscrowSubtractionError15);
+        point_0
+            .ts
+            .checked_add(
+                d_t.checked_mul(
+                    block
+                        .checked_sub(point_0.blk)
+                        .unwrap_or_revert_with(Error::VotingEscrowSubtractionError17),
+                )
+                .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError8)
+                .checked_div(d_block)
+                .unwrap_or_revert_with(Error::VotingEscrowDivisionError6),
+            )
+            .unwrap_or_revert_with(Error::VotingEscrowAdditionError22)
+    }
+
+    /// The following ERC20/minime-compatible methods are not real balanceOf and supply!
+    /// They measure the weights for the purpose of voting, so they don't represent real coins.
+    /// @notice Binary search to find the epoch of a timestamp
+    /// @param t Timestamp to find
+    /// @param max_epoch Don't go beyond this epoch
+    /// @return Last epoch recorded at or before `t`
+    fn _find_timestamp_epoch(&self, t: U256, max_epoch: U256) -> U256 {
+        // Binary search
+        let mut min: U256 = 0.into();
+        let mut max: U256 = max_epoch;
+        for _ in 0..128 {
+            // Will be always enough for 128-bit numbers
+            if min >= max {
+                break;
+            }
+            let mid: U256 = min
+                .checked_add(max)
+                .unwrap_or_revert_with(Error::VotingEscrowFindTimestampEpochError1)
+                .checked_add(1.into())
+                .unwrap_or_revert_with(Error::VotingEscrowFindTimestampEpochError2)
+                .checked_div(2.into())
+                .unwrap_or_revert_with(Error::VotingEscrowFindTimestampEpochError3);
+            if PointHistory::instance().get(&mid).ts <= t {
+                min = mid;
+            } else {
+                max = mid
+                    .checked_sub(1.into())
+                    .unwrap_or_revert_with(Error::VotingEscrowFindTimestampEpochError4);
+            }
+        }
         min
     }
 
@@ -670,12 +1369,26 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 blocktime.into()
             }
         };
+        if get_unlocked() {
+            if self._is_unlocked_at(t) {
+                return 0.into();
+            }
+            // Locks may have been withdrawn since, so look the voting power up in the history
+            return self.balance_of_at_time(addr, t);
+        }
+        // Additional locks carry no voting power, see `_checkpoint_lock`
+        self._lock_balance_of(addr, t)
+    }
 
-        let epoch: U256 = UserPointEpoch::instance().get(&addr);
+    /// @notice Voting power of a single lock at time `t`
+    /// @param lock Storage key of the lock, see `lock_key`
+    /// @param t Epoch time to return voting power at
+    fn _lock_balance_of(&self, lock: Key, t: U256) -> U256 {
+        let epoch: U256 = UserPointEpoch::instance().get(&lock);
         if epoch == 0.into() {
             0.into()
         } else {
-            let mut last_point: Point = UserPointHistory::instance().get(&addr, &epoch);
+            let mut last_point: Point = UserPointHistory::instance().get(&lock, &epoch);
             last_point.set_bias(
                 last_point
                     .bias()
@@ -700,14 +1413,37 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
         }
     }
 
-    #[allow(unused_assignments)]
+    /// @notice Measure voting power of `addr` at block height `block`
+    /// @dev Adheres to MiniMe `balanceOfAt` interface, the height must have been covered by the block height feeder
+    /// @param addr User's wallet address
+    /// @param block Block to calculate the voting power at
+    /// @return Voting power
     fn balance_of_at(&self, addr: Key, block: U256) -> U256 {
-        if block > block_number().into() {
-            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBlockNumber1));
+        self.balance_of_at_time(addr, self._block_timestamp(block))
+    }
+
+    /// @notice Measure voting power of `addr` at a past timestamp `t`
+    /// @param addr User's wallet address
+    /// @param t Epoch time to calculate the voting power at
+    /// @return Voting power
+    fn balance_of_at_time(&self, addr: Key, t: U256) -> U256 {
+        if t > U256::from(u64::from(get_blocktime())) {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidTimestamp1));
+        }
+        if self._is_unlocked_at(t) {
+            return 0.into();
         }
+        // Additional locks carry no voting power, see `_checkpoint_lock`
+        self._lock_balance_of_at_time(addr, t)
+    }
+
+    /// @notice Voting power of a single lock at a past timestamp `t`
+    /// @param lock Storage key of the lock, see `lock_key`
+    /// @param t Epoch time to calculate the voting power at
+    fn _lock_balance_of_at_time(&self, lock: Key, t: U256) -> U256 {
         // Binary search
         let mut min: U256 = 0.into();
-        let mut max: U256 = UserPointEpoch::instance().get(&addr);
+        let mut max: U256 = UserPointEpoch::instance().get(&lock);
         for _ in 0..128 {
             // Will be always enough for 128-bit numbers
             if min >= max {
@@ -720,7 +1456,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 .unwrap_or_revert_with(Error::VotingEscrowAdditionError19)
                 .checked_div(2.into())
                 .unwrap_or_revert_with(Error::VotingEscrowAdditionError20);
-            if UserPointHistory::instance().get(&addr, &mid).blk <= block {
+            if UserPointHistory::instance().get(&lock, &mid).ts <= t {
                 min = mid;
             } else {
                 max = mid
@@ -728,56 +1464,14 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                     .unwrap_or_revert_with(Error::VotingEscrowSubtractionError14);
             }
         }
-        let mut upoint: Point = UserPointHistory::instance().get(&addr, &min);
-        let max_epoch: U256 = get_epoch();
-        let epoch: U256 = self._find_block_epoch(block, max_epoch);
-        let point_0: Point = PointHistory::instance().get(&epoch);
-        let mut d_block: U256 = 0.into();
-        let mut d_t: U256 = 0.into();
-        if epoch < max_epoch {
-            let point_1: Point = PointHistory::instance().get(
-                &epoch
-                    .checked_add(1.into())
-                    .unwrap_or_revert_with(Error::VotingEscrowAdditionError21),
-            );
-            d_block = point_1
-                .blk
-                .checked_sub(point_0.blk)
-                .unwrap_or_revert_with(Error::VotingEscrowSubtractionError32);
-            d_t = point_1
-                .ts
-                .checked_sub(point_0.ts)
-                .unwrap_or_revert_with(Error::VotingEscrowSubtractionError15);
-        } else {
-            d_block = U256::from(block_number())
-                .checked_sub(point_0.blk)
-                .unwrap_or_revert_with(Error::VotingEscrowSubtractionError33);
-            d_t = U256::from(u64::from(get_blocktime()))
-                .checked_sub(point_0.ts)
-                .unwrap_or_revert_with(Error::VotingEscrowSubtractionError16);
-        }
-        let mut block_time: U256 = point_0.ts;
-        if d_block != 0.into() {
-            block_time = block_time
-                .checked_add(
-                    d_t.checked_mul(
-                        block
-                            .checked_sub(point_0.blk)
-                            .unwrap_or_revert_with(Error::VotingEscrowSubtractionError17),
-                    )
-                    .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError8)
-                    .checked_div(d_block)
-                    .unwrap_or_revert_with(Error::VotingEscrowDivisionError6),
-                )
-                .unwrap_or_revert_with(Error::VotingEscrowAdditionError22);
-        }
+        let mut upoint: Point = UserPointHistory::instance().get(&lock, &min);
         upoint.set_bias(
             upoint
                 .bias()
                 .checked_sub(
                     upoint
                         .slope()
-                        .checked_mul(convert(block_time, upoint.ts))
+                        .checked_mul(convert(t, upoint.ts))
                         .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError9),
                 )
                 .unwrap_or_revert_with(Error::VotingEscrowSubtractionError18),
@@ -847,70 +1541,476 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 blocktime.into()
             }
         };
+        if get_unlocked() {
+            if self._is_unlocked_at(t) {
+                return 0.into();
+            }
+            return self.total_supply_at_time(t);
+        }
 
         let epoch: U256 = get_epoch();
         let last_point: Point = PointHistory::instance().get(&epoch);
         self._supply_at(last_point, t)
     }
 
+    /// @notice Calculate total voting power at block height `block`
+    /// @dev Adheres to the ERC20 `totalSupplyAt` interface, the height must have been covered by the block height feeder
+    /// @param block Block to calculate the total voting power at
+    /// @return Total voting power at `block`
     fn total_supply_at(&self, block: U256) -> U256 {
-        if block > block_number().into() {
-            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBlockNumber2));
+        self.total_supply_at_time(self._block_timestamp(block))
+    }
+
+    /// @notice Calculate total voting power at a past timestamp `t`
+    /// @param t Epoch time to calculate the total voting power at
+    /// @return Total voting power at `t`
+    fn total_supply_at_time(&self, t: U256) -> U256 {
+        if t > U256::from(u64::from(get_blocktime())) {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidTimestamp2));
+        }
+        if self._is_unlocked_at(t) {
+            return 0.into();
         }
         let epoch: U256 = get_epoch();
-        let target_epoch: U256 = self._find_block_epoch(block, epoch);
+        let target_epoch: U256 = self._find_timestamp_epoch(t, epoch);
         let point: Point = PointHistory::instance().get(&target_epoch);
-        let mut dt: U256 = 0.into();
-        if target_epoch < epoch {
-            let point_next: Point = PointHistory::instance().get(
-                &target_epoch
-                    .checked_add(1.into())
-                    .unwrap_or_revert_with(Error::VotingEscrowAdditionError25),
-            );
-            if point.blk != point_next.blk {
-                dt = block
-                    .checked_sub(point.blk)
-                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError21)
-                    .checked_mul(
-                        point_next
-                            .ts
-                            .checked_sub(point.ts)
-                            .unwrap_or_revert_with(Error::VotingEscrowSubtractionError22),
-                    )
-                    .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError11)
-                    .checked_div(
-                        point_next
-                            .blk
-                            .checked_sub(point.blk)
-                            .unwrap_or_revert_with(Error::VotingEscrowSubtractionError23),
+        self._supply_at(point, t)
+    }
+
+    /// @notice Expand a (start, end, step) range into the timestamps it covers
+    /// @param start First timestamp of the range
+    /// @param end Last timestamp of the range, included when hit by a step
+    /// @param step Distance between two timestamps
+    /// @return Timestamps `start`, `start + step`, ... up to `end`
+    fn _time_range(&self, start: U256, end: U256, step: U256) -> Vec<U256> {
+        if step == 0.into() || start > end {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidTimeRange));
+        }
+        let mut ts: Vec<U256> = Vec::new();
+        let mut t: U256 = start;
+        while t <= end {
+            if U256::from(ts.len()) >= MAX_HISTORY_POINTS {
+                runtime::revert(ApiError::from(Error::VotingEscrowTooManyTimestamps1));
+            }
+            ts.push(t);
+            t = t
+                .checked_add(step)
+                .unwrap_or_revert_with(Error::VotingEscrowHistoryArithmeticError1);
+        }
+        ts
+    }
+
+    /// @notice Measure voting power of `addr` at several past timestamps
+    /// @param addr User's wallet address
+    /// @param ts Epoch times to calculate the voting power at
+    /// @return Voting power at each of `ts`
+    fn balance_of_at_times(&self, addr: Key, ts: Vec<U256>) -> Vec<U256> {
+        if U256::from(ts.len()) > MAX_HISTORY_POINTS {
+            runtime::revert(ApiError::from(Error::VotingEscrowTooManyTimestamps2));
+        }
+        ts.into_iter()
+            .map(|t| self.balance_of_at_time(addr, t))
+            .collect()
+    }
+
+    /// @notice Measure voting power of `addr` over a range of past timestamps
+    /// @param addr User's wallet address
+    /// @param start First timestamp of the range
+    /// @param end Last timestamp of the range
+    /// @param step Distance between two timestamps
+    /// @return Voting power at `start`, `start + step`, ... up to `end`
+    fn balance_of_range(&self, addr: Key, start: U256, end: U256, step: U256) -> Vec<U256> {
+        self.balance_of_at_times(addr, self._time_range(start, end, step))
+    }
+
+    /// @notice Calculate total voting power at several past timestamps
+    /// @param ts Epoch times to calculate the total voting power at
+    /// @return Total voting power at each of `ts`
+    fn total_supply_at_times(&self, ts: Vec<U256>) -> Vec<U256> {
+        if U256::from(ts.len()) > MAX_HISTORY_POINTS {
+            runtime::revert(ApiError::from(Error::VotingEscrowTooManyTimestamps3));
+        }
+        ts.into_iter()
+            .map(|t| self.total_supply_at_time(t))
+            .collect()
+    }
+
+    /// @notice Calculate total voting power over a range of past timestamps
+    /// @param start First timestamp of the range
+    /// @param end Last timestamp of the range
+    /// @param step Distance between two timestamps
+    /// @return Total voting power at `start`, `start + step`, ... up to `end`
+    fn total_supply_range(&self, start: U256, end: U256, step: U256) -> Vec<U256> {
+        self.total_supply_at_times(self._time_range(start, end, step))
+    }
+
+    /// @notice Extrapolate an aggregated boost point to time `t`
+    /// @dev Applies the slope changes scheduled at every week boundary on the way
+    /// @param point Delegated or received boost point to start from
+    /// @param t Time to extrapolate to, not earlier than `point.ts`
+    /// @param slope_changes Scheduled slope changes of the account the point belongs to
+    /// @return Point with its bias and slope as of `t`
+    fn _boost_point_at<F: Fn(&U256) -> i128>(
+        &self,
+        point: Point,
+        t: U256,
+        slope_changes: F,
+    ) -> Point {
+        let mut last_point: Point = point;
+        if t <= last_point.ts {
+            return last_point;
+        }
+        let mut t_i: U256 = last_point
+            .ts
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError1)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError2);
+        for _ in 0..255 {
+            if last_point.slope() == 0 {
+                // Nothing is delegated anymore, so nothing is scheduled either
+                last_point.ts = t;
+                break;
+            }
+            t_i = t_i
+                .checked_add(WEEK)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError3);
+            let mut d_slope: i128 = 0;
+            if t_i > t {
+                t_i = t;
+            } else {
+                d_slope = slope_changes(&t_i);
+            }
+            last_point.set_bias(
+                last_point
+                    .bias()
+                    .checked_sub(
+                        last_point
+                            .slope()
+                            .checked_mul(convert(t_i, last_point.ts))
+                            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError4),
                     )
-                    .unwrap_or_revert_with(Error::VotingEscrowDivisionError8);
-            }
-        } else if point.blk != block_number().into() {
-            dt = (block
-                .checked_sub(point.blk)
-                .unwrap_or_revert_with(Error::VotingEscrowSubtractionError34))
-            .checked_mul(
-                U256::from(u64::from(get_blocktime()))
-                    .checked_sub(point.ts)
-                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError24),
-            )
-            .unwrap_or_revert_with(Error::VotingEscrowMultiplicationError12)
-            .checked_div(
-                U256::from(block_number())
-                    .checked_sub(point.blk)
-                    .unwrap_or_revert_with(Error::VotingEscrowSubtractionError35),
-            )
-            .unwrap_or_revert_with(Error::VotingEscrowDivisionError12);
-        }
-        // Now dt contains info on how far are we beyond point
-        self._supply_at(
-            point,
-            point
-                .ts
-                .checked_add(dt)
-                .unwrap_or_revert_with(Error::VotingEscrowAdditionError26),
-        )
+                    .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError5),
+            );
+            last_point.set_slope(
+                last_point
+                    .slope()
+                    .checked_add(d_slope)
+                    .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError6),
+            );
+            last_point.ts = t_i;
+            if t_i == t {
+                break;
+            }
+        }
+        if last_point.bias() < 0 {
+            last_point.set_bias(0);
+        }
+        if last_point.slope() < 0 {
+            last_point.set_slope(0);
+        }
+        last_point
+    }
+
+    fn _delegated_point(&self, addr: Key, t: U256) -> Point {
+        self._boost_point_at(DelegatedBoostPoints::instance().get(&addr), t, |time| {
+            DelegatedSlopeChanges::instance().get(&addr, time)
+        })
+    }
+
+    fn _received_point(&self, addr: Key, t: U256) -> Point {
+        self._boost_point_at(ReceivedBoostPoints::instance().get(&addr), t, |time| {
+            ReceivedSlopeChanges::instance().get(&addr, time)
+        })
+    }
+
+    /// @notice Add a boost to (or, with negative `bias` and `slope`, remove it from)
+    ///         the aggregated points of its delegator and receiver
+    /// @param delegator Account the boost is delegated from
+    /// @param receiver Account the boost is delegated to
+    /// @param bias Current value of the boost
+    /// @param slope Rate of decrease of the boost
+    /// @param end Time the boost expires
+    fn _update_boost_points(
+        &self,
+        delegator: Key,
+        receiver: Key,
+        bias: i128,
+        slope: i128,
+        end: U256,
+    ) {
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        let mut delegated: Point = self._delegated_point(delegator, now);
+        delegated.set_bias(
+            delegated
+                .bias()
+                .checked_add(bias)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError7),
+        );
+        delegated.set_slope(
+            delegated
+                .slope()
+                .checked_add(slope)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError8),
+        );
+        DelegatedBoostPoints::instance().set(&delegator, delegated);
+        DelegatedSlopeChanges::instance().set(
+            &delegator,
+            &end,
+            DelegatedSlopeChanges::instance()
+                .get(&delegator, &end)
+                .checked_sub(slope)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError9),
+        );
+
+        let mut received: Point = self._received_point(receiver, now);
+        received.set_bias(
+            received
+                .bias()
+                .checked_add(bias)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError10),
+        );
+        received.set_slope(
+            received
+                .slope()
+                .checked_add(slope)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError11),
+        );
+        ReceivedBoostPoints::instance().set(&receiver, received);
+        ReceivedSlopeChanges::instance().set(
+            &receiver,
+            &end,
+            ReceivedSlopeChanges::instance()
+                .get(&receiver, &end)
+                .checked_sub(slope)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError12),
+        );
+    }
+
+    /// @notice Build a boost of `percentage` of the undelegated voting power of `delegator`
+    /// @param delegator Account the boost is delegated from
+    /// @param percentage Share of the undelegated voting power in bps (10000 == 100%)
+    /// @param cancel_time Time before which only the receiver can cancel the boost
+    /// @param expire_time Time the boost expires, rounded down to whole weeks
+    /// @return Boost decaying linearly from now to `expire_time`
+    fn _new_boost(
+        &self,
+        delegator: Key,
+        percentage: U256,
+        cancel_time: U256,
+        expire_time: U256,
+    ) -> Boost {
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        if percentage == 0.into() || percentage > MAX_BOOST_PERCENTAGE {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBoostPercentage));
+        }
+        let expire_time: U256 = expire_time
+            .checked_div(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError13)
+            .checked_mul(WEEK)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError14); // Expiry is rounded down to weeks
+        if expire_time <= now || expire_time > self.locked_end(delegator) {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBoostExpireTime));
+        }
+        if cancel_time > expire_time {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBoostCancelTime));
+        }
+        // Boosts are backed by the primary lock only, as their expiry is bounded by its end
+        let balance: U256 = self._lock_balance_of(delegator, now);
+        let delegated: U256 = self.delegated_balance(delegator);
+        if balance <= delegated {
+            runtime::revert(ApiError::from(Error::VotingEscrowNoBoostAvailable));
+        }
+        let value: U256 = balance
+            .checked_sub(delegated)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError15)
+            .checked_mul(percentage)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError16)
+            .checked_div(MAX_BOOST_PERCENTAGE)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError17);
+        let duration: U256 = expire_time
+            .checked_sub(now)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError18);
+        let slope: i128 = value
+            .checked_div(duration)
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError19)
+            .as_u128()
+            .try_into()
+            .unwrap();
+        if slope == 0 {
+            runtime::revert(ApiError::from(Error::VotingEscrowNoBoostAvailable));
+        }
+        let mut boost: Boost = Boost {
+            bias: Default::default(),
+            slope: Default::default(),
+            ts: now,
+            end: expire_time,
+            cancel_time,
+        };
+        boost.set_slope(slope);
+        // Like user points, the bias is derived from the slope so that it reaches zero exactly at expiry
+        boost.set_bias(
+            slope
+                .checked_mul(duration.as_u128().try_into().unwrap())
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError20),
+        );
+        boost
+    }
+
+    /// @notice Current value of a boost, zero once it expired
+    fn _boost_bias(&self, boost: Boost) -> i128 {
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        if boost.end <= now {
+            0
+        } else {
+            boost
+                .slope()
+                .checked_mul(convert(boost.end, now))
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError21)
+        }
+    }
+
+    fn create_boost(
+        &mut self,
+        receiver: Key,
+        percentage: U256,
+        cancel_time: U256,
+        expire_time: U256,
+    ) {
+        self.not_unlocked();
+        let delegator: Key = self.get_caller();
+        if receiver == delegator {
+            runtime::revert(ApiError::from(Error::VotingEscrowCannotBoostSelf));
+        }
+        if Boosts::instance().get(&delegator, &receiver).end
+            > U256::from(u64::from(get_blocktime()))
+        {
+            runtime::revert(ApiError::from(Error::VotingEscrowBoostAlreadyExists));
+        }
+        let boost: Boost = self._new_boost(delegator, percentage, cancel_time, expire_time);
+        Boosts::instance().set(&delegator, &receiver, boost);
+        self._update_boost_points(delegator, receiver, boost.bias(), boost.slope(), boost.end);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::DelegateBoost {
+                delegator,
+                receiver,
+                bias: boost.bias().try_into().unwrap(),
+                slope: boost.slope().try_into().unwrap(),
+                expire_time: boost.end,
+            },
+        );
+    }
+
+    fn extend_boost(
+        &mut self,
+        receiver: Key,
+        percentage: U256,
+        cancel_time: U256,
+        expire_time: U256,
+    ) {
+        self.not_unlocked();
+        let delegator: Key = self.get_caller();
+        let old_boost: Boost = Boosts::instance().get(&delegator, &receiver);
+        if old_boost.end <= U256::from(u64::from(get_blocktime())) {
+            runtime::revert(ApiError::from(Error::VotingEscrowNoActiveBoost));
+        }
+        if expire_time < old_boost.end {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBoostExpireTime));
+        }
+        // The receiver keeps the guarantee it was given
+        if cancel_time < old_boost.cancel_time {
+            runtime::revert(ApiError::from(Error::VotingEscrowInvalidBoostCancelTime));
+        }
+        // Release the old boost first so that its power is available again
+        let old_bias: i128 = self._boost_bias(old_boost);
+        self._update_boost_points(
+            delegator,
+            receiver,
+            -old_bias,
+            -old_boost.slope(),
+            old_boost.end,
+        );
+        let boost: Boost = self._new_boost(delegator, percentage, cancel_time, expire_time);
+        if boost.bias() < old_bias {
+            runtime::revert(ApiError::from(Error::VotingEscrowCannotDecreaseBoost));
+        }
+        Boosts::instance().set(&delegator, &receiver, boost);
+        self._update_boost_points(delegator, receiver, boost.bias(), boost.slope(), boost.end);
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::ExtendBoost {
+                delegator,
+                receiver,
+                bias: boost.bias().try_into().unwrap(),
+                slope: boost.slope().try_into().unwrap(),
+                expire_time: boost.end,
+            },
+        );
+    }
+
+    fn cancel_boost(&mut self, delegator: Key, receiver: Key) {
+        if self.get_caller() != delegator && self.get_caller() != receiver {
+            runtime::revert(ApiError::from(
+                Error::VotingEscrowNotBoostDelegatorOrReceiver,
+            ));
+        }
+        let boost: Boost = Boosts::instance().get(&delegator, &receiver);
+        let now: U256 = U256::from(u64::from(get_blocktime()));
+        if boost.end <= now {
+            runtime::revert(ApiError::from(Error::VotingEscrowNoActiveBoost));
+        }
+        // The delegator can only take the boost back once its cancel time passed
+        if self.get_caller() != receiver && now < boost.cancel_time {
+            runtime::revert(ApiError::from(Error::VotingEscrowBoostNotCancellable));
+        }
+        self._update_boost_points(
+            delegator,
+            receiver,
+            -self._boost_bias(boost),
+            -boost.slope(),
+            boost.end,
+        );
+        Boosts::instance().set(&delegator, &receiver, Boost::default());
+        VOTINGESCROW::emit(
+            self,
+            &VotingEscrowEvent::CancelBoost {
+                delegator,
+                receiver,
+            },
+        );
+    }
+
+    fn delegated_balance(&self, addr: Key) -> U256 {
+        self._delegated_point(addr, U256::from(u64::from(get_blocktime())))
+            .bias()
+            .try_into()
+            .unwrap()
+    }
+
+    fn received_balance(&self, addr: Key) -> U256 {
+        self._received_point(addr, U256::from(u64::from(get_blocktime())))
+            .bias()
+            .try_into()
+            .unwrap()
+    }
+
+    fn adjusted_balance_of(&self, addr: Key) -> U256 {
+        if get_unlocked() {
+            return 0.into();
+        }
+        let balance: U256 = self
+            .balance_of(addr, None)
+            .checked_add(self.received_balance(addr))
+            .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError22);
+        let delegated: U256 = self.delegated_balance(addr);
+        if balance > delegated {
+            balance
+                .checked_sub(delegated)
+                .unwrap_or_revert_with(Error::VotingEscrowBoostArithmeticError23)
+        } else {
+            0.into()
+        }
     }
 
     fn change_controller(&self, new_controller: Key) {
@@ -936,6 +2036,31 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("admin", admin.to_string());
                 storage::new_uref(event);
             }
+            VotingEscrowEvent::CommitSmartWalletChecker { checker } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("checker", checker.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::ApplySmartWalletChecker { checker } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("checker", checker.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::ApprovedDepositor {
+                depositor,
+                approved,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("depositor", depositor.to_string());
+                event.insert("approved", approved.to_string());
+                storage::new_uref(event);
+            }
             VotingEscrowEvent::Deposit {
                 provider,
                 value,
@@ -956,6 +2081,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
             VotingEscrowEvent::Withdraw {
                 provider,
                 value,
+                penalty,
                 ts,
             } => {
                 let mut event = BTreeMap::new();
@@ -963,6 +2089,7 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("event_type", voting_escrow_event.type_name());
                 event.insert("provider", provider.to_string());
                 event.insert("value", value.to_string());
+                event.insert("penalty", penalty.to_string());
                 event.insert("ts", ts.to_string());
                 storage::new_uref(event);
             }
@@ -977,6 +2104,112 @@ pub trait VOTINGESCROW<Storage: ContractStorage>: ContractContext<Storage> {
                 event.insert("supply", supply.to_string());
                 storage::new_uref(event);
             }
+            VotingEscrowEvent::DelegateBoost {
+                delegator,
+                receiver,
+                bias,
+                slope,
+                expire_time,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("delegator", delegator.to_string());
+                event.insert("receiver", receiver.to_string());
+                event.insert("bias", bias.to_string());
+                event.insert("slope", slope.to_string());
+                event.insert("expire_time", expire_time.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::ExtendBoost {
+                delegator,
+                receiver,
+                bias,
+                slope,
+                expire_time,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("delegator", delegator.to_string());
+                event.insert("receiver", receiver.to_string());
+                event.insert("bias", bias.to_string());
+                event.insert("slope", slope.to_string());
+                event.insert("expire_time", expire_time.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::CancelBoost {
+                delegator,
+                receiver,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("delegator", delegator.to_string());
+                event.insert("receiver", receiver.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::MergeLocks {
+                provider,
+                from_id,
+                to_id,
+                value,
+                locktime,
+                ts,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("provider", provider.to_string());
+                event.insert("from_id", from_id.to_string());
+                event.insert("to_id", to_id.to_string());
+                event.insert("value", value.to_string());
+                event.insert("locktime", locktime.to_string());
+                event.insert("ts", ts.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::SplitLock {
+                provider,
+                lock_id,
+                new_lock_id,
+                value,
+                locktime,
+                ts,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("provider", provider.to_string());
+                event.insert("lock_id", lock_id.to_string());
+                event.insert("new_lock_id", new_lock_id.to_string());
+                event.insert("value", value.to_string());
+                event.insert("locktime", locktime.to_string());
+                event.insert("ts", ts.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::PerpetualLock {
+                provider,
+                enabled,
+                locktime,
+                ts,
+            } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("provider", provider.to_string());
+                event.insert("enabled", enabled.to_string());
+                event.insert("locktime", locktime.to_string());
+                event.insert("ts", ts.to_string());
+                storage::new_uref(event);
+            }
+            VotingEscrowEvent::UnlockAll { admin, ts } => {
+                let mut event = BTreeMap::new();
+                event.insert("contract_package_hash", get_package_hash().to_string());
+                event.insert("event_type", voting_escrow_event.type_name());
+                event.insert("admin", admin.to_string());
+                event.insert("ts", ts.to_string());
+                storage::new_uref(event);
+            }
         };
     }
 }
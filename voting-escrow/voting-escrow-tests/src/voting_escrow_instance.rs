@@ -11,7 +11,7 @@ use casperlabs_test_env::{TestContract, TestEnv};
 use common::utils::hash;
 use hex::encode;
 use std::time::SystemTime;
-use voting_escrow_crate::data::{LockedBalance, Point, LOCKED, USER_POINT_HISTORY};
+use voting_escrow_crate::data::{LockedBalance, Point, LOCKED, LOCK_COUNT, USER_POINT_HISTORY};
 
 pub const MILLI_SECONDS_IN_DAY: u64 = 86400000;
 
@@ -66,11 +66,60 @@ impl VOTINGESCROWInstance {
       self.0
           .call_contract(owner, "apply_transfer_ownership", runtime_args! {}, time);
   }
+  pub fn commit_smart_wallet_checker(&self, owner: AccountHash, addr: Key, time: u64) {
+      self.0.call_contract(
+          owner,
+          "commit_smart_wallet_checker",
+          runtime_args! {
+              "addr" => addr
+          },
+          time,
+      );
+  }
+
+  pub fn apply_smart_wallet_checker(&self, owner: AccountHash, time: u64) {
+      self.0
+          .call_contract(owner, "apply_smart_wallet_checker", runtime_args! {}, time);
+  }
+
+  pub fn set_block_height_feeder(&self, owner: AccountHash, addr: Key, time: u64) {
+      self.0.call_contract(
+          owner,
+          "set_block_height_feeder",
+          runtime_args! {
+              "addr" => addr
+          },
+          time,
+      );
+  }
+
+  pub fn record_block_height(&self, owner: AccountHash, block: U256, time: u64) {
+      self.0.call_contract(
+          owner,
+          "record_block_height",
+          runtime_args! {
+              "block" => block
+          },
+          time,
+      );
+  }
+
   pub fn checkpoint(&self, owner: AccountHash, time: u64) {
       self.0
           .call_contract(owner, "checkpoint", runtime_args! {}, time);
   }
 
+  pub fn checkpoint_bounded(&self, owner: AccountHash, max_weeks: U256, time: u64) {
+      self.0.call_contract(
+          owner,
+          "checkpoint_bounded",
+          runtime_args! {
+              "max_weeks" => max_weeks
+          },
+          time,
+      );
+  }
+
   pub fn deposit_for(&self, owner: AccountHash, addr: Key, value: U256, time: u64) {
       self.0.call_contract(
           owner,
@@ -95,6 +144,38 @@ impl VOTINGESCROWInstance {
       );
   }
 
+  pub fn create_lock_for(
+      &self,
+      owner: AccountHash,
+      addr: Key,
+      value: U256,
+      unlock_time: U256,
+      time: u64,
+  ) {
+      self.0.call_contract(
+          owner,
+          "create_lock_for",
+          runtime_args! {
+              "addr" => addr,
+              "value" => value,
+              "unlock_time" => unlock_time
+          },
+          time,
+      );
+  }
+
+  pub fn set_approved_depositor(&self, owner: AccountHash, addr: Key, approved: bool, time: u64) {
+      self.0.call_contract(
+          owner,
+          "set_approved_depositor",
+          runtime_args! {
+              "addr" => addr,
+              "approved" => approved
+          },
+          time,
+      );
+  }
+
   pub fn increase_amount(&self, owner: AccountHash, value: U256, time: u64) {
       self.0.call_contract(
           owner,
@@ -121,6 +202,76 @@ impl VOTINGESCROWInstance {
       self.0
           .call_contract(owner, "withdraw", runtime_args! {}, time);
   }
+  pub fn withdraw_early(&self, owner: AccountHash, time: u64) {
+      self.0
+          .call_contract(owner, "withdraw_early", runtime_args! {}, time);
+  }
+  pub fn set_early_withdraw_enabled(&self, owner: AccountHash, enabled: bool, time: u64) {
+      self.0.call_contract(
+          owner,
+          "set_early_withdraw_enabled",
+          runtime_args! {
+              "enabled" => enabled
+          },
+          time,
+      );
+  }
+  pub fn set_penalty_receiver(&self, owner: AccountHash, addr: Key, time: u64) {
+      self.0.call_contract(
+          owner,
+          "set_penalty_receiver",
+          runtime_args! {
+              "addr" => addr
+          },
+          time,
+      );
+  }
+  pub fn withdraw_lock(&self, owner: AccountHash, lock_id: U256, time: u64) {
+      self.0.call_contract(
+          owner,
+          "withdraw_lock",
+          runtime_args! {
+              "lock_id" => lock_id
+          },
+          time,
+      );
+  }
+  pub fn merge_locks(&self, owner: AccountHash, from_id: U256, to_id: U256, time: u64) {
+      self.0.call_contract(
+          owner,
+          "merge_locks",
+          runtime_args! {
+              "from_id" => from_id,
+              "to_id" => to_id
+          },
+          time,
+      );
+  }
+  pub fn split_lock(&self, owner: AccountHash, lock_id: U256, value: U256, time: u64) {
+      self.0.call_contract(
+          owner,
+          "split_lock",
+          runtime_args! {
+              "lock_id" => lock_id,
+              "value" => value
+          },
+          time,
+      );
+  }
+  pub fn set_perpetual_lock(&self, owner: AccountHash, enabled: bool, time: u64) {
+      self.0.call_contract(
+          owner,
+          "set_perpetual_lock",
+          runtime_args! {
+              "enabled" => enabled
+          },
+          time,
+      );
+  }
+  pub fn unlock_all(&self, owner: AccountHash, time: u64) {
+      self.0
+          .call_contract(owner, "unlock_all", runtime_args! {}, time);
+  }
   pub fn total_supply(&self, owner: AccountHash, t: Option<U256>, time: u64) {
       self.0.call_contract(
           owner,
@@ -132,6 +283,62 @@ impl VOTINGESCROWInstance {
       );
   }
 
+  pub fn create_boost(
+      &self,
+      owner: AccountHash,
+      receiver: Key,
+      percentage: U256,
+      cancel_time: U256,
+      expire_time: U256,
+      time: u64,
+  ) {
+      self.0.call_contract(
+          owner,
+          "create_boost",
+          runtime_args! {
+              "receiver" => receiver,
+              "percentage" => percentage,
+              "cancel_time" => cancel_time,
+              "expire_time" => expire_time
+          },
+          time,
+      );
+  }
+
+  pub fn extend_boost(
+      &self,
+      owner: AccountHash,
+      receiver: Key,
+      percentage: U256,
+      cancel_time: U256,
+      expire_time: U256,
+      time: u64,
+  ) {
+      self.0.call_contract(
+          owner,
+          "extend_boost",
+          runtime_args! {
+              "receiver" => receiver,
+              "percentage" => percentage,
+              "cancel_time" => cancel_time,
+              "expire_time" => expire_time
+          },
+          time,
+      );
+  }
+
+  pub fn cancel_boost(&self, owner: AccountHash, delegator: Key, receiver: Key, time: u64) {
+      self.0.call_contract(
+          owner,
+          "cancel_boost",
+          runtime_args! {
+              "delegator" => delegator,
+              "receiver" => receiver
+          },
+          time,
+      );
+  }
+
   pub fn change_controller(&self, owner: AccountHash, new_controller: Key, time: u64) {
       self.0.call_contract(
           owner,
@@ -185,6 +392,17 @@ impl VOTINGESCROWInstance {
       }
   }
 
+  pub fn query_lock(&self, owner: &Key, lock_id: U256) -> LockedBalance {
+      if lock_id == 0.into() {
+          return self.query_locked(owner);
+      }
+      let mut hasher = VarBlake2b::new(32).unwrap();
+      hasher.update(format!("{}{}{}", LOCK_COUNT, owner.to_formatted_string(), lock_id).as_bytes());
+      let mut lock = [0u8; 32];
+      hasher.finalize_variable(|hash| lock.clone_from_slice(hash));
+      self.query_locked(&Key::Hash(lock))
+  }
+
   pub fn query_user_point_history(&self, user: &Key, user_epoch: &U256) -> Point {
       Point {
           bias: self
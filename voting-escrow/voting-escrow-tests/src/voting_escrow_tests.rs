@@ -1,9 +1,9 @@
-use crate::voting_escrow_instance::{now, VOTINGESCROWInstance, MILLI_SECONDS_IN_DAY};
+use crate::voting_escrow_instance::{key_to_str, now, VOTINGESCROWInstance, MILLI_SECONDS_IN_DAY};
 use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U128, U256};
 use casperlabs_test_env::{TestContract, TestEnv};
 use common::keys::*;
 use crv20::Address;
-use voting_escrow_crate::data::{LockedBalance, Point, WEEK};
+use voting_escrow_crate::data::{LockedBalance, Point, LOCK_COUNT, MAXTIME, MAX_LOCK_ID, WEEK};
 pub const TEN_E_NINE: u128 = 1000000000;
 // CRV
 fn deploy_erc20_crv(env: &TestEnv, sender: AccountHash, time_now: u64) -> TestContract {
@@ -81,6 +81,139 @@ fn test_apply_transfer_ownership() {
     let ret: Key = instance.key_value(ADMIN.to_string());
     assert_eq!(ret, addr, "Ownership transfer not applied");
 }
+#[test]
+fn test_commit_smart_wallet_checker() {
+    let (_, owner, instance, _, time_now) = deploy();
+    let checker: Key = Key::Hash([7u8; 32]);
+    instance.commit_smart_wallet_checker(owner, checker, time_now);
+    let ret: Key = instance.key_value(FUTURE_SMART_WALLET_CHECKER.to_string());
+    assert_eq!(ret, checker, "Smart wallet checker not committed");
+}
+#[test]
+fn test_apply_smart_wallet_checker() {
+    let (_, owner, instance, _, time_now) = deploy();
+    let checker: Key = Key::Hash([7u8; 32]);
+    instance.commit_smart_wallet_checker(owner, checker, time_now);
+    instance.apply_smart_wallet_checker(owner, time_now);
+    let ret: Key = instance.key_value(SMART_WALLET_CHECKER.to_string());
+    assert_eq!(ret, checker, "Smart wallet checker not applied");
+}
+#[test]
+#[should_panic]
+fn test_commit_smart_wallet_checker_by_non_admin() {
+    let (env, _, instance, _, time_now) = deploy();
+    let user = env.next_user();
+    instance.commit_smart_wallet_checker(user, Key::Hash([7u8; 32]), time_now);
+}
+fn deploy_smart_wallet_checker(env: &TestEnv, owner: AccountHash, time_now: u64) -> TestContract {
+    TestContract::new(
+        env,
+        "smart-wallet-checker.wasm",
+        "smart_wallet_checker",
+        owner,
+        runtime_args! {},
+        time_now,
+    )
+}
+fn create_lock_for_contract(
+    owner: AccountHash,
+    instance: &VOTINGESCROWInstance,
+    erc20_crv: &TestContract,
+    wallet: Key,
+    time_now: u64,
+) {
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.set_approved_depositor(owner, Key::from(owner), true, time_now);
+    instance.create_lock_for(
+        owner,
+        wallet,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
+}
+#[test]
+#[should_panic]
+fn test_create_lock_for_contract_without_checker() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
+    create_lock_for_contract(owner, &instance, &erc20_crv, Key::Hash([9u8; 32]), time_now);
+}
+#[test]
+fn test_create_lock_for_contract_approved_by_checker() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let wallet: Key = Key::Hash([9u8; 32]);
+    let checker = deploy_smart_wallet_checker(&env, owner, time_now);
+    checker.call_contract(
+        owner,
+        "approve_wallet",
+        runtime_args! {
+            "addr" => wallet
+        },
+        time_now,
+    );
+    instance.commit_smart_wallet_checker(owner, Key::Hash(checker.package_hash()), time_now);
+    instance.apply_smart_wallet_checker(owner, time_now);
+    create_lock_for_contract(owner, &instance, &erc20_crv, wallet, time_now);
+    let ret: LockedBalance = instance.query_locked(&wallet);
+    assert_eq!(
+        ret.amount,
+        (false, U128::from(1000 * TEN_E_NINE)),
+        "Lock not created for approved wallet"
+    );
+}
+#[test]
+#[should_panic]
+fn test_create_lock_for_contract_revoked_by_checker() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let wallet: Key = Key::Hash([9u8; 32]);
+    let checker = deploy_smart_wallet_checker(&env, owner, time_now);
+    checker.call_contract(
+        owner,
+        "approve_wallet",
+        runtime_args! {
+            "addr" => wallet
+        },
+        time_now,
+    );
+    checker.call_contract(
+        owner,
+        "revoke_wallet",
+        runtime_args! {
+            "addr" => wallet
+        },
+        time_now,
+    );
+    instance.commit_smart_wallet_checker(owner, Key::Hash(checker.package_hash()), time_now);
+    instance.apply_smart_wallet_checker(owner, time_now);
+    create_lock_for_contract(owner, &instance, &erc20_crv, wallet, time_now);
+}
+#[test]
+#[should_panic]
+fn test_create_lock_for_contract_checker_committed_only() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let wallet: Key = Key::Hash([9u8; 32]);
+    let checker = deploy_smart_wallet_checker(&env, owner, time_now);
+    checker.call_contract(
+        owner,
+        "approve_wallet",
+        runtime_args! {
+            "addr" => wallet
+        },
+        time_now,
+    );
+    instance.commit_smart_wallet_checker(owner, Key::Hash(checker.package_hash()), time_now);
+    create_lock_for_contract(owner, &instance, &erc20_crv, wallet, time_now);
+}
 
 #[test]
 fn test_get_last_user_slope() {
@@ -154,146 +287,118 @@ fn test_locked_end() {
     assert_eq!(ret.end / WEEK, unlock_time / WEEK, "Invalid default value");
 }
 #[test]
-fn test_checkpoint() {
-    let (_, owner, instance, _, time_now) = deploy();
-    instance.checkpoint(owner, time_now);
-}
-#[test]
-fn test_deposit_for() {
+fn test_create_lock_for() {
     let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let amount_approve: U256 = U256::from(400 * TEN_E_NINE);
-    let amount: U256 = U256::from(200 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let user = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
-    instance.create_lock(owner, amount, unlock_time, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
-        owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
-        },
-        time_now,
+    instance.set_approved_depositor(owner, Key::from(owner), true, time_now);
+    instance.create_lock_for(owner, Key::from(user), amount, unlock_time, time_now);
+    let ret: LockedBalance = instance.query_locked(&Key::from(user));
+    assert_eq!(
+        ret.amount,
+        (false, U128::from(1000 * TEN_E_NINE)),
+        "Lock not created for user"
     );
-    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    let addr = env.next_user();
-    instance.deposit_for(addr, Key::from(owner), amount, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
-        owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
-        },
-        time_now,
+    assert_eq!(ret.end / WEEK, unlock_time / WEEK, "Invalid lock end");
+    let ret: LockedBalance = instance.query_locked(&Key::from(owner));
+    assert_eq!(
+        ret.amount,
+        (false, U128::from(0)),
+        "Lock created for funder"
     );
-    let balance_after_deposit: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert!(balance_after_deposit > balance_after_lock);
 }
-
 #[test]
-fn test_create_lock() {
+#[should_panic]
+fn test_create_lock_for_not_approved_depositor() {
     let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let user = env.next_user();
     let amount: U256 = U256::from(1000 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+    instance.create_lock_for(
         owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
-        },
+        Key::from(user),
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
         time_now,
     );
-    let balance_before_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert_eq!(balance_before_lock, 0.into());
-    instance.create_lock(owner, amount, unlock_time, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+}
+#[test]
+fn test_withdraw_early() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
         owner,
+        "increase_allowance",
         runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
+            "spender" => spender,
+            "amount" => amount
         },
         time_now,
     );
-    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert!(
-        balance_after_lock / TEN_E_NINE <= 882.into()
-            && balance_after_lock / TEN_E_NINE >= 879.into()
-    );
+    instance.set_early_withdraw_enabled(owner, true, time_now);
+    instance.set_penalty_receiver(owner, Key::from(receiver), time_now);
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    instance.withdraw_early(owner, time_now + MILLI_SECONDS_IN_DAY * 30);
+    let ret: LockedBalance = instance.query_locked(&Key::from(owner));
+    assert_eq!(ret.amount, (false, U128::from(0)), "Lock not cleared");
+    assert_eq!(ret.end, 0.into(), "Lock end not cleared");
+    let supply: U256 = instance.key_value(SUPPLY.to_string());
+    assert_eq!(supply, 0.into());
 }
-
 #[test]
-fn test_increase_amount() {
-    let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let amount_approve: U256 = U256::from(400 * TEN_E_NINE);
-    let amount: U256 = U256::from(200 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+#[should_panic]
+fn test_withdraw_early_disabled() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
-    instance.create_lock(owner, amount, unlock_time, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+    instance.create_lock(
         owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
-        },
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
         time_now,
     );
-    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    instance.increase_amount(owner, amount, time_now);
+    instance.withdraw_early(owner, time_now + MILLI_SECONDS_IN_DAY * 30);
+}
+fn ve_balance_of(
+    env: &TestEnv,
+    owner: AccountHash,
+    instance: &VOTINGESCROWInstance,
+    t: u64,
+) -> U256 {
     TestContract::new(
-        &env,
+        env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
@@ -301,19 +406,15 @@ fn test_increase_amount() {
             "entrypoint" => String::from(VE_BALANCE_OF),
             "package_hash" => Key::Hash(instance.package_hash()),
             "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
+            "t" => U256::from(t)
         },
-        time_now,
+        t,
     );
-    let balance_after_increase_amount: U256 =
-        env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert!(balance_after_increase_amount > balance_after_lock);
+    env.query_account_named_key(owner, &[BALANCE_OF.into()])
 }
-
 #[test]
-fn test_increase_unlock_time() {
+fn test_split_lock() {
     let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
     let amount: U256 = U256::from(1000 * TEN_E_NINE);
     let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
     let spender: Address = Address::Contract(instance.package_hash().into());
@@ -322,49 +423,43 @@ fn test_increase_unlock_time() {
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
     instance.create_lock(owner, amount, unlock_time, time_now);
+    let balance_before_split: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    instance.split_lock(owner, 0.into(), U256::from(400 * TEN_E_NINE), time_now);
+    let primary: LockedBalance = instance.query_lock(&Key::from(owner), 0.into());
+    let split: LockedBalance = instance.query_lock(&Key::from(owner), 1.into());
+    assert_eq!(primary.amount, (false, U128::from(600 * TEN_E_NINE)));
+    assert_eq!(split.amount, (false, U128::from(400 * TEN_E_NINE)));
+    assert_eq!(primary.end, split.end, "Split lock must keep the end");
+    // The split off tokens stop voting, only the primary lock counts
+    let balance_after_split: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    assert!(balance_after_split < balance_before_split);
+    assert!(balance_after_split > balance_before_split / 2);
     TestContract::new(
         &env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
         runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
+            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
             "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
             "t" => U256::from(time_now)
         },
         time_now,
     );
-    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    // assert_eq!(balance_after_lock,0.into());
-    let unlock_time_increase = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
-    instance.increase_unlock_time(owner, unlock_time_increase, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
-        owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
-        },
-        time_now,
+    let supply: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
+    assert_eq!(
+        supply, balance_after_split,
+        "Split lock must not count in the supply"
     );
-    let balance_after_increase_unlock_time: U256 =
-        env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert!(balance_after_increase_unlock_time > balance_after_lock);
 }
 #[test]
-fn test_withdraw() {
+fn test_merge_locks() {
     let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
     let amount: U256 = U256::from(1000 * TEN_E_NINE);
     let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
     let spender: Address = Address::Contract(instance.package_hash().into());
@@ -373,87 +468,161 @@ fn test_withdraw() {
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
     instance.create_lock(owner, amount, unlock_time, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+    let balance_before_split: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    instance.split_lock(owner, 0.into(), U256::from(400 * TEN_E_NINE), time_now);
+    instance.merge_locks(owner, 1.into(), 0.into(), time_now);
+    let primary: LockedBalance = instance.query_lock(&Key::from(owner), 0.into());
+    let split: LockedBalance = instance.query_lock(&Key::from(owner), 1.into());
+    assert_eq!(primary.amount, (false, U128::from(1000 * TEN_E_NINE)));
+    assert_eq!(split.amount, (false, U128::from(0)));
+    assert_eq!(primary.end / WEEK, unlock_time / WEEK);
+    let balance_after_merge: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    assert_eq!(balance_after_merge, balance_before_split);
+}
+#[test]
+#[should_panic]
+fn test_split_lock_exceeds_lock() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
         owner,
+        "increase_allowance",
         runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
+            "spender" => spender,
+            "amount" => amount
         },
         time_now,
     );
-    let _balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    // assert_eq!(balance_after_lock,0.into());
-    let after_unlock_time = time_now + MILLI_SECONDS_IN_DAY * 365;
-    instance.withdraw(owner, after_unlock_time);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+    instance.create_lock(
         owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(after_unlock_time)
-        },
-        after_unlock_time,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
     );
-    let balance_after_withdraw: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert_eq!(balance_after_withdraw, 0.into());
+    instance.split_lock(owner, 0.into(), amount, time_now);
 }
 #[test]
-fn test_balance_of() {
-    let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+fn test_split_lock_reuses_emptied_lock() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
     let amount: U256 = U256::from(1000 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
+    instance.split_lock(owner, 0.into(), U256::from(100 * TEN_E_NINE), time_now);
+    instance.split_lock(owner, 0.into(), U256::from(100 * TEN_E_NINE), time_now);
+    instance.merge_locks(owner, 1.into(), 0.into(), time_now);
+    // The lock emptied by the merge is handed out again instead of a third id
+    instance.split_lock(owner, 2.into(), U256::from(50 * TEN_E_NINE), time_now);
+    let lock_count: U256 = instance
+        .contract()
+        .query_dictionary(LOCK_COUNT, key_to_str(&Key::from(owner)))
+        .unwrap();
+    assert_eq!(lock_count, 2.into());
+    let reused: LockedBalance = instance.query_lock(&Key::from(owner), 1.into());
+    assert_eq!(reused.amount, (false, U128::from(50 * TEN_E_NINE)));
+}
+#[test]
+#[should_panic]
+fn test_split_lock_too_many_locks() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
+    for _ in 0..=MAX_LOCK_ID.as_u64() {
+        instance.split_lock(owner, 0.into(), U256::from(TEN_E_NINE), time_now);
+    }
+}
+#[test]
+fn test_votes_backed_by_primary_lock() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(2500 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
         },
         time_now,
     );
     instance.create_lock(owner, amount, unlock_time, time_now);
+    let balance_before_split: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    instance.split_lock(owner, 0.into(), U256::from(2000 * TEN_E_NINE), time_now);
     TestContract::new(
         &env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
         runtime_args! {
-            "entrypoint" => String::from(VE_BALANCE_OF),
+            "entrypoint" => String::from(GET_LAST_USER_SLOPE),
             "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "t" => U256::from(time_now)
+            "addr" => Key::from(owner)
         },
         time_now,
     );
-    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
-    assert!(
-        balance_after_lock / TEN_E_NINE <= 882.into()
-            && balance_after_lock / TEN_E_NINE >= 879.into()
+    // Only the 500 tokens left in the primary lock vote for gauges
+    let slope: (bool, U128) = env.query_account_named_key(owner, &[GET_LAST_USER_SLOPE.into()]);
+    assert_eq!(slope, (false, 3.into()));
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(LOCKED_END),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner)
+        },
+        time_now,
     );
+    let locked_end: U256 = env.query_account_named_key(owner, &[LOCKED_END.into()]);
+    let primary: LockedBalance = instance.query_lock(&Key::from(owner), 0.into());
+    assert_eq!(locked_end, primary.end);
+    // As does the voting power
+    let balance_after_split: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    assert!(balance_after_split < balance_before_split / 4);
+    assert!(balance_after_split > 0.into());
 }
-
 #[test]
-fn test_balance_of_at() {
+fn test_perpetual_lock() {
     let (env, owner, instance, erc20_crv, time_now) = deploy();
-
-    let amount: U256 = U256::from(2500 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
@@ -464,191 +633,1364 @@ fn test_balance_of_at() {
         },
         time_now,
     );
-    let time_call_balance_at: u64 = time_now + MILLI_SECONDS_IN_DAY;
-    let current_block: U256 = U256::from(time_now / 45000);
     instance.create_lock(owner, amount, unlock_time, time_now);
+    instance.set_perpetual_lock(owner, true, time_now);
+    let balance_now: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    // Voting power of a full MAXTIME lock
+    assert!(balance_now / TEN_E_NINE <= 884.into() && balance_now / TEN_E_NINE >= 882.into());
+    let later: u64 = time_now + MILLI_SECONDS_IN_DAY * 400;
+    let balance_later: U256 = ve_balance_of(&env, owner, &instance, later);
+    assert_eq!(balance_later, balance_now, "Perpetual lock must not decay");
     TestContract::new(
         &env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
         runtime_args! {
-            "entrypoint" => String::from(BALANCE_OF_AT),
+            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
             "package_hash" => Key::Hash(instance.package_hash()),
-            "addr" => Key::from(owner),
-            "block" => current_block
+            "t" => U256::from(later)
         },
-        time_call_balance_at,
+        later,
     );
-    let ret: U256 = env.query_account_named_key(owner, &[BALANCE_OF_AT.into()]);
-    assert!(
-        ret / TEN_E_NINE >= 2385.into() && ret / TEN_E_NINE <= 2395.into(),
-        "Invalid default value balance of at"
+    let supply: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
+    assert_eq!(supply, balance_now);
+    instance.set_perpetual_lock(owner, false, later);
+    let ret: LockedBalance = instance.query_locked(&Key::from(owner));
+    assert_eq!(
+        ret.end,
+        (U256::from(later) + MAXTIME) / WEEK * WEEK,
+        "Lock must restart as a MAXTIME lock"
     );
+    let balance_after: U256 =
+        ve_balance_of(&env, owner, &instance, later + MILLI_SECONDS_IN_DAY * 30);
+    assert!(balance_after < balance_now, "Lock must decay again");
 }
-
 #[test]
-fn test_total_supply() {
-    let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let user = env.next_user();
-    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+#[should_panic]
+fn test_withdraw_perpetual_lock() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
     let amount: U256 = U256::from(1000 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
+    instance.set_perpetual_lock(owner, true, time_now);
+    instance.withdraw(owner, time_now + MILLI_SECONDS_IN_DAY * 400);
+}
+#[test]
+fn test_unlock_all() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
-        "transfer",
+        "increase_allowance",
         runtime_args! {
-            "recipient" => Key::from(user),
+            "spender" => spender,
             "amount" => amount
         },
         time_now,
     );
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
+    instance.unlock_all(owner, time_now);
+    let unlocked: bool = instance.key_value(UNLOCKED.to_string());
+    assert!(unlocked);
+    let balance: U256 = ve_balance_of(&env, owner, &instance, time_now);
+    assert_eq!(balance, 0.into(), "No voting power after unlock_all");
+    instance.withdraw(owner, time_now);
+    let ret: LockedBalance = instance.query_locked(&Key::from(owner));
+    assert_eq!(ret.amount, (false, U128::from(0)), "Lock not withdrawn");
+}
+#[test]
+fn test_unlock_all_keeps_history() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
-        user,
+        owner,
         "increase_allowance",
         runtime_args! {
-            "spender" => Key::Hash(instance.package_hash()),
-            "amount" => amount_approve
+            "spender" => spender,
+            "amount" => amount
         },
         time_now,
     );
-
-    instance.create_lock(owner, amount, unlock_time, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+    instance.create_lock(
         owner,
-        runtime_args! {
-            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "t" => U256::from(time_now),
-        },
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
         time_now,
     );
-    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
-    assert!(ret / TEN_E_NINE <= 882.into() && ret / TEN_E_NINE >= 879.into());
-    instance.create_lock(user, amount, unlock_time, time_now);
-    TestContract::new(
-        &env,
-        TEST_SESSION_CODE_WASM,
-        TEST_SESSION_CODE_NAME,
+    let before_unlock: u64 = time_now + MILLI_SECONDS_IN_DAY * 5;
+    let balance: U256 = ve_balance_of(&env, owner, &instance, before_unlock);
+    assert!(balance > 0.into());
+    let unlocked_at: u64 = time_now + MILLI_SECONDS_IN_DAY * 10;
+    instance.unlock_all(owner, unlocked_at);
+    let ret: U256 = instance.key_value(UNLOCKED_AT.to_string());
+    assert_eq!(ret, U256::from(unlocked_at));
+    for entrypoint in [GET_LAST_USER_SLOPE, LOCKED_END] {
+        TestContract::new(
+            &env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            owner,
+            runtime_args! {
+                "entrypoint" => String::from(entrypoint),
+                "package_hash" => Key::Hash(instance.package_hash()),
+                "addr" => Key::from(owner)
+            },
+            unlocked_at,
+        );
+    }
+    let slope: (bool, U128) = env.query_account_named_key(owner, &[GET_LAST_USER_SLOPE.into()]);
+    assert_eq!(slope, (false, 0.into()), "Nothing votes after unlock_all");
+    let locked_end: U256 = env.query_account_named_key(owner, &[LOCKED_END.into()]);
+    assert_eq!(locked_end, 0.into(), "Every lock ended with unlock_all");
+    instance.withdraw(owner, unlocked_at);
+    // Voting power before the unlock survives the withdrawal, none is left from the unlock on
+    for (t, expected) in [(before_unlock, balance), (unlocked_at, 0.into())] {
+        TestContract::new(
+            &env,
+            TEST_SESSION_CODE_WASM,
+            TEST_SESSION_CODE_NAME,
+            owner,
+            runtime_args! {
+                "entrypoint" => String::from(VE_BALANCE_OF),
+                "package_hash" => Key::Hash(instance.package_hash()),
+                "addr" => Key::from(owner),
+                "t" => U256::from(t)
+            },
+            unlocked_at,
+        );
+        let ret: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+        assert_eq!(ret, expected);
+    }
+}
+#[test]
+fn test_withdraw_early_after_unlock_all() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
         owner,
+        "increase_allowance",
         runtime_args! {
-            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "t" => U256::from(time_now),
+            "spender" => spender,
+            "amount" => amount
         },
         time_now,
     );
-    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
-    assert!(
-        ret / TEN_E_NINE <= 1770.into() && ret / TEN_E_NINE >= 1755.into(),
-        "Invalid default total supply"
+    instance.set_early_withdraw_enabled(owner, true, time_now);
+    instance.set_penalty_receiver(owner, Key::from(receiver), time_now);
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
     );
-    //Total supply will be 0 after lock time expired
+    instance.unlock_all(owner, time_now);
+    instance.withdraw_early(owner, time_now + MILLI_SECONDS_IN_DAY * 30);
     TestContract::new(
         &env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
         runtime_args! {
-            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
-            "package_hash" => Key::Hash(instance.package_hash()),
-            "t" => unlock_time,
+            "entrypoint" => String::from(BALANCE_OF),
+            "package_hash" => Key::Hash(erc20_crv.package_hash()),
+            "owner" => Address::from(receiver)
         },
-        time_now,
+        time_now + MILLI_SECONDS_IN_DAY * 30,
     );
-    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
-    assert_eq!(ret / TEN_E_NINE, 0.into(), "Invalid default total supply");
+    let penalty: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert_eq!(penalty, 0.into(), "No penalty once everything is unlocked");
 }
 #[test]
-fn test_total_supply_at() {
-    let (env, owner, instance, erc20_crv, time_now) = deploy();
-    let user = env.next_user();
-    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+#[should_panic]
+fn test_create_lock_after_unlock_all() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
     let amount: U256 = U256::from(1000 * TEN_E_NINE);
-    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
     let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
         "increase_allowance",
         runtime_args! {
             "spender" => spender,
-            "amount" => amount_approve
+            "amount" => amount
         },
         time_now,
     );
+    instance.unlock_all(owner, time_now);
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
+}
+#[test]
+fn test_checkpoint() {
+    let (_, owner, instance, _, time_now) = deploy();
+    instance.checkpoint(owner, time_now);
+}
+#[test]
+fn test_checkpoint_bounded() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
+    let week: u64 = WEEK.as_u64();
+    instance.checkpoint(owner, time_now);
+    let later: u64 = time_now + week * 150;
+    instance.checkpoint_bounded(owner, 100.into(), later);
+    let epoch: U256 = instance.key_value(EPOCH.to_string());
+    assert_eq!(epoch, 101.into(), "Only 100 weeks should be filled");
+    instance.checkpoint_bounded(owner, 100.into(), later);
+    // The global point is recent enough for user operations again
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
         owner,
-        "transfer",
+        "increase_allowance",
         runtime_args! {
-            "recipient" => Key::from(user),
+            "spender" => spender,
             "amount" => amount
         },
-        time_now,
+        later,
+    );
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(later + MILLI_SECONDS_IN_DAY * 365),
+        later,
     );
+    let ret: LockedBalance = instance.query_locked(&Key::from(owner));
+    assert_eq!(ret.amount, (false, U128::from(1000 * TEN_E_NINE)));
+}
+#[test]
+#[should_panic]
+fn test_create_lock_stale_global_point() {
+    let (_, owner, instance, erc20_crv, time_now) = deploy();
+    instance.checkpoint(owner, time_now);
+    let later: u64 = time_now + WEEK.as_u64() * 150;
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let spender: Address = Address::Contract(instance.package_hash().into());
     erc20_crv.call_contract(
-        user,
+        owner,
         "increase_allowance",
         runtime_args! {
-            "spender" => Key::Hash(instance.package_hash()),
+            "spender" => spender,
+            "amount" => amount
+        },
+        later,
+    );
+    instance.create_lock(
+        owner,
+        amount,
+        U256::from(later + MILLI_SECONDS_IN_DAY * 365),
+        later,
+    );
+}
+#[test]
+fn test_deposit_for() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount_approve: U256 = U256::from(400 * TEN_E_NINE);
+    let amount: U256 = U256::from(200 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
             "amount" => amount_approve
         },
         time_now,
     );
-
     instance.create_lock(owner, amount, unlock_time, time_now);
-    let current_block: U256 = U256::from(time_now / 45000);
     TestContract::new(
         &env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
         runtime_args! {
-            "entrypoint" => String::from(TOTAL_SUPPLY_AT),
+            "entrypoint" => String::from(VE_BALANCE_OF),
             "package_hash" => Key::Hash(instance.package_hash()),
-            "block" => current_block,
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
         },
         time_now,
     );
-    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY_AT.into()]);
-    assert!(ret / TEN_E_NINE <= 882.into() && ret / TEN_E_NINE >= 879.into());
-    instance.create_lock(user, amount, unlock_time, time_now);
+    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    let addr = env.next_user();
+    instance.deposit_for(addr, Key::from(owner), amount, time_now);
     TestContract::new(
         &env,
         TEST_SESSION_CODE_WASM,
         TEST_SESSION_CODE_NAME,
         owner,
         runtime_args! {
-            "entrypoint" => String::from(TOTAL_SUPPLY_AT),
+            "entrypoint" => String::from(VE_BALANCE_OF),
             "package_hash" => Key::Hash(instance.package_hash()),
-            "block" => current_block,
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
         },
         time_now,
     );
-    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY_AT.into()]);
-    assert!(
-        ret / TEN_E_NINE <= 1770.into() && ret / TEN_E_NINE >= 1755.into(),
-        "Invalid default total supply at"
-    );
+    let balance_after_deposit: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert!(balance_after_deposit > balance_after_lock);
 }
 
 #[test]
-fn test_change_controller() {
-    let (env, owner, instance, _, time_now) = deploy();
-    let new_controller: Key = Key::Account(env.next_user());
-    instance.change_controller(owner, new_controller, time_now);
-    let ret: Key = instance.key_value(CONTROLLER.to_string());
-    assert_eq!(ret, new_controller, "Controller not changed");
+fn test_create_lock() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_before_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert_eq!(balance_before_lock, 0.into());
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert!(
+        balance_after_lock / TEN_E_NINE <= 882.into()
+            && balance_after_lock / TEN_E_NINE >= 879.into()
+    );
+}
+
+#[test]
+fn test_increase_amount() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount_approve: U256 = U256::from(400 * TEN_E_NINE);
+    let amount: U256 = U256::from(200 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    instance.increase_amount(owner, amount, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_after_increase_amount: U256 =
+        env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert!(balance_after_increase_amount > balance_after_lock);
+}
+
+#[test]
+fn test_increase_unlock_time() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    // assert_eq!(balance_after_lock,0.into());
+    let unlock_time_increase = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    instance.increase_unlock_time(owner, unlock_time_increase, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_after_increase_unlock_time: U256 =
+        env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert!(balance_after_increase_unlock_time > balance_after_lock);
+}
+#[test]
+fn test_withdraw() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let _balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    // assert_eq!(balance_after_lock,0.into());
+    let after_unlock_time = time_now + MILLI_SECONDS_IN_DAY * 365;
+    instance.withdraw(owner, after_unlock_time);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(after_unlock_time)
+        },
+        after_unlock_time,
+    );
+    let balance_after_withdraw: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert_eq!(balance_after_withdraw, 0.into());
+}
+#[test]
+fn test_balance_of() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance_after_lock: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert!(
+        balance_after_lock / TEN_E_NINE <= 882.into()
+            && balance_after_lock / TEN_E_NINE >= 879.into()
+    );
+}
+
+#[test]
+fn test_balance_of_at() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+
+    let amount: U256 = U256::from(2500 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    let time_call_balance_at: u64 = time_now + MILLI_SECONDS_IN_DAY;
+    instance.set_block_height_feeder(owner, Key::from(owner), time_now);
+    instance.record_block_height(owner, 1000.into(), time_now);
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    instance.record_block_height(owner, 3000.into(), time_call_balance_at);
+    // Block 2000 is half way between the recorded heights
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF_AT),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "block" => U256::from(2000)
+        },
+        time_call_balance_at,
+    );
+    let ret: U256 = env.query_account_named_key(owner, &[BALANCE_OF_AT.into()]);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF_AT_TIME),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now + MILLI_SECONDS_IN_DAY / 2)
+        },
+        time_call_balance_at,
+    );
+    let ret_at_time: U256 = env.query_account_named_key(owner, &[BALANCE_OF_AT_TIME.into()]);
+    assert_eq!(ret, ret_at_time, "Block not mapped to its timestamp");
+    assert!(
+        ret / TEN_E_NINE >= 2385.into() && ret / TEN_E_NINE <= 2395.into(),
+        "Invalid default value balance of at"
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_balance_of_at_unrecorded_block() {
+    let (env, owner, instance, _, time_now) = deploy();
+    instance.set_block_height_feeder(owner, Key::from(owner), time_now);
+    instance.record_block_height(owner, 1000.into(), time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF_AT),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "block" => U256::from(1001)
+        },
+        time_now,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_record_block_height_by_non_feeder() {
+    let (env, _, instance, _, time_now) = deploy();
+    let user = env.next_user();
+    instance.record_block_height(user, 1000.into(), time_now);
+}
+
+#[test]
+#[should_panic]
+fn test_record_block_height_not_increasing() {
+    let (_, owner, instance, _, time_now) = deploy();
+    instance.set_block_height_feeder(owner, Key::from(owner), time_now);
+    instance.record_block_height(owner, 1000.into(), time_now);
+    instance.record_block_height(owner, 1000.into(), time_now + 1000);
+}
+
+#[test]
+fn test_total_supply() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let user = env.next_user();
+    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    erc20_crv.call_contract(
+        owner,
+        "transfer",
+        runtime_args! {
+            "recipient" => Key::from(user),
+            "amount" => amount
+        },
+        time_now,
+    );
+    erc20_crv.call_contract(
+        user,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => Key::Hash(instance.package_hash()),
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "t" => U256::from(time_now),
+        },
+        time_now,
+    );
+    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
+    assert!(ret / TEN_E_NINE <= 882.into() && ret / TEN_E_NINE >= 879.into());
+    instance.create_lock(user, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "t" => U256::from(time_now),
+        },
+        time_now,
+    );
+    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
+    assert!(
+        ret / TEN_E_NINE <= 1770.into() && ret / TEN_E_NINE >= 1755.into(),
+        "Invalid default total supply"
+    );
+    //Total supply will be 0 after lock time expired
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_TOTAL_SUPPLY),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "t" => unlock_time,
+        },
+        time_now,
+    );
+    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY.into()]);
+    assert_eq!(ret / TEN_E_NINE, 0.into(), "Invalid default total supply");
+}
+#[test]
+fn test_total_supply_at() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let user = env.next_user();
+    let amount_approve: U256 = U256::from(1000 * TEN_E_NINE);
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+    erc20_crv.call_contract(
+        owner,
+        "transfer",
+        runtime_args! {
+            "recipient" => Key::from(user),
+            "amount" => amount
+        },
+        time_now,
+    );
+    erc20_crv.call_contract(
+        user,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => Key::Hash(instance.package_hash()),
+            "amount" => amount_approve
+        },
+        time_now,
+    );
+
+    instance.set_block_height_feeder(owner, Key::from(owner), time_now);
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    instance.record_block_height(owner, 1000.into(), time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(TOTAL_SUPPLY_AT),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "block" => U256::from(1000),
+        },
+        time_now,
+    );
+    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY_AT.into()]);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(TOTAL_SUPPLY_AT_TIME),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "t" => U256::from(time_now),
+        },
+        time_now,
+    );
+    let ret_at_time: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY_AT_TIME.into()]);
+    assert_eq!(ret, ret_at_time, "Block not mapped to its timestamp");
+    assert!(ret / TEN_E_NINE <= 882.into() && ret / TEN_E_NINE >= 879.into());
+    instance.create_lock(user, amount, unlock_time, time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(TOTAL_SUPPLY_AT),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "block" => current_block,
+        },
+        time_now,
+    );
+    let ret: U256 = env.query_account_named_key(owner, &[TOTAL_SUPPLY_AT.into()]);
+    assert!(
+        ret / TEN_E_NINE <= 1770.into() && ret / TEN_E_NINE >= 1755.into(),
+        "Invalid default total supply at"
+    );
+}
+#[test]
+fn test_historical_batch_and_range() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let step: u64 = MILLI_SECONDS_IN_DAY * 5;
+    let later: u64 = time_now + step * 2;
+    let ts: Vec<U256> = vec![
+        U256::from(time_now),
+        U256::from(time_now + step),
+        U256::from(later),
+    ];
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF_AT_TIMES),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "ts" => ts.clone()
+        },
+        later,
+    );
+    let balances: Vec<U256> = env.query_account_named_key(owner, &[BALANCE_OF_AT_TIMES.into()]);
+    assert_eq!(balances.len(), 3);
+    assert!(balances[0] > balances[1] && balances[1] > balances[2]);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(BALANCE_OF_RANGE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "start" => U256::from(time_now),
+            "end" => U256::from(later),
+            "step" => U256::from(step)
+        },
+        later,
+    );
+    let range: Vec<U256> = env.query_account_named_key(owner, &[BALANCE_OF_RANGE.into()]);
+    assert_eq!(range, balances, "Range and batch queries must agree");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(TOTAL_SUPPLY_AT_TIMES),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "ts" => ts
+        },
+        later,
+    );
+    let supplies: Vec<U256> = env.query_account_named_key(owner, &[TOTAL_SUPPLY_AT_TIMES.into()]);
+    assert_eq!(supplies, balances, "Single locker owns the whole supply");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(TOTAL_SUPPLY_RANGE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "start" => U256::from(time_now),
+            "end" => U256::from(later),
+            "step" => U256::from(step)
+        },
+        later,
+    );
+    let range: Vec<U256> = env.query_account_named_key(owner, &[TOTAL_SUPPLY_RANGE.into()]);
+    assert_eq!(range, supplies);
+}
+
+#[test]
+fn test_change_controller() {
+    let (env, owner, instance, _, time_now) = deploy();
+    let new_controller: Key = Key::Account(env.next_user());
+    instance.change_controller(owner, new_controller, time_now);
+    let ret: Key = instance.key_value(CONTROLLER.to_string());
+    assert_eq!(ret, new_controller, "Controller not changed");
+}
+
+#[test]
+fn test_create_boost() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(5000),
+        0.into(),
+        expire_time,
+        time_now,
+    );
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(DELEGATED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner)
+        },
+        time_now,
+    );
+    let delegated: U256 = env.query_account_named_key(owner, &[DELEGATED_BALANCE.into()]);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(RECEIVED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        time_now,
+    );
+    let received: U256 = env.query_account_named_key(owner, &[RECEIVED_BALANCE.into()]);
+    assert_eq!(delegated, received, "Delegated and received boost differ");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(VE_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner),
+            "t" => U256::from(time_now)
+        },
+        time_now,
+    );
+    let balance: U256 = env.query_account_named_key(owner, &[BALANCE_OF.into()]);
+    assert!(received > 0.into(), "Boost not received");
+    assert!(received <= balance / 2, "Invalid received boost");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(ADJUSTED_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        time_now,
+    );
+    let adjusted: U256 = env.query_account_named_key(owner, &[ADJUSTED_BALANCE_OF.into()]);
+    assert_eq!(adjusted, received, "Receiver does not own a lock");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(ADJUSTED_BALANCE_OF),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner)
+        },
+        time_now,
+    );
+    let adjusted: U256 = env.query_account_named_key(owner, &[ADJUSTED_BALANCE_OF.into()]);
+    assert_eq!(
+        adjusted,
+        balance - delegated,
+        "Invalid adjusted balance of delegator"
+    );
+    //Boost will be 0 after it expired
+    let after_expire_time: u64 = expire_time.as_u64();
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(RECEIVED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        after_expire_time,
+    );
+    let received: U256 = env.query_account_named_key(owner, &[RECEIVED_BALANCE.into()]);
+    assert_eq!(received, 0.into(), "Boost did not expire");
+}
+
+#[test]
+fn test_extend_boost() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(2500),
+        0.into(),
+        expire_time,
+        time_now,
+    );
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(RECEIVED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        time_now,
+    );
+    let received_before: U256 = env.query_account_named_key(owner, &[RECEIVED_BALANCE.into()]);
+    instance.extend_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(5000),
+        0.into(),
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 2),
+        time_now,
+    );
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(RECEIVED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        time_now,
+    );
+    let received_after: U256 = env.query_account_named_key(owner, &[RECEIVED_BALANCE.into()]);
+    assert!(received_after > received_before, "Boost not extended");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(DELEGATED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner)
+        },
+        time_now,
+    );
+    let delegated: U256 = env.query_account_named_key(owner, &[DELEGATED_BALANCE.into()]);
+    assert_eq!(delegated, received_after, "Old boost not released");
+}
+
+#[test]
+fn test_cancel_boost() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(10000),
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 30),
+        expire_time,
+        time_now,
+    );
+    // The receiver can give the boost back before its cancel time
+    instance.cancel_boost(receiver, Key::from(owner), Key::from(receiver), time_now);
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(RECEIVED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        time_now,
+    );
+    let received: U256 = env.query_account_named_key(owner, &[RECEIVED_BALANCE.into()]);
+    assert_eq!(received, 0.into(), "Boost not cancelled");
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(DELEGATED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(owner)
+        },
+        time_now,
+    );
+    let delegated: U256 = env.query_account_named_key(owner, &[DELEGATED_BALANCE.into()]);
+    assert_eq!(delegated, 0.into(), "Boost not cancelled");
+}
+
+#[test]
+#[should_panic]
+fn test_cancel_boost_by_delegator_before_cancel_time() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let cancel_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 30);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(10000),
+        cancel_time,
+        expire_time,
+        time_now,
+    );
+    instance.cancel_boost(
+        owner,
+        Key::from(owner),
+        Key::from(receiver),
+        cancel_time.as_u64() - 1,
+    );
+}
+
+#[test]
+fn test_cancel_boost_by_delegator_after_cancel_time() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let cancel_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 30);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(10000),
+        cancel_time,
+        expire_time,
+        time_now,
+    );
+    instance.cancel_boost(
+        owner,
+        Key::from(owner),
+        Key::from(receiver),
+        cancel_time.as_u64(),
+    );
+    TestContract::new(
+        &env,
+        TEST_SESSION_CODE_WASM,
+        TEST_SESSION_CODE_NAME,
+        owner,
+        runtime_args! {
+            "entrypoint" => String::from(RECEIVED_BALANCE),
+            "package_hash" => Key::Hash(instance.package_hash()),
+            "addr" => Key::from(receiver)
+        },
+        cancel_time.as_u64(),
+    );
+    let received: U256 = env.query_account_named_key(owner, &[RECEIVED_BALANCE.into()]);
+    assert_eq!(received, 0.into(), "Boost not cancelled");
+}
+
+#[test]
+#[should_panic]
+fn test_create_boost_cancel_time_after_expiry() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let cancel_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 30);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(10000),
+        expire_time + 1,
+        expire_time,
+        time_now,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_extend_boost_earlier_cancel_time() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    let expire_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365);
+    let cancel_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 30);
+    instance.create_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(5000),
+        cancel_time,
+        expire_time,
+        time_now,
+    );
+    instance.extend_boost(
+        owner,
+        Key::from(receiver),
+        U256::from(5000),
+        cancel_time - 1,
+        expire_time,
+        time_now,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_create_boost_to_self() {
+    let (env, owner, instance, erc20_crv, time_now) = deploy();
+    let receiver: AccountHash = env.next_user();
+    let amount: U256 = U256::from(1000 * TEN_E_NINE);
+    let unlock_time = U256::from(time_now + MILLI_SECONDS_IN_DAY * 365 * 4);
+    let spender: Address = Address::Contract(instance.package_hash().into());
+    erc20_crv.call_contract(
+        owner,
+        "increase_allowance",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+        time_now,
+    );
+    instance.create_lock(owner, amount, unlock_time, time_now);
+    instance.create_boost(
+        owner,
+        Key::from(owner),
+        U256::from(10000),
+        0.into(),
+        U256::from(time_now + MILLI_SECONDS_IN_DAY * 365),
+        time_now,
+    );
 }
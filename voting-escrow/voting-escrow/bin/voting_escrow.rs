@@ -1,7 +1,7 @@
 #![no_main]
 #![no_std]
 extern crate alloc;
-use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec};
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
@@ -81,7 +81,29 @@ fn apply_transfer_ownership() {
     VotingEscrow::default().apply_transfer_ownership();
 }
 
+/// @notice Release every lock so that all tokens can be withdrawn
+/// @dev Admin only and irreversible, voting power drops to zero and no lock can be created anymore
+#[no_mangle]
+fn unlock_all() {
+    VotingEscrow::default().unlock_all();
+}
+
+/// @notice Set an external contract to check for approved smart contract wallets
+/// @param addr Address of Smart contract checker
+#[no_mangle]
+fn commit_smart_wallet_checker() {
+    let addr: Key = runtime::get_named_arg("addr");
+    VotingEscrow::default().commit_smart_wallet_checker(addr);
+}
+
+/// @notice Apply setting external contract to check approved smart contract wallets
+#[no_mangle]
+fn apply_smart_wallet_checker() {
+    VotingEscrow::default().apply_smart_wallet_checker();
+}
+
 /// @notice Get the most recently recorded rate of voting power decrease for `addr`
+/// @dev Only the primary lock (id 0) is taken into account, as it alone backs gauge votes
 /// @param addr Address of the user wallet
 /// @return Value of the slope
 #[no_mangle]
@@ -104,6 +126,7 @@ fn user_point_history_ts() {
 }
 
 /// @notice Get timestamp when `_addr`'s lock finishes
+/// @dev End of the primary lock (id 0), additional locks may end at other times
 /// @param _addr User wallet
 /// @return Epoch time of the lock end
 #[no_mangle]
@@ -119,6 +142,14 @@ fn checkpoint() {
     VotingEscrow::default().checkpoint();
 }
 
+/// @notice Record global data to checkpoint, walking at most `max_weeks` weeks
+/// @param max_weeks Maximum number of weeks to fill in the point history
+#[no_mangle]
+fn checkpoint_bounded() {
+    let max_weeks: U256 = runtime::get_named_arg("max_weeks");
+    VotingEscrow::default().checkpoint_bounded(max_weeks);
+}
+
 /// @notice Deposit `_value` tokens for `_addr` and add to the lock
 /// @dev Anyone (even a smart contract) can deposit for someone else, but cannot extend their locktime and deposit for a brand new user
 /// @param _addr User's wallet address
@@ -140,6 +171,29 @@ fn create_lock() {
     VotingEscrow::default().create_lock(value, unlock_time);
 }
 
+/// @notice Deposit `_value` tokens of `self.get_caller()` for `addr` and lock until `_unlock_time`
+/// @dev Only callable by approved depositors, `addr` must not have an existing lock
+/// @param addr User's wallet address
+/// @param _value Amount to deposit
+/// @param _unlock_time Epoch time when tokens unlock, rounded down to whole weeks
+#[no_mangle]
+fn create_lock_for() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let value: U256 = runtime::get_named_arg("value");
+    let unlock_time: U256 = runtime::get_named_arg("unlock_time");
+    VotingEscrow::default().create_lock_for(addr, value, unlock_time);
+}
+
+/// @notice Allow or disallow `addr` to create locks on behalf of other accounts
+/// @param addr Address of the depositor
+/// @param approved bool - can `addr` call `create_lock_for`?
+#[no_mangle]
+fn set_approved_depositor() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let approved: bool = runtime::get_named_arg("approved");
+    VotingEscrow::default().set_approved_depositor(addr, approved);
+}
+
 /// @notice Deposit `_value` additional tokens for `self.get_caller()` without modifying the unlock time
 /// @param _value Amount of tokens to deposit and add to the lock
 #[no_mangle]
@@ -163,8 +217,95 @@ fn withdraw() {
     VotingEscrow::default().withdraw();
 }
 
+/// @notice Withdraw all tokens for `self.get_caller()` before the lock expires
+/// @dev A penalty proportional to the remaining lock time is sent to the penalty receiver
+#[no_mangle]
+fn withdraw_early() {
+    VotingEscrow::default().withdraw_early();
+}
+
+/// @notice Allow or disallow withdrawing before the lock expires
+/// @param enabled bool - is `withdraw_early` callable?
+#[no_mangle]
+fn set_early_withdraw_enabled() {
+    let enabled: bool = runtime::get_named_arg("enabled");
+    VotingEscrow::default().set_early_withdraw_enabled(enabled);
+}
+
+/// @notice Set the address receiving early withdrawal penalties, e.g. the fee distributor
+/// @param addr Address of the penalty receiver
+#[no_mangle]
+fn set_penalty_receiver() {
+    let addr: Key = runtime::get_named_arg("addr");
+    VotingEscrow::default().set_penalty_receiver(addr);
+}
+
+/// @notice Withdraw all tokens of the lock `lock_id` of `self.get_caller()`
+/// @dev Only possible if the lock has expired
+/// @param lock_id Id of the lock, 0 for the primary lock
+#[no_mangle]
+fn withdraw_lock() {
+    let lock_id: U256 = runtime::get_named_arg("lock_id");
+    VotingEscrow::default().withdraw_lock(lock_id);
+}
+
+/// @notice Merge the lock `from_id` of `self.get_caller()` into its lock `to_id`
+/// @param from_id Id of the lock to empty
+/// @param to_id Id of the lock receiving the tokens
+#[no_mangle]
+fn merge_locks() {
+    let from_id: U256 = runtime::get_named_arg("from_id");
+    let to_id: U256 = runtime::get_named_arg("to_id");
+    VotingEscrow::default().merge_locks(from_id, to_id);
+}
+
+/// @notice Move `value` tokens of the lock `lock_id` of `self.get_caller()` into a new lock
+/// @dev Emptied locks are reused first, lock ids never exceed `MAX_LOCK_ID`
+/// @param lock_id Id of the lock to split
+/// @param value Amount of tokens to move into the new lock
+/// @return Id of the new lock
+#[no_mangle]
+fn split_lock() {
+    let lock_id: U256 = runtime::get_named_arg("lock_id");
+    let value: U256 = runtime::get_named_arg("value");
+    let ret: U256 = VotingEscrow::default().split_lock(lock_id, value);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Turn the perpetual max lock of `self.get_caller()` on or off
+/// @dev While on, the lock keeps the voting power of a fresh `MAXTIME` lock and cannot be withdrawn
+/// @param enabled bool - should the lock of `self.get_caller()` be perpetual?
+#[no_mangle]
+fn set_perpetual_lock() {
+    let enabled: bool = runtime::get_named_arg("enabled");
+    VotingEscrow::default().set_perpetual_lock(enabled);
+}
+
+/// @notice Number of locks of `addr`, the primary lock (id 0) included
+/// @param addr User's wallet address
+/// @return Number of lock ids
+#[no_mangle]
+fn lock_count() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ret: U256 = VotingEscrow::default().lock_count(addr);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Get the lock `lock_id` of `addr`
+/// @param addr User's wallet address
+/// @param lock_id Id of the lock, 0 for the primary lock
+/// @return Locked amount and end of the lock
+#[no_mangle]
+fn locked_of() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let lock_id: U256 = runtime::get_named_arg("lock_id");
+    let ret: data::LockedBalance = VotingEscrow::default().locked_of(addr, lock_id);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// @notice Get the current voting power for `self.get_caller()`
-/// @dev Adheres to the ERC20 `balanceOf` interface for Aragon compatibility
+/// @dev Adheres to the ERC20 `balanceOf` interface for Aragon compatibility.
+///      Sums up every lock of `addr`, unlike the primary lock only views used for gauge votes
 /// @param addr User wallet address
 /// @param _t Epoch time to return voting power at
 /// @return User voting power
@@ -178,6 +319,7 @@ fn balance_of() {
 
 /// @notice Measure voting power of `addr` at block height `_block`
 /// @dev Adheres to MiniMe `balanceOfAt` interface: https://github.com/Giveth/minime
+///      `_block` must be covered by the heights recorded by the block height feeder
 /// @param addr User's wallet address
 /// @param _block Block to calculate the voting power at
 /// @return Voting power
@@ -189,6 +331,18 @@ fn balance_of_at() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// @notice Measure voting power of `addr` at a past timestamp `t`
+/// @param addr User's wallet address
+/// @param t Epoch time to calculate the voting power at
+/// @return Voting power
+#[no_mangle]
+fn balance_of_at_time() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let t: U256 = runtime::get_named_arg("t");
+    let ret: U256 = VotingEscrow::default().balance_of_at_time(addr, t);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 /// @notice Calculate total voting power
 /// @dev Adheres to the ERC20 `totalSupply` interface for Aragon compatibility
 /// @return Total voting power
@@ -209,6 +363,85 @@ fn total_supply_at() {
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
+/// @notice Calculate total voting power at a past timestamp `t`
+/// @param t Epoch time to calculate the total voting power at
+/// @return Total voting power at `t`
+#[no_mangle]
+fn total_supply_at_time() {
+    let t: U256 = runtime::get_named_arg("t");
+    let ret: U256 = VotingEscrow::default().total_supply_at_time(t);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Measure voting power of `addr` at several past timestamps
+/// @param addr User's wallet address
+/// @param ts Epoch times to calculate the voting power at
+/// @return Voting power at each of `ts`
+#[no_mangle]
+fn balance_of_at_times() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ts: Vec<U256> = runtime::get_named_arg("ts");
+    let ret: Vec<U256> = VotingEscrow::default().balance_of_at_times(addr, ts);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Measure voting power of `addr` over a range of past timestamps
+/// @param addr User's wallet address
+/// @param start First timestamp of the range
+/// @param end Last timestamp of the range
+/// @param step Distance between two timestamps
+/// @return Voting power at `start`, `start + step`, ... up to `end`
+#[no_mangle]
+fn balance_of_range() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let start: U256 = runtime::get_named_arg("start");
+    let end: U256 = runtime::get_named_arg("end");
+    let step: U256 = runtime::get_named_arg("step");
+    let ret: Vec<U256> = VotingEscrow::default().balance_of_range(addr, start, end, step);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Calculate total voting power at several past timestamps
+/// @param ts Epoch times to calculate the total voting power at
+/// @return Total voting power at each of `ts`
+#[no_mangle]
+fn total_supply_at_times() {
+    let ts: Vec<U256> = runtime::get_named_arg("ts");
+    let ret: Vec<U256> = VotingEscrow::default().total_supply_at_times(ts);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Calculate total voting power over a range of past timestamps
+/// @param start First timestamp of the range
+/// @param end Last timestamp of the range
+/// @param step Distance between two timestamps
+/// @return Total voting power at `start`, `start + step`, ... up to `end`
+#[no_mangle]
+fn total_supply_range() {
+    let start: U256 = runtime::get_named_arg("start");
+    let end: U256 = runtime::get_named_arg("end");
+    let step: U256 = runtime::get_named_arg("step");
+    let ret: Vec<U256> = VotingEscrow::default().total_supply_range(start, end, step);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Record the Casper block height the current deploy is executed in
+/// @dev Only callable by the trusted block height feeder
+/// @param block Block height of the current deploy
+#[no_mangle]
+fn record_block_height() {
+    let block: U256 = runtime::get_named_arg("block");
+    VotingEscrow::default().record_block_height(block);
+}
+
+/// @notice Set the trusted account or contract allowed to record block heights
+/// @param addr Address of the block height feeder
+#[no_mangle]
+fn set_block_height_feeder() {
+    let addr: Key = runtime::get_named_arg("addr");
+    VotingEscrow::default().set_block_height_feeder(addr);
+}
+
 /// Dummy methods for compatibility with Aragon
 /// @dev Dummy method required for Aragon compatibility
 #[no_mangle]
@@ -217,6 +450,78 @@ fn change_controller() {
     VotingEscrow::default().change_controller(new_controller);
 }
 
+/// @notice Delegate `percentage` of the undelegated voting power of `self.get_caller()` to `receiver`
+/// @dev The boost decays linearly and reaches zero at `expire_time`, which cannot be after the lock end
+/// @param receiver Account receiving the boost
+/// @param percentage Share of the undelegated voting power in bps (10000 == 100%)
+/// @param cancel_time Epoch time before which only `receiver` can cancel the boost, not after `expire_time`
+/// @param expire_time Epoch time when the boost expires, rounded down to whole weeks
+#[no_mangle]
+fn create_boost() {
+    let receiver: Key = runtime::get_named_arg("receiver");
+    let percentage: U256 = runtime::get_named_arg("percentage");
+    let cancel_time: U256 = runtime::get_named_arg("cancel_time");
+    let expire_time: U256 = runtime::get_named_arg("expire_time");
+    VotingEscrow::default().create_boost(receiver, percentage, cancel_time, expire_time);
+}
+
+/// @notice Replace the active boost of `self.get_caller()` to `receiver` with a larger or longer one
+/// @dev `percentage` applies to the undelegated voting power once the current boost is released
+/// @param receiver Account receiving the boost
+/// @param percentage Share of the undelegated voting power in bps (10000 == 100%)
+/// @param cancel_time New epoch time before which only `receiver` can cancel, not before the current one
+/// @param expire_time New epoch time when the boost expires, not before the current one
+#[no_mangle]
+fn extend_boost() {
+    let receiver: Key = runtime::get_named_arg("receiver");
+    let percentage: U256 = runtime::get_named_arg("percentage");
+    let cancel_time: U256 = runtime::get_named_arg("cancel_time");
+    let expire_time: U256 = runtime::get_named_arg("expire_time");
+    VotingEscrow::default().extend_boost(receiver, percentage, cancel_time, expire_time);
+}
+
+/// @notice Cancel the active boost from `delegator` to `receiver`
+/// @dev Callable by the receiver at any time, by the delegator once the cancel time passed
+/// @param delegator Account the boost is delegated from
+/// @param receiver Account receiving the boost
+#[no_mangle]
+fn cancel_boost() {
+    let delegator: Key = runtime::get_named_arg("delegator");
+    let receiver: Key = runtime::get_named_arg("receiver");
+    VotingEscrow::default().cancel_boost(delegator, receiver);
+}
+
+/// @notice Get the voting power `addr` currently delegates to others
+/// @param addr User wallet address
+/// @return Delegated voting power
+#[no_mangle]
+fn delegated_balance() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ret: U256 = VotingEscrow::default().delegated_balance(addr);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Get the voting power `addr` currently receives from others
+/// @param addr User wallet address
+/// @return Received voting power
+#[no_mangle]
+fn received_balance() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ret: U256 = VotingEscrow::default().received_balance(addr);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// @notice Get the voting power of `addr` with delegations applied
+/// @dev Own voting power minus delegated plus received boosts, this is what gauges should use
+/// @param addr User wallet address
+/// @return Adjusted voting power
+#[no_mangle]
+fn adjusted_balance_of() {
+    let addr: Key = runtime::get_named_arg("addr");
+    let ret: U256 = VotingEscrow::default().adjusted_balance_of(addr);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
 // Variables
 
 #[no_mangle]
@@ -271,6 +576,15 @@ fn slope_changes() {
     )
 }
 
+#[no_mangle]
+fn boosts() {
+    let delegator: Key = runtime::get_named_arg("delegator");
+    let receiver: Key = runtime::get_named_arg("receiver");
+    runtime::ret(
+        CLValue::from_t(data::Boosts::instance().get(&delegator, &receiver)).unwrap_or_revert(),
+    )
+}
+
 #[no_mangle]
 fn controller() {
     runtime::ret(CLValue::from_t(data::get_controller()).unwrap_or_revert())
@@ -311,6 +625,61 @@ fn future_admin() {
     runtime::ret(CLValue::from_t(data::get_future_admin()).unwrap_or_revert())
 }
 
+#[no_mangle]
+fn perpetual_locks() {
+    let addr: Key = runtime::get_named_arg("addr");
+    runtime::ret(CLValue::from_t(data::PerpetualLocks::instance().get(&addr)).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn unlocked() {
+    runtime::ret(CLValue::from_t(data::get_unlocked()).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn early_withdraw_enabled() {
+    runtime::ret(CLValue::from_t(data::get_early_withdraw_enabled()).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn penalty_receiver() {
+    runtime::ret(CLValue::from_t(data::get_penalty_receiver()).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn approved_depositors() {
+    let addr: Key = runtime::get_named_arg("addr");
+    runtime::ret(
+        CLValue::from_t(data::ApprovedDepositors::instance().get(&addr)).unwrap_or_revert(),
+    )
+}
+
+#[no_mangle]
+fn block_height_feeder() {
+    runtime::ret(CLValue::from_t(data::get_block_height_feeder()).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn block_height_epoch() {
+    runtime::ret(CLValue::from_t(data::get_block_height_epoch()).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn block_height_history() {
+    let idx: U256 = runtime::get_named_arg("idx");
+    runtime::ret(CLValue::from_t(data::BlockHeightHistory::instance().get(&idx)).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn smart_wallet_checker() {
+    runtime::ret(CLValue::from_t(data::get_smart_wallet_checker()).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn future_smart_wallet_checker() {
+    runtime::ret(CLValue::from_t(data::get_future_smart_wallet_checker()).unwrap_or_revert())
+}
+
 fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
     entry_points.add_entry_point(EntryPoint::new(
@@ -341,6 +710,27 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unlock_all",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "commit_smart_wallet_checker",
+        vec![Parameter::new("addr", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "apply_smart_wallet_checker",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "get_last_user_slope",
         vec![Parameter::new("addr", Key::cl_type())],
@@ -372,6 +762,13 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "checkpoint_bounded",
+        vec![Parameter::new("max_weeks", U256::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "deposit_for",
         vec![
@@ -392,6 +789,27 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_lock_for",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("value", U256::cl_type()),
+            Parameter::new("unlock_time", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_approved_depositor",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("approved", bool::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "increase_amount",
         vec![Parameter::new("value", U256::cl_type())],
@@ -413,6 +831,78 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw_early",
+        vec![],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_early_withdraw_enabled",
+        vec![Parameter::new("enabled", bool::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_penalty_receiver",
+        vec![Parameter::new("addr", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "withdraw_lock",
+        vec![Parameter::new("lock_id", U256::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "merge_locks",
+        vec![
+            Parameter::new("from_id", U256::cl_type()),
+            Parameter::new("to_id", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "split_lock",
+        vec![
+            Parameter::new("lock_id", U256::cl_type()),
+            Parameter::new("value", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_perpetual_lock",
+        vec![Parameter::new("enabled", bool::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "lock_count",
+        vec![Parameter::new("addr", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "locked_of",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("lock_id", U256::cl_type()),
+        ],
+        data::LockedBalance::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "balance_of",
         vec![
@@ -447,6 +937,77 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_at_time",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("t", U256::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply_at_time",
+        vec![Parameter::new("t", U256::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_at_times",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("ts", Vec::<U256>::cl_type()),
+        ],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of_range",
+        vec![
+            Parameter::new("addr", Key::cl_type()),
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("end", U256::cl_type()),
+            Parameter::new("step", U256::cl_type()),
+        ],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply_at_times",
+        vec![Parameter::new("ts", Vec::<U256>::cl_type())],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "total_supply_range",
+        vec![
+            Parameter::new("start", U256::cl_type()),
+            Parameter::new("end", U256::cl_type()),
+            Parameter::new("step", U256::cl_type()),
+        ],
+        Vec::<U256>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "record_block_height",
+        vec![Parameter::new("block", U256::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_block_height_feeder",
+        vec![Parameter::new("addr", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "change_controller",
         vec![Parameter::new("new_controller", Key::cl_type())],
@@ -454,6 +1015,61 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "create_boost",
+        vec![
+            Parameter::new("receiver", Key::cl_type()),
+            Parameter::new("percentage", U256::cl_type()),
+            Parameter::new("cancel_time", U256::cl_type()),
+            Parameter::new("expire_time", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "extend_boost",
+        vec![
+            Parameter::new("receiver", Key::cl_type()),
+            Parameter::new("percentage", U256::cl_type()),
+            Parameter::new("cancel_time", U256::cl_type()),
+            Parameter::new("expire_time", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_boost",
+        vec![
+            Parameter::new("delegator", Key::cl_type()),
+            Parameter::new("receiver", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegated_balance",
+        vec![Parameter::new("addr", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "received_balance",
+        vec![Parameter::new("addr", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "adjusted_balance_of",
+        vec![Parameter::new("addr", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     // Variables
     entry_points.add_entry_point(EntryPoint::new(
         "token",
@@ -514,6 +1130,16 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "boosts",
+        vec![
+            Parameter::new("delegator", Key::cl_type()),
+            Parameter::new("receiver", Key::cl_type()),
+        ],
+        data::Boost::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "controller",
         vec![],
@@ -570,6 +1196,76 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "perpetual_locks",
+        vec![Parameter::new("addr", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "unlocked",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "early_withdraw_enabled",
+        vec![],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "penalty_receiver",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "approved_depositors",
+        vec![Parameter::new("addr", Key::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "block_height_feeder",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "block_height_epoch",
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "block_height_history",
+        vec![Parameter::new("idx", U256::cl_type())],
+        data::BlockHeight::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "smart_wallet_checker",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "future_smart_wallet_checker",
+        vec![],
+        Key::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points
 }
 